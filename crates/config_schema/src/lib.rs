@@ -0,0 +1,290 @@
+//! Settings schema shared by `mzoon` (`MoonZoon.toml`) and `moon` (env variables).
+
+use std::{fmt, path::Path};
+
+pub const PUBLIC_PEM_PATH: &str = "backend/private/public.pem";
+pub const PRIVATE_PEM_PATH: &str = "backend/private/private.pem";
+
+// ------ ValueKind ------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Port,
+    Bool,
+    LogLevel,
+    /// Comma-separated in env variables, an array in `MoonZoon.toml`.
+    List,
+    String,
+}
+
+impl ValueKind {
+    pub fn expected(&self) -> &'static str {
+        match self {
+            Self::Port => "a port number (0 - 65535)",
+            Self::Bool => "`true` or `false`",
+            Self::LogLevel => "one of `off`, `error`, `warn`, `info`, `debug`, `trace`",
+            Self::List => "a comma-separated list",
+            Self::String => "a string",
+        }
+    }
+
+    /// Validates a value in the env variable format.
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        let valid = match self {
+            Self::Port => value.parse::<u16>().is_ok(),
+            Self::Bool => matches!(value, "true" | "false"),
+            Self::LogLevel => ["off", "error", "warn", "info", "debug", "trace"]
+                .iter()
+                .any(|level| level.eq_ignore_ascii_case(value)),
+            Self::List | Self::String => true,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("expected {}, found '{value}'", self.expected()))
+        }
+    }
+}
+
+// ------ Setting ------
+
+#[derive(Debug)]
+pub struct Setting {
+    /// `None` for settings derived by `mzoon` from the build mode and CLI arguments.
+    pub toml_key: Option<&'static str>,
+    pub env_var: &'static str,
+    pub kind: ValueKind,
+}
+
+impl Setting {
+    const fn new(toml_key: &'static str, env_var: &'static str, kind: ValueKind) -> Self {
+        Self {
+            toml_key: Some(toml_key),
+            env_var,
+            kind,
+        }
+    }
+
+    const fn derived(env_var: &'static str, kind: ValueKind) -> Self {
+        Self {
+            toml_key: None,
+            env_var,
+            kind,
+        }
+    }
+
+    pub fn name(&self, source: Source) -> &'static str {
+        match (source, self.toml_key) {
+            (Source::Toml(_), Some(toml_key)) => toml_key,
+            _ => self.env_var,
+        }
+    }
+}
+
+pub static PORT: Setting = Setting::new("port", "PORT", ValueKind::Port);
+pub static HTTPS: Setting = Setting::new("https", "HTTPS", ValueKind::Bool);
+pub static CACHE_BUSTING: Setting = Setting::new("cache_busting", "CACHE_BUSTING", ValueKind::Bool);
+pub static BACKEND_LOG_LEVEL: Setting =
+    Setting::new("backend_log_level", "BACKEND_LOG_LEVEL", ValueKind::LogLevel);
+pub static FRONTEND_MULTITHREADING: Setting = Setting::new(
+    "frontend_multithreading",
+    "FRONTEND_MULTITHREADING",
+    ValueKind::Bool,
+);
+pub static REDIRECT_PORT: Setting = Setting::new("redirect.port", "REDIRECT_PORT", ValueKind::Port);
+pub static REDIRECT_ENABLED: Setting =
+    Setting::new("redirect.enabled", "REDIRECT_ENABLED", ValueKind::Bool);
+pub static CORS_ORIGINS: Setting = Setting::new("cors.origins", "CORS_ORIGINS", ValueKind::List);
+pub static COMPRESSED_PKG: Setting = Setting::derived("COMPRESSED_PKG", ValueKind::Bool);
+pub static FRONTEND_DIST: Setting = Setting::derived("FRONTEND_DIST", ValueKind::Bool);
+pub static FRONTEND_AUTO_RELOAD: Setting =
+    Setting::derived("FRONTEND_AUTO_RELOAD", ValueKind::Bool);
+pub static PROFILE: Setting = Setting::derived("PROFILE", ValueKind::String);
+pub static OTLP_ENDPOINT: Setting =
+    Setting::new("otlp.endpoint", "OTLP_ENDPOINT", ValueKind::String);
+pub static OTLP_SERVICE_NAME: Setting =
+    Setting::new("otlp.service_name", "OTLP_SERVICE_NAME", ValueKind::String);
+pub static BACKPLANE_ADDRESS: Setting =
+    Setting::new("backplane.address", "BACKPLANE_ADDRESS", ValueKind::String);
+pub static BACKPLANE_INSTANCE_ID: Setting = Setting::new(
+    "backplane.instance_id",
+    "BACKPLANE_INSTANCE_ID",
+    ValueKind::String,
+);
+
+pub static SETTINGS: [&Setting; 16] = [
+    &PORT,
+    &HTTPS,
+    &CACHE_BUSTING,
+    &BACKEND_LOG_LEVEL,
+    &FRONTEND_MULTITHREADING,
+    &REDIRECT_PORT,
+    &REDIRECT_ENABLED,
+    &CORS_ORIGINS,
+    &COMPRESSED_PKG,
+    &FRONTEND_DIST,
+    &FRONTEND_AUTO_RELOAD,
+    &PROFILE,
+    &OTLP_ENDPOINT,
+    &OTLP_SERVICE_NAME,
+    &BACKPLANE_ADDRESS,
+    &BACKPLANE_INSTANCE_ID,
+];
+
+/// Env variables starting with these prefixes belong to tables with known keys.
+pub const ENV_VAR_TABLE_PREFIXES: [&str; 4] = ["REDIRECT_", "CORS_", "OTLP_", "BACKPLANE_"];
+
+pub fn setting_by_env_var(env_var: &str) -> Option<&'static Setting> {
+    SETTINGS
+        .iter()
+        .copied()
+        .find(|setting| setting.env_var == env_var)
+}
+
+// ------ Source ------
+
+#[derive(Debug, Clone, Copy)]
+pub enum Source<'a> {
+    Toml(&'a str),
+    EnvVars,
+}
+
+impl fmt::Display for Source<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Toml(file_name) => write!(f, "{file_name}"),
+            Self::EnvVars => write!(f, "env variables"),
+        }
+    }
+}
+
+// ------ Problem ------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProblemCode {
+    RedirectPortConflict,
+    MissingPemFiles,
+    NoCorsOrigins,
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    pub code: ProblemCode,
+    pub severity: Severity,
+    pub settings: Vec<&'static Setting>,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn describe(&self, source: Source) -> String {
+        let settings = self
+            .settings
+            .iter()
+            .map(|setting| format!("`{}`", setting.name(source)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{source}: {settings}: {}", self.message)
+    }
+}
+
+// ------ Settings ------
+
+/// Loaded values relevant for the combination rules.
+pub struct Settings {
+    pub port: u16,
+    pub https: bool,
+    pub redirect_port: u16,
+    pub redirect_enabled: bool,
+    pub cors_origins_empty: bool,
+}
+
+impl Settings {
+    pub fn check_combinations(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+
+        if self.redirect_enabled && self.redirect_port == self.port {
+            problems.push(Problem {
+                code: ProblemCode::RedirectPortConflict,
+                severity: Severity::Error,
+                settings: vec![&REDIRECT_PORT, &PORT],
+                message: format!(
+                    "the redirect port has to differ from the app port {}",
+                    self.port
+                ),
+            });
+        }
+
+        if self.https {
+            let missing_pem_files = missing_pem_files(Path::new("."));
+            if !missing_pem_files.is_empty() {
+                problems.push(Problem {
+                    code: ProblemCode::MissingPemFiles,
+                    severity: Severity::Error,
+                    settings: vec![&HTTPS],
+                    message: format!("HTTPS requires the files {missing_pem_files:?}"),
+                });
+            }
+        }
+
+        if self.cors_origins_empty {
+            problems.push(Problem {
+                code: ProblemCode::NoCorsOrigins,
+                severity: Severity::Warning,
+                settings: vec![&CORS_ORIGINS],
+                message: "no cross-origin requests will be allowed; use \"*\" to allow all origins"
+                    .to_owned(),
+            });
+        }
+
+        problems
+    }
+}
+
+pub fn missing_pem_files(project_root: &Path) -> Vec<&'static str> {
+    [PUBLIC_PEM_PATH, PRIVATE_PEM_PATH]
+        .into_iter()
+        .filter(|path| !project_root.join(path).is_file())
+        .collect()
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_validation() {
+        assert!(ValueKind::Port.validate("8080").is_ok());
+        assert!(ValueKind::Port.validate("80800").is_err());
+        assert!(ValueKind::Bool.validate("yes").is_err());
+        assert!(ValueKind::LogLevel.validate("WARN").is_ok());
+        assert!(ValueKind::LogLevel.validate("verbose").is_err());
+    }
+
+    #[test]
+    fn test_redirect_port_conflict() {
+        let settings = Settings {
+            port: 8080,
+            https: false,
+            redirect_port: 8080,
+            redirect_enabled: true,
+            cors_origins_empty: false,
+        };
+        let problems = settings.check_combinations();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].code, ProblemCode::RedirectPortConflict);
+        assert!(problems[0]
+            .describe(Source::EnvVars)
+            .starts_with("env variables: `REDIRECT_PORT`, `PORT`"));
+        assert!(problems[0]
+            .describe(Source::Toml("MoonZoon.toml"))
+            .starts_with("MoonZoon.toml: `redirect.port`, `port`"));
+    }
+}
@@ -0,0 +1,252 @@
+use crate::Lang;
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use std::{borrow::Cow, collections::BTreeMap, fmt};
+
+// ------ Catalog ------
+
+/// Translated messages in [Fluent](https://projectfluent.org/) format, one or more `.ftl` files per language.
+///
+/// ```ftl
+/// hello = Hello, { $name }!
+/// unread-emails = { $count ->
+///     [one] You have one unread email.
+///    *[other] You have { $count } unread emails.
+/// }
+/// ```
+///
+/// Plural categories (`zero`, `one`, `two`, `few`, `many` and `other`) are selected by the language rules.
+pub struct Catalog {
+    fallback: Lang,
+    bundles: BTreeMap<Lang, FluentBundle<FluentResource>>,
+}
+
+impl Catalog {
+    /// Messages missing in the requested language are taken from the `fallback` language.
+    pub fn new(fallback: Lang) -> Self {
+        Self {
+            fallback,
+            bundles: BTreeMap::new(),
+        }
+    }
+
+    pub fn fallback(&self) -> &Lang {
+        &self.fallback
+    }
+
+    pub fn langs(&self) -> Vec<Lang> {
+        self.bundles.keys().cloned().collect()
+    }
+
+    /// Adds messages to the language, messages with the same id are replaced.
+    /// Valid messages are added even when the file contains syntax errors.
+    pub fn add_ftl(&mut self, lang: Lang, ftl: impl Into<String>) -> Result<(), CatalogError> {
+        let (resource, errors) = match FluentResource::try_new(ftl.into()) {
+            Ok(resource) => (resource, Vec::new()),
+            Err((resource, errors)) => (resource, errors),
+        };
+        self.bundles
+            .entry(lang.clone())
+            .or_insert_with(|| {
+                let langid = lang.as_str().parse().unwrap_or_default();
+                let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+                // Unicode isolation marks around arguments would end up in text inputs and titles.
+                bundle.set_use_isolating(false);
+                bundle
+            })
+            .add_resource_overriding(resource);
+
+        if errors.is_empty() {
+            return Ok(());
+        }
+        Err(CatalogError {
+            lang,
+            errors: errors.iter().map(ToString::to_string).collect(),
+        })
+    }
+
+    /// Formats the message `id` (or its attribute with `id.attribute`).
+    /// Tries `lang`, `lang` without its region (`en` for `en-US`) and then the fallback language.
+    /// Returns `id` when the message doesn't exist.
+    pub fn translate(&self, lang: &Lang, id: &str, args: &Args) -> String {
+        let (message_id, attribute) = match id.split_once('.') {
+            Some((message_id, attribute)) => (message_id, Some(attribute)),
+            None => (id, None),
+        };
+        let fluent_args = args.to_fluent_args();
+        [lang.clone(), lang.without_subtags(), self.fallback.clone()]
+            .iter()
+            .find_map(|lang| {
+                let bundle = self.bundles.get(lang)?;
+                let message = bundle.get_message(message_id)?;
+                let pattern = match attribute {
+                    Some(attribute) => message.get_attribute(attribute)?.value(),
+                    None => message.value()?,
+                };
+                let mut errors = Vec::new();
+                Some(
+                    bundle
+                        .format_pattern(pattern, fluent_args.as_ref(), &mut errors)
+                        .into_owned(),
+                )
+            })
+            .unwrap_or_else(|| id.to_owned())
+    }
+}
+
+// ------ Args ------
+
+/// Values of message arguments, e.g. `$name` or `$count`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Args(Vec<(Cow<'static, str>, ArgValue)>);
+
+impl Args {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(mut self, name: impl Into<Cow<'static, str>>, value: impl Into<ArgValue>) -> Self {
+        self.0.push((name.into(), value.into()));
+        self
+    }
+
+    fn to_fluent_args(&self) -> Option<FluentArgs<'_>> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let mut args = FluentArgs::new();
+        for (name, value) in &self.0 {
+            let value = match value {
+                ArgValue::String(string) => FluentValue::from(string.as_str()),
+                ArgValue::Number(number) => FluentValue::from(*number),
+            };
+            args.set(name.as_ref(), value);
+        }
+        Some(args)
+    }
+}
+
+// ------ ArgValue ------
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgValue {
+    String(String),
+    Number(f64),
+}
+
+impl From<String> for ArgValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for ArgValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+macro_rules! make_arg_value_impls {
+    ($($type:ty),*) => (
+        $(
+        impl From<$type> for ArgValue {
+            fn from(value: $type) -> Self {
+                Self::Number(value as f64)
+            }
+        }
+        )*
+    )
+}
+make_arg_value_impls!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+// ------ CatalogError ------
+
+#[derive(Debug, Clone)]
+pub struct CatalogError {
+    pub lang: Lang,
+    pub errors: Vec<String>,
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid messages for '{}': {}",
+            self.lang,
+            self.errors.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EN: &str = "
+hello = Hello, { $name }!
+emails = { $count ->
+    [one] One email
+   *[other] { $count } emails
+}
+button = Save
+    .title = Save changes
+";
+
+    const CS: &str = "
+emails = { $count ->
+    [one] { $count } e-mail
+    [few] { $count } e-maily
+   *[other] { $count } e-mailů
+}
+";
+
+    fn catalog() -> Catalog {
+        let mut catalog = Catalog::new(Lang::English);
+        catalog.add_ftl(Lang::English, EN).unwrap();
+        catalog.add_ftl(Lang::Czech, CS).unwrap();
+        catalog
+    }
+
+    #[test]
+    fn plurals() {
+        let catalog = catalog();
+        let emails =
+            |lang, count| catalog.translate(lang, "emails", &Args::new().set("count", count));
+        assert_eq!(emails(&Lang::English, 1), "One email");
+        assert_eq!(emails(&Lang::English, 5), "5 emails");
+        assert_eq!(emails(&Lang::Czech, 3), "3 e-maily");
+        assert_eq!(emails(&Lang::Czech, 5), "5 e-mailů");
+    }
+
+    #[test]
+    fn fallbacks() {
+        let catalog = catalog();
+        let args = Args::new().set("name", "Jana");
+        assert_eq!(
+            catalog.translate(&Lang::Czech, "hello", &args),
+            "Hello, Jana!"
+        );
+        let en_us = Lang::new("en-US").unwrap();
+        assert_eq!(
+            catalog.translate(&en_us, "button.title", &Args::new()),
+            "Save changes"
+        );
+        assert_eq!(
+            catalog.translate(&Lang::Czech, "missing", &Args::new()),
+            "missing"
+        );
+    }
+
+    #[test]
+    fn syntax_errors() {
+        let mut catalog = Catalog::new(Lang::English);
+        assert!(catalog
+            .add_ftl(Lang::English, "valid = Yes\ninvalid = {")
+            .is_err());
+        assert_eq!(
+            catalog.translate(&Lang::English, "valid", &Args::new()),
+            "Yes"
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! Locale-aware formatting of numbers, dates and times.
+//!
+//! Only the most common conventions are covered, formats of unknown languages fall back to English or ISO 8601.
+
+use crate::Lang;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+// ------ format_number ------
+
+/// `format_number(&Lang::Czech, 12345.678, 2)` returns `"12 345,68"`.
+pub fn format_number(lang: &Lang, number: f64, fraction_digits: usize) -> String {
+    let Separators {
+        decimal,
+        group,
+        min_grouping_digits,
+    } = Separators::new(lang);
+
+    let formatted = format!("{:.*}", fraction_digits, number.abs());
+    let (integer, fraction) = formatted
+        .split_once('.')
+        .unwrap_or((formatted.as_str(), ""));
+
+    let mut output = String::new();
+    if number.is_sign_negative()
+        && formatted
+            .chars()
+            .any(|char| char.is_ascii_digit() && char != '0')
+    {
+        output.push('-');
+    }
+    let digit_count = integer.len();
+    for (index, digit) in integer.chars().enumerate() {
+        let remaining = digit_count - index;
+        if index > 0 && remaining % 3 == 0 && digit_count >= 3 + min_grouping_digits {
+            output.push(group);
+        }
+        output.push(digit);
+    }
+    if !fraction.is_empty() {
+        output.push(decimal);
+        output.push_str(fraction);
+    }
+    output
+}
+
+struct Separators {
+    decimal: char,
+    group: char,
+    /// Digits required before the first group separator, e.g. Spanish writes `1000` but `10.000`.
+    min_grouping_digits: usize,
+}
+
+impl Separators {
+    fn new(lang: &Lang) -> Self {
+        let (decimal, group) = match lang.language() {
+            "de" | "nl" | "it" | "es" | "pt" | "da" | "id" | "tr" | "el" => (',', '.'),
+            "cs" | "sk" | "pl" | "ru" | "uk" | "sv" | "no" | "nb" | "nn" | "fi" | "hu" => {
+                (',', '\u{a0}')
+            }
+            "fr" => (',', '\u{202f}'),
+            _ => ('.', ','),
+        };
+        let min_grouping_digits = match lang.language() {
+            "es" | "pl" => 2,
+            _ => 1,
+        };
+        Self {
+            decimal,
+            group,
+            min_grouping_digits,
+        }
+    }
+}
+
+// ------ format_date ------
+
+/// `format_date(&Lang::Czech, date)` returns `"24. 12. 2023"`, `en-US` returns `"12/24/2023"`.
+pub fn format_date(lang: &Lang, date: NaiveDate) -> String {
+    let (year, month, day) = (date.year(), date.month(), date.day());
+    match (lang.language(), lang.region()) {
+        ("en", None | Some("US")) => format!("{month}/{day}/{year}"),
+        ("en", _) | ("fr" | "es" | "it" | "pt" | "el", _) => {
+            format!("{day:02}/{month:02}/{year}")
+        }
+        ("cs" | "sk", _) => format!("{day}. {month}. {year}"),
+        ("de" | "da" | "fi" | "no" | "nb" | "nn" | "pl" | "ru" | "uk" | "tr", _) => {
+            format!("{day:02}.{month:02}.{year}")
+        }
+        ("nl", _) => format!("{day:02}-{month:02}-{year}"),
+        ("ja" | "zh", _) => format!("{year}/{month:02}/{day:02}"),
+        _ => format!("{year}-{month:02}-{day:02}"),
+    }
+}
+
+// ------ format_time ------
+
+/// `format_time(&Lang::English, time)` returns `"3:05 PM"`, other languages mostly use `"15:05"`.
+pub fn format_time(lang: &Lang, time: NaiveTime) -> String {
+    let (hour, minute) = (time.hour(), time.minute());
+    match (lang.language(), lang.region()) {
+        ("en", None | Some("US" | "CA" | "AU" | "NZ" | "IN" | "PH")) => {
+            let (is_pm, hour) = time.hour12();
+            let period = if is_pm { "PM" } else { "AM" };
+            format!("{hour}:{minute:02} {period}")
+        }
+        _ => format!("{hour:02}:{minute:02}"),
+    }
+}
+
+// ------ format_date_time ------
+
+pub fn format_date_time(lang: &Lang, date_time: NaiveDateTime) -> String {
+    format!(
+        "{} {}",
+        format_date(lang, date_time.date()),
+        format_time(lang, date_time.time())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(
+            format_number(&Lang::English, 1234567.891, 2),
+            "1,234,567.89"
+        );
+        assert_eq!(format_number(&Lang::Czech, -12345.6, 1), "-12\u{a0}345,6");
+        assert_eq!(format_number(&Lang::German, 999.0, 0), "999");
+        assert_eq!(format_number(&Lang::Spanish, 1000.0, 0), "1000");
+        assert_eq!(format_number(&Lang::Spanish, 10000.0, 0), "10.000");
+        assert_eq!(format_number(&Lang::English, -0.001, 2), "0.00");
+    }
+
+    #[test]
+    fn dates_and_times() {
+        let date_time = NaiveDate::from_ymd_opt(2023, 12, 4)
+            .unwrap()
+            .and_hms_opt(15, 5, 0)
+            .unwrap();
+        let format = |tag: &str| format_date_time(&Lang::new(tag).unwrap(), date_time);
+        assert_eq!(format("en-US"), "12/4/2023 3:05 PM");
+        assert_eq!(format("en-GB"), "04/12/2023 15:05");
+        assert_eq!(format("cs"), "4. 12. 2023 15:05");
+        assert_eq!(format("de-AT"), "04.12.2023 15:05");
+        assert_eq!(format("sv"), "2023-12-04 15:05");
+    }
+}
@@ -1,34 +1,142 @@
 use serde::{Deserialize, Serialize};
-use std::{borrow::Cow, fmt};
+use std::{borrow::Cow, fmt, str::FromStr};
+use unic_langid::LanguageIdentifier;
+
+mod catalog;
+pub use catalog::{ArgValue, Args, Catalog, CatalogError};
+
+pub mod format;
 
 // ------ Lang ------
-// @TODO: Add remaining langs
 // @TODO optional `serde`?
+/// [BCP 47](https://www.rfc-editor.org/info/bcp47) language tag.
+/// Common languages have their own variants, other valid tags are stored in `Custom`.
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize, Default)]
 pub enum Lang {
     Czech,
+    Danish,
+    Dutch,
     #[default]
     English,
+    Finnish,
     French,
+    German,
+    Italian,
     // @TODO `no` vs `nb` vs `ny`
     Norwegian,
+    Polish,
+    Portuguese,
+    Slovak,
     Spanish,
     Swedish,
     Custom(Cow<'static, str>),
 }
 
 impl Lang {
+    const KNOWN: [Self; 14] = [
+        Self::Czech,
+        Self::Danish,
+        Self::Dutch,
+        Self::English,
+        Self::Finnish,
+        Self::French,
+        Self::German,
+        Self::Italian,
+        Self::Norwegian,
+        Self::Polish,
+        Self::Portuguese,
+        Self::Slovak,
+        Self::Spanish,
+        Self::Swedish,
+    ];
+
+    /// Validates the tag and normalizes its case, e.g. `en-us` becomes `en-US`.
+    pub fn new(tag: &str) -> Result<Self, LangError> {
+        let tag = tag
+            .parse::<LanguageIdentifier>()
+            .map_err(|_| LangError(tag.to_owned()))?
+            .to_string();
+        Ok(Self::KNOWN
+            .into_iter()
+            .find(|lang| lang.as_str() == tag)
+            .unwrap_or(Self::Custom(Cow::Owned(tag))))
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             Self::Czech => "cs",
+            Self::Danish => "da",
+            Self::Dutch => "nl",
             Self::English => "en",
+            Self::Finnish => "fi",
             Self::French => "fr",
+            Self::German => "de",
+            Self::Italian => "it",
             Self::Norwegian => "no",
+            Self::Polish => "pl",
+            Self::Portuguese => "pt",
+            Self::Slovak => "sk",
             Self::Spanish => "es",
             Self::Swedish => "sv",
             Self::Custom(lang) => lang,
         }
     }
+
+    /// Primary language subtag, e.g. `en` for `en-US`.
+    pub fn language(&self) -> &str {
+        self.subtags().next().unwrap_or_default()
+    }
+
+    /// Region subtag, e.g. `US` for `en-US`.
+    pub fn region(&self) -> Option<&str> {
+        self.subtags()
+            .skip(1)
+            .find(|subtag| subtag.len() == 2 || subtag.chars().all(|char| char.is_ascii_digit()))
+    }
+
+    /// The language without the region and other subtags, e.g. `Lang::English` for `en-US`.
+    pub fn without_subtags(&self) -> Self {
+        Self::new(self.language()).unwrap_or_else(|_| self.clone())
+    }
+
+    /// The best match of `requested` languages (ordered by preference) in `available` languages.
+    /// `en-US` matches `en-US`, then `en` and then `en-GB`.
+    pub fn negotiate<'a>(
+        requested: impl IntoIterator<Item = &'a Lang>,
+        available: &[Lang],
+    ) -> Option<Lang> {
+        requested.into_iter().find_map(|requested| {
+            let language = requested.language();
+            available
+                .iter()
+                .find(|lang| *lang == requested)
+                .or_else(|| available.iter().find(|lang| lang.as_str() == language))
+                .or_else(|| available.iter().find(|lang| lang.language() == language))
+                .cloned()
+        })
+    }
+
+    /// Negotiates the language from the `Accept-Language` header value, e.g. `cs-CZ,cs;q=0.9,en;q=0.8`.
+    pub fn from_accept_language(header: &str, available: &[Lang]) -> Option<Lang> {
+        let mut requested = header
+            .split(',')
+            .filter_map(|item| {
+                let mut parts = item.split(';');
+                let lang = Lang::new(parts.next()?.trim()).ok()?;
+                let quality = parts
+                    .find_map(|part| part.trim().strip_prefix("q="))
+                    .and_then(|quality| quality.parse::<f32>().ok())
+                    .unwrap_or(1.);
+                Some((lang, quality))
+            })
+            .collect::<Vec<_>>();
+        requested.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        Self::negotiate(requested.iter().map(|(lang, _)| lang), available)
+    }
+
+    fn subtags(&self) -> impl Iterator<Item = &str> {
+        self.as_str().split('-')
+    }
 }
 
 impl fmt::Display for Lang {
@@ -36,3 +144,55 @@ impl fmt::Display for Lang {
         write!(f, "{}", self.as_str())
     }
 }
+
+impl FromStr for Lang {
+    type Err = LangError;
+
+    fn from_str(tag: &str) -> Result<Self, Self::Err> {
+        Self::new(tag)
+    }
+}
+
+// ------ LangError ------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LangError(pub String);
+
+impl fmt::Display for LangError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid language tag '{}'", self.0)
+    }
+}
+
+impl std::error::Error for LangError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_normalizes_tags() {
+        assert_eq!(Lang::new("cs").unwrap(), Lang::Czech);
+        assert_eq!(Lang::new("en-us").unwrap().as_str(), "en-US");
+        assert_eq!(Lang::new("zh-hant-tw").unwrap().as_str(), "zh-Hant-TW");
+        assert!(Lang::new("not a tag").is_err());
+    }
+
+    #[test]
+    fn subtags() {
+        let lang = Lang::new("zh-Hant-TW").unwrap();
+        assert_eq!(lang.language(), "zh");
+        assert_eq!(lang.region(), Some("TW"));
+        assert_eq!(Lang::new("en-GB").unwrap().without_subtags(), Lang::English);
+    }
+
+    #[test]
+    fn from_accept_language() {
+        let available = [Lang::English, Lang::Czech, Lang::new("pt-BR").unwrap()];
+        let negotiate = |header| Lang::from_accept_language(header, &available);
+        assert_eq!(negotiate("cs-CZ,cs;q=0.9,en;q=0.8"), Some(Lang::Czech));
+        assert_eq!(negotiate("de;q=0.9,en-GB;q=0.8"), Some(Lang::English));
+        assert_eq!(negotiate("en;q=0.5,pt"), available.get(2).cloned());
+        assert_eq!(negotiate("de"), None);
+    }
+}
@@ -1,9 +1,11 @@
 pub mod index;
 pub mod p_var;
 pub mod sessions;
+pub(crate) mod storage;
 
-pub use index::Index;
+pub use index::{Index, WAIT_FOR_TIMEOUT};
 pub use p_var::PVar;
+use std::{fmt, str::FromStr};
 use uuid::Uuid;
 
 // ------ ActorId ------
@@ -17,6 +19,20 @@ impl ActorId {
     }
 }
 
+impl fmt::Display for ActorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for ActorId {
+    type Err = uuid::Error;
+
+    fn from_str(actor_id: &str) -> Result<Self, Self::Err> {
+        Ok(ActorId(actor_id.parse()?))
+    }
+}
+
 // ------ ActorInstance ------
 
 pub trait ActorInstance {
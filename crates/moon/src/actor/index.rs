@@ -1,37 +1,129 @@
 use crate::actor::{ActorId, PVar};
 use async_trait::async_trait;
 use std::borrow::Borrow;
+use tokio::sync::Notify;
+use tokio::time::{timeout_at, Duration, Instant};
+
+/// How long `wait_for` waits for the key before it returns `None`.
+pub const WAIT_FOR_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[async_trait(?Send)]
 pub trait Index {
     type PVar: PVar;
     type Actor;
 
-    fn insert(&self, _key: <Self::PVar as PVar>::Value, _actor_id: ActorId) {
-        todo!()
-    }
+    /// Implementations have to call `notify_waiters` on `inserted()` after each insert.
+    fn insert(&self, key: <Self::PVar as PVar>::Value, actor_id: ActorId);
 
-    fn get(&self, _key: impl Borrow<<Self::PVar as PVar>::Value>) -> Option<Self::Actor> {
-        todo!()
-    }
+    fn get(&self, key: impl Borrow<<Self::PVar as PVar>::Value>) -> Option<Self::Actor>;
 
-    fn for_each(&self, _f: impl FnMut(<Self::PVar as PVar>::Value, Self::Actor)) {
-        todo!()
-    }
+    fn for_each(&self, f: impl FnMut(<Self::PVar as PVar>::Value, Self::Actor));
+
+    /// Notified on every insert, `wait_for` can't wake up without it.
+    fn inserted(&self) -> &Notify;
 
+    /// Waits until the key is inserted or `WAIT_FOR_TIMEOUT` elapses.
     async fn wait_for(
         &self,
         key: impl Borrow<<Self::PVar as PVar>::Value> + 'static,
     ) -> Option<Self::Actor> {
-        // @TODO backoff + jitter + queue or something else?
+        self.wait_for_until(key, Instant::now() + WAIT_FOR_TIMEOUT)
+            .await
+    }
+
+    /// Waits until the key is inserted or the `deadline` is reached.
+    async fn wait_for_until(
+        &self,
+        key: impl Borrow<<Self::PVar as PVar>::Value> + 'static,
+        deadline: Instant,
+    ) -> Option<Self::Actor> {
         let key = key.borrow();
-        for i in 0..10 {
-            let session = self.get(key);
-            if session.is_some() {
-                return session;
+        loop {
+            // Create the future before `get` so an insert between them isn't missed
+            // (`notify_waiters` wakes all `Notified` futures created before the call).
+            let inserted = self.inserted().notified();
+            if let Some(actor) = self.get(key) {
+                return Some(actor);
+            }
+            if timeout_at(deadline, inserted).await.is_err() {
+                return self.get(key);
             }
-            tokio::time::sleep(tokio::time::Duration::from_millis(i * 200)).await;
         }
-        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    struct TestPVar(ActorId);
+    impl PVar for TestPVar {
+        type Value = u32;
+
+        fn actor_id(&self) -> ActorId {
+            self.0
+        }
+    }
+
+    #[derive(Default)]
+    struct TestIndex {
+        actors: Mutex<HashMap<u32, ActorId>>,
+        inserted: Notify,
+    }
+    impl Index for TestIndex {
+        type PVar = TestPVar;
+        type Actor = ActorId;
+
+        fn insert(&self, key: u32, actor_id: ActorId) {
+            self.actors.lock().unwrap().insert(key, actor_id);
+            self.inserted.notify_waiters();
+        }
+
+        fn get(&self, key: impl Borrow<u32>) -> Option<ActorId> {
+            self.actors.lock().unwrap().get(key.borrow()).copied()
+        }
+
+        fn for_each(&self, mut f: impl FnMut(u32, ActorId)) {
+            for (key, actor_id) in self.actors.lock().unwrap().iter() {
+                f(*key, *actor_id);
+            }
+        }
+
+        fn inserted(&self) -> &Notify {
+            &self.inserted
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_resolves_on_insert() {
+        let index = Arc::new(TestIndex::default());
+        let actor_id = ActorId::new();
+
+        // The key is inserted only after `wait_for` has checked the index and started waiting.
+        let inserter = tokio::spawn({
+            let index = Arc::clone(&index);
+            async move {
+                tokio::task::yield_now().await;
+                index.insert(1, actor_id);
+            }
+        });
+        let deadline = Instant::now() + Duration::from_secs(5);
+        assert_eq!(index.wait_for_until(1, deadline).await, Some(actor_id));
+        inserter.await.unwrap();
+
+        // Already inserted keys are returned immediately.
+        assert_eq!(index.wait_for(1).await, Some(actor_id));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_returns_none_at_deadline() {
+        let index = TestIndex::default();
+        index.insert(1, ActorId::new());
+
+        let deadline = Instant::now() + Duration::from_millis(50);
+        assert_eq!(index.wait_for_until(2, deadline).await, None);
+        assert!(Instant::now() >= deadline);
     }
 }
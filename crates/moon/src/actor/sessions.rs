@@ -1,5 +1,7 @@
 use crate::actor::{ActorId, ActorInstance, Index, PVar};
+use crate::backplane::{self, BackplaneMessage};
 use crate::sse::ShareableSSEMethods;
+use crate::telemetry;
 use crate::MessageSSE;
 use chashmap::CHashMap;
 use futures::future::join_all;
@@ -7,20 +9,63 @@ use moonlight::{serde_json, CorId, DownMsgTransporterForSer, Serialize, SessionI
 use once_cell::sync::Lazy;
 use std::borrow::Borrow;
 use std::cell::RefCell;
+use tokio::sync::Notify;
+use tokio::time::{Duration, Instant};
+
+/// How long `send_down_msg` waits for a session connecting to this instance
+/// before it publishes the DownMsg through the backplane.
+const LOCAL_SESSION_TIMEOUT: Duration = Duration::from_millis(500);
 
 // @TODO rewrite to a proper virtual actor
 
+/// Sends the DownMsg to all sessions, including sessions connected to other Moon instances
+/// when the backplane is configured.
 pub async fn broadcast_down_msg<DMsg: Serialize>(down_msg: &DMsg, cor_id: CorId) {
     let mut send_down_msg_futs = vec![];
     by_session_id().for_each(|_, session_actor| {
         send_down_msg_futs.push(async move { session_actor.send_down_msg(down_msg, cor_id).await });
     });
     join_all(send_down_msg_futs).await;
+
+    if backplane::is_enabled() {
+        backplane::publish(BackplaneMessage::Broadcast {
+            data: down_msg_data(down_msg, cor_id),
+        });
+    }
+}
+
+/// Sends the DownMsg to the session connected to this Moon instance
+/// or publishes it through the backplane (if configured) to the instance holding the session.
+/// Without the backplane it waits for the session like `Index::wait_for`,
+/// with the backplane it waits only `LOCAL_SESSION_TIMEOUT` before publishing.
+pub async fn send_down_msg<DMsg: Serialize>(session_id: SessionId, down_msg: &DMsg, cor_id: CorId) {
+    if backplane::is_enabled() {
+        // The session may be still connecting to this instance.
+        let deadline = Instant::now() + LOCAL_SESSION_TIMEOUT;
+        if let Some(session_actor) = by_session_id().wait_for_until(session_id, deadline).await {
+            return session_actor.send_down_msg(down_msg, cor_id).await;
+        }
+        let _span = telemetry::down_msg_span(session_id, cor_id).entered();
+        return backplane::publish(BackplaneMessage::DownMsg {
+            session_id,
+            data: down_msg_data(down_msg, cor_id),
+        });
+    }
+    match by_session_id().wait_for(session_id).await {
+        Some(session_actor) => session_actor.send_down_msg(down_msg, cor_id).await,
+        None => log::warn!("DownMsg {cor_id} dropped, session {session_id} not found"),
+    }
+}
+
+fn down_msg_data<DMsg: Serialize>(down_msg: &DMsg, cor_id: CorId) -> String {
+    let down_msg_transporter = DownMsgTransporterForSer { down_msg, cor_id };
+    serde_json::to_string(&down_msg_transporter).unwrap()
 }
 
 // ------ Indices ------
 
 static BY_SESSION_ID: Lazy<CHashMap<SessionId, SessionActor>> = Lazy::new(CHashMap::new);
+static BY_SESSION_ID_INSERTED: Lazy<Notify> = Lazy::new(Notify::new);
 
 pub const fn by_session_id() -> BySessionId {
     BySessionId
@@ -34,6 +79,7 @@ impl Index for BySessionId {
 
     fn insert(&self, key: <Self::PVar as PVar>::Value, actor_id: ActorId) {
         BY_SESSION_ID.insert(key, SessionActor { actor_id });
+        BY_SESSION_ID_INSERTED.notify_waiters();
     }
 
     fn get(&self, key: impl Borrow<<Self::PVar as PVar>::Value>) -> Option<Self::Actor> {
@@ -49,6 +95,10 @@ impl Index for BySessionId {
             true
         });
     }
+
+    fn inserted(&self) -> &Notify {
+        &BY_SESSION_ID_INSERTED
+    }
 }
 
 // ------ PVars ------
@@ -117,6 +167,8 @@ impl SessionActor {
         }
     }
 
+    /// Sends the DownMsg only to the session connected to this Moon instance,
+    /// use [`send_down_msg`] to reach sessions connected to other instances through the backplane.
     pub async fn send_down_msg<DMsg: Serialize>(&self, down_msg: &DMsg, cor_id: CorId) {
         if let Some(instance) = SESSION_ACTOR_INSTANCES.get(&self.actor_id) {
             instance.send_down_msg(down_msg, cor_id).await;
@@ -156,8 +208,6 @@ impl SessionActorInstance {
     fn create(session_id: SessionId, message_sse: MessageSSE) -> ActorId {
         let actor_id = ActorId::new();
 
-        by_session_id().insert(session_id, actor_id);
-
         let actor_instance = Self {
             actor_id,
             message_sse,
@@ -165,6 +215,9 @@ impl SessionActorInstance {
         };
         SESSION_ACTOR_INSTANCES.insert(actor_id, actor_instance);
 
+        // Insert into the index last to wake up `wait_for` callers with a ready actor.
+        by_session_id().insert(session_id, actor_id);
+
         println!(
             "New session: `{}`. (Session count: {})",
             session_id,
@@ -176,12 +229,9 @@ impl SessionActorInstance {
     pub async fn send_down_msg<DMsg: Serialize>(&self, down_msg: &DMsg, cor_id: CorId) {
         let session_id = self.session_id.read().unwrap();
 
-        let down_msg_transporter = DownMsgTransporterForSer { down_msg, cor_id };
-
-        #[cfg(feature = "serde")]
-        let down_msg_transporter = serde_json::to_string(&down_msg_transporter).unwrap();
+        let _span = telemetry::down_msg_span(session_id, cor_id).entered();
 
         self.message_sse
-            .send(&session_id, "down_msg", &down_msg_transporter);
+            .send(&session_id, "down_msg", &down_msg_data(down_msg, cor_id));
     }
 }
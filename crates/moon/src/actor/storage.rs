@@ -0,0 +1,64 @@
+//! Actor state stored as JSON files in `backend/private/actors/<ActorInstance::KEY>/<ActorId>.json`.
+
+use crate::actor::ActorId;
+use moonlight::{serde_json, DeserializeOwned, Serialize};
+use std::{
+    io::{self, ErrorKind},
+    path::PathBuf,
+};
+use tokio::fs;
+
+const STORAGE_PATH: &str = "backend/private/actors";
+
+pub(crate) async fn write<T: Serialize>(
+    actor_key: &str,
+    actor_id: ActorId,
+    value: &T,
+) -> io::Result<()> {
+    let folder = folder(actor_key);
+    fs::create_dir_all(&folder).await?;
+    let json =
+        serde_json::to_vec(value).map_err(|error| io::Error::new(ErrorKind::InvalidData, error))?;
+    // Write and rename to not leave a truncated file when the process is killed.
+    let temp_path = folder.join(format!("{actor_id}.json.tmp"));
+    fs::write(&temp_path, json).await?;
+    fs::rename(temp_path, folder.join(format!("{actor_id}.json"))).await
+}
+
+pub(crate) async fn remove(actor_key: &str, actor_id: ActorId) -> io::Result<()> {
+    match fs::remove_file(folder(actor_key).join(format!("{actor_id}.json"))).await {
+        Err(error) if error.kind() != ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+/// Values that can't be deserialized are skipped with a warning.
+pub(crate) async fn read_all<T: DeserializeOwned>(
+    actor_key: &str,
+) -> io::Result<Vec<(ActorId, T)>> {
+    let mut entries = match fs::read_dir(folder(actor_key)).await {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    let mut values = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let Some(actor_id) = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str()?.strip_suffix(".json"))
+            .and_then(|actor_id| actor_id.parse::<ActorId>().ok())
+        else {
+            continue;
+        };
+        match serde_json::from_slice(&fs::read(&path).await?) {
+            Ok(value) => values.push((actor_id, value)),
+            Err(error) => log::warn!("Skipping invalid actor state {path:?}: {error}"),
+        }
+    }
+    Ok(values)
+}
+
+fn folder(actor_key: &str) -> PathBuf {
+    PathBuf::from(STORAGE_PATH).join(actor_key)
+}
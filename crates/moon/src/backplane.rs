@@ -0,0 +1,672 @@
+use crate::config::CONFIG;
+use crate::sse::ShareableSSEMethods;
+use crate::MessageSSE;
+use moonlight::SessionId;
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::Mutex;
+use std::{
+    collections::VecDeque,
+    fmt,
+    io::{self, BufRead, BufReader, ErrorKind, Read, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, SyncSender, TrySendError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+#[cfg(unix)]
+use std::{
+    fs,
+    os::unix::net::{UnixListener, UnixStream},
+    path::PathBuf,
+};
+
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+// Frames for a slow peer are dropped when its queue is full.
+const MAX_QUEUED_FRAMES: usize = 1024;
+// A stuck peer is disconnected when a write doesn't complete in time.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+// NOTE: Sync with `Index::WAIT_FOR_TIMEOUT`.
+const PENDING_DOWN_MSG_TTL: Duration = Duration::from_secs(10);
+const MAX_PENDING_DOWN_MSGS: usize = 1024;
+
+static BACKPLANE: OnceCell<Box<dyn Backplane>> = OnceCell::new();
+
+// DownMsgs for sessions without an SSE connection on this instance,
+// delivered when the session connects.
+static PENDING_DOWN_MSGS: Lazy<Mutex<VecDeque<PendingDownMsg>>> = Lazy::new(<_>::default);
+
+static INSTANCE_ID: Lazy<String> = Lazy::new(|| {
+    CONFIG
+        .backplane
+        .instance_id
+        .clone()
+        .unwrap_or_else(|| Uuid::new_v4().to_string())
+});
+
+pub type MessageHandler = Arc<dyn Fn(BackplaneMessage) + Send + Sync>;
+
+// ------ Backplane ------
+
+/// Routes DownMsgs and broadcasts between Moon instances so a DownMsg reaches its session
+/// even when the session's SSE connection is held by another instance.
+pub trait Backplane: Send + Sync + 'static {
+    /// Delivers the message to all other instances connected to the backplane.
+    fn publish(&self, message: BackplaneMessage);
+
+    /// Registers the handler for messages published by other instances.
+    /// Called once by Moon on start.
+    fn subscribe(&self, handler: MessageHandler);
+}
+
+// ------ BackplaneMessage ------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackplaneMessage {
+    /// Serialized `DownMsgTransporter` for the session.
+    DownMsg { session_id: SessionId, data: String },
+    /// Serialized `DownMsgTransporter` for all sessions.
+    Broadcast { data: String },
+}
+
+impl BackplaneMessage {
+    /// One line, e.g. `down_msg <session_id> <data>` or `broadcast <data>`.
+    /// `data` is compact JSON so it never contains a line break.
+    fn to_frame(&self) -> String {
+        match self {
+            Self::DownMsg { session_id, data } => format!("down_msg {session_id} {data}\n"),
+            Self::Broadcast { data } => format!("broadcast {data}\n"),
+        }
+    }
+
+    fn from_frame(frame: &str) -> Option<Self> {
+        let frame = frame.trim_end_matches(['\r', '\n']);
+        let (kind, rest) = frame.split_once(' ')?;
+        match kind {
+            "down_msg" => {
+                let (session_id, data) = rest.split_once(' ')?;
+                Some(Self::DownMsg {
+                    session_id: session_id.parse().ok()?,
+                    data: data.to_owned(),
+                })
+            }
+            "broadcast" => Some(Self::Broadcast {
+                data: rest.to_owned(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+struct PendingDownMsg {
+    session_id: SessionId,
+    data: String,
+    received: Instant,
+}
+
+// ------ public ------
+
+/// Replaces the backplane created from `BACKPLANE_ADDRESS`. Call it before `start`.
+///
+/// # Panics
+///
+/// Panics when the backplane has been already set or Moon has been already started with a backplane.
+pub fn set_backplane(backplane: impl Backplane) {
+    if BACKPLANE.set(Box::new(backplane)).is_err() {
+        panic!("backplane has been already set");
+    }
+}
+
+/// `BACKPLANE_INSTANCE_ID` or a random UUID.
+/// Sent in the `X-Moon-Instance` header as a sticky-session hint for load balancers.
+pub fn instance_id() -> &'static str {
+    &INSTANCE_ID
+}
+
+// ------ private ------
+
+pub(crate) fn init(message_sse: MessageSSE) {
+    if let Some(address) = &CONFIG.backplane.address {
+        if BACKPLANE.get().is_none() {
+            let backplane = SocketBackplane::new(address)
+                .unwrap_or_else(|error| panic!("invalid BACKPLANE_ADDRESS: {error}"));
+            set_backplane(backplane);
+        }
+    }
+    let Some(backplane) = BACKPLANE.get() else {
+        return;
+    };
+    backplane.subscribe(Arc::new(move |message| match message {
+        // Sessions connected to another instance aren't in our SSE connections,
+        // but the session may be still connecting to this one.
+        BackplaneMessage::DownMsg { session_id, data } => {
+            // Locked before `send` to not miss `deliver_pending_down_msgs` of a new connection.
+            let mut pending_down_msgs = PENDING_DOWN_MSGS.lock();
+            if message_sse.send(&session_id, "down_msg", &data).is_none() {
+                pending_down_msgs
+                    .retain(|down_msg| down_msg.received.elapsed() < PENDING_DOWN_MSG_TTL);
+                if pending_down_msgs.len() >= MAX_PENDING_DOWN_MSGS {
+                    pending_down_msgs.pop_front();
+                }
+                pending_down_msgs.push_back(PendingDownMsg {
+                    session_id,
+                    data,
+                    received: Instant::now(),
+                });
+            }
+        }
+        BackplaneMessage::Broadcast { data } => {
+            let _ = message_sse.broadcast("down_msg", &data);
+        }
+    }));
+}
+
+/// Sends DownMsgs received through the backplane before the session's SSE connection was created.
+pub(crate) fn deliver_pending_down_msgs(session_id: SessionId, message_sse: &MessageSSE) {
+    if !is_enabled() {
+        return;
+    }
+    PENDING_DOWN_MSGS.lock().retain(|down_msg| {
+        if down_msg.session_id != session_id {
+            return down_msg.received.elapsed() < PENDING_DOWN_MSG_TTL;
+        }
+        let _ = message_sse.send(&session_id, "down_msg", &down_msg.data);
+        false
+    });
+}
+
+pub(crate) fn is_enabled() -> bool {
+    BACKPLANE.get().is_some()
+}
+
+pub(crate) fn publish(message: BackplaneMessage) {
+    if let Some(backplane) = BACKPLANE.get() {
+        backplane.publish(message);
+    }
+}
+
+// ------ InProcessBackplane ------
+
+/// Connects instances living in one process, e.g. in tests.
+/// Create the first one with `new` and the other ones with `connect`.
+pub struct InProcessBackplane {
+    peer_id: usize,
+    hub: Arc<InProcessHub>,
+}
+
+#[derive(Default)]
+struct InProcessHub {
+    next_peer_id: AtomicUsize,
+    handlers: Mutex<Vec<(usize, MessageHandler)>>,
+}
+
+impl InProcessBackplane {
+    pub fn new() -> Self {
+        Self {
+            peer_id: 0,
+            hub: Arc::new(InProcessHub {
+                next_peer_id: AtomicUsize::new(1),
+                ..InProcessHub::default()
+            }),
+        }
+    }
+
+    /// Creates another instance connected to the same backplane.
+    pub fn connect(&self) -> Self {
+        Self {
+            peer_id: self.hub.next_peer_id.fetch_add(1, Ordering::SeqCst),
+            hub: Arc::clone(&self.hub),
+        }
+    }
+}
+
+impl Default for InProcessBackplane {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Backplane for InProcessBackplane {
+    fn publish(&self, message: BackplaneMessage) {
+        // Clone handlers to not hold the lock while they run.
+        let handlers = self
+            .hub
+            .handlers
+            .lock()
+            .iter()
+            .filter(|(peer_id, _)| *peer_id != self.peer_id)
+            .map(|(_, handler)| Arc::clone(handler))
+            .collect::<Vec<_>>();
+        for handler in handlers {
+            handler(message.clone());
+        }
+    }
+
+    fn subscribe(&self, handler: MessageHandler) {
+        self.hub.handlers.lock().push((self.peer_id, handler));
+    }
+}
+
+// ------ SocketBackplane ------
+
+/// Backplane over TCP (`tcp://127.0.0.1:9100`) or a Unix socket (`unix:///tmp/moon_backplane.sock`).
+///
+/// The first instance that binds the address becomes the hub relaying messages
+/// between instances, the other ones connect to it. When the hub stops, another instance takes over.
+/// Messages published while an instance is reconnecting are dropped.
+/// Each peer is written by its own thread, so a slow peer doesn't block publishers.
+pub struct SocketBackplane {
+    shared: Arc<SocketShared>,
+}
+
+struct SocketShared {
+    address: SocketAddress,
+    next_peer_id: AtomicUsize,
+    peers: Mutex<Vec<Peer>>,
+}
+
+struct Peer {
+    id: usize,
+    frames: SyncSender<Arc<str>>,
+}
+
+impl SocketBackplane {
+    pub fn new(address: &str) -> Result<Self, String> {
+        Ok(Self {
+            shared: Arc::new(SocketShared {
+                address: SocketAddress::parse(address)?,
+                next_peer_id: AtomicUsize::new(0),
+                peers: Mutex::new(Vec::new()),
+            }),
+        })
+    }
+}
+
+impl Backplane for SocketBackplane {
+    fn publish(&self, message: BackplaneMessage) {
+        self.shared.send(&message.to_frame(), None);
+    }
+
+    fn subscribe(&self, handler: MessageHandler) {
+        let shared = Arc::clone(&self.shared);
+        thread::Builder::new()
+            .name("moon_backplane".to_owned())
+            .spawn(move || shared.run(handler))
+            .expect("failed to spawn the backplane thread");
+    }
+}
+
+impl SocketShared {
+    fn run(self: Arc<Self>, handler: MessageHandler) {
+        loop {
+            match self.address.bind() {
+                Ok(listener) => {
+                    log::info!("Backplane hub listening on {}", self.address);
+                    self.serve_as_hub(listener, &handler);
+                }
+                Err(error) if error.kind() == ErrorKind::AddrInUse => {
+                    match self.address.connect() {
+                        Ok(stream) => {
+                            log::info!("Backplane connected to the hub on {}", self.address);
+                            let peer_id = self.add_peer(&stream);
+                            read_frames(stream, |message, _| handler(message));
+                            self.remove_peer(peer_id);
+                            log::warn!("Backplane disconnected from the hub on {}", self.address);
+                        }
+                        Err(error) => {
+                            self.address.remove_stale_socket(&error);
+                            log::warn!("Backplane failed to connect to {}: {error}", self.address);
+                        }
+                    }
+                }
+                Err(error) => log::error!("Backplane failed to bind {}: {error}", self.address),
+            }
+            thread::sleep(RECONNECT_INTERVAL);
+        }
+    }
+
+    fn serve_as_hub(self: &Arc<Self>, listener: Listener, handler: &MessageHandler) {
+        loop {
+            let stream = match listener.accept() {
+                Ok(stream) => stream,
+                Err(error) => {
+                    log::warn!("Backplane hub failed to accept a connection: {error}");
+                    continue;
+                }
+            };
+            let peer_id = self.add_peer(&stream);
+            let this = Arc::clone(self);
+            let handler = Arc::clone(handler);
+            thread::spawn(move || {
+                read_frames(stream, |message, frame| {
+                    this.send(frame, Some(peer_id));
+                    handler(message);
+                });
+                this.remove_peer(peer_id);
+            });
+        }
+    }
+
+    fn add_peer(&self, stream: &Stream) -> usize {
+        let id = self.next_peer_id.fetch_add(1, Ordering::SeqCst);
+        let writer = stream.try_clone().and_then(|writer| {
+            writer
+                .set_write_timeout(Some(WRITE_TIMEOUT))
+                .map(|()| writer)
+        });
+        let mut writer = match writer {
+            Ok(writer) => writer,
+            Err(error) => {
+                log::error!("Backplane failed to clone a connection: {error}");
+                return id;
+            }
+        };
+        let (frames, frame_receiver) = mpsc::sync_channel::<Arc<str>>(MAX_QUEUED_FRAMES);
+        let spawned = thread::Builder::new()
+            .name("moon_backplane_writer".to_owned())
+            .spawn(move || {
+                for frame in frame_receiver {
+                    if let Err(error) = writer.write_all(frame.as_bytes()) {
+                        log::warn!("Backplane failed to write to a peer: {error}");
+                        // Ends the peer's reader, so the peer is removed or reconnected.
+                        writer.shutdown();
+                        return;
+                    }
+                }
+            });
+        match spawned {
+            Ok(_) => self.peers.lock().push(Peer { id, frames }),
+            Err(error) => log::error!("Backplane failed to spawn a writer thread: {error}"),
+        }
+        id
+    }
+
+    fn remove_peer(&self, peer_id: usize) {
+        self.peers.lock().retain(|peer| peer.id != peer_id);
+    }
+
+    /// Queues the frame for all connected peers except `except`, disconnected peers are removed.
+    /// It never blocks on socket I/O.
+    fn send(&self, frame: &str, except: Option<usize>) {
+        let frame = Arc::<str>::from(frame);
+        self.peers.lock().retain(|peer| {
+            if Some(peer.id) == except {
+                return true;
+            }
+            match peer.frames.try_send(Arc::clone(&frame)) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    log::warn!("Backplane dropped a frame for a slow peer");
+                    true
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            }
+        });
+    }
+}
+
+/// Calls `on_message` with each valid frame until the connection is closed.
+fn read_frames(stream: Stream, mut on_message: impl FnMut(BackplaneMessage, &str)) {
+    let mut reader = BufReader::new(stream);
+    let mut frame = String::new();
+    loop {
+        frame.clear();
+        match reader.read_line(&mut frame) {
+            Ok(0) => return,
+            Ok(_) => match BackplaneMessage::from_frame(&frame) {
+                Some(message) => on_message(message, &frame),
+                None => log::warn!("Backplane received an invalid frame: {frame}"),
+            },
+            Err(error) => {
+                log::warn!("Backplane failed to read from a peer: {error}");
+                return;
+            }
+        }
+    }
+}
+
+// ------ SocketAddress ------
+
+#[derive(Debug, Clone)]
+enum SocketAddress {
+    Tcp(String),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl SocketAddress {
+    fn parse(address: &str) -> Result<Self, String> {
+        if let Some(address) = address.strip_prefix("tcp://") {
+            return Ok(Self::Tcp(address.to_owned()));
+        }
+        if let Some(path) = address.strip_prefix("unix://") {
+            #[cfg(unix)]
+            return Ok(Self::Unix(PathBuf::from(path)));
+            #[cfg(not(unix))]
+            return Err(format!(
+                "Unix sockets aren't supported on this platform: '{path}'"
+            ));
+        }
+        Err(format!(
+            "expected 'tcp://<host>:<port>' or 'unix://<path>', found '{address}'"
+        ))
+    }
+
+    fn bind(&self) -> io::Result<Listener> {
+        Ok(match self {
+            Self::Tcp(address) => Listener::Tcp(TcpListener::bind(address)?),
+            #[cfg(unix)]
+            Self::Unix(path) => Listener::Unix(UnixListener::bind(path)?),
+        })
+    }
+
+    fn connect(&self) -> io::Result<Stream> {
+        Ok(match self {
+            Self::Tcp(address) => {
+                let stream = TcpStream::connect(address)?;
+                stream.set_nodelay(true)?;
+                Stream::Tcp(stream)
+            }
+            #[cfg(unix)]
+            Self::Unix(path) => Stream::Unix(UnixStream::connect(path)?),
+        })
+    }
+
+    /// The socket file of a crashed hub stays on the disk and prevents binding.
+    fn remove_stale_socket(&self, connect_error: &io::Error) {
+        if connect_error.kind() != ErrorKind::ConnectionRefused {
+            return;
+        }
+        match self {
+            Self::Tcp(_) => (),
+            #[cfg(unix)]
+            Self::Unix(path) => {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+}
+
+impl fmt::Display for SocketAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(address) => write!(f, "tcp://{address}"),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "unix://{}", path.display()),
+        }
+    }
+}
+
+// ------ Listener ------
+
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl Listener {
+    fn accept(&self) -> io::Result<Stream> {
+        Ok(match self {
+            Self::Tcp(listener) => {
+                let (stream, _) = listener.accept()?;
+                stream.set_nodelay(true)?;
+                Stream::Tcp(stream)
+            }
+            #[cfg(unix)]
+            Self::Unix(listener) => Stream::Unix(listener.accept()?.0),
+        })
+    }
+}
+
+// ------ Stream ------
+
+enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Stream {
+    fn try_clone(&self) -> io::Result<Self> {
+        Ok(match self {
+            Self::Tcp(stream) => Self::Tcp(stream.try_clone()?),
+            #[cfg(unix)]
+            Self::Unix(stream) => Self::Unix(stream.try_clone()?),
+        })
+    }
+
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.set_write_timeout(timeout),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.set_write_timeout(timeout),
+        }
+    }
+
+    fn shutdown(&self) {
+        let _ = match self {
+            Self::Tcp(stream) => stream.shutdown(Shutdown::Both),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.shutdown(Shutdown::Both),
+        };
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::{self, Receiver};
+    use std::time::Instant;
+
+    fn subscribe(backplane: &impl Backplane) -> Receiver<BackplaneMessage> {
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+        backplane.subscribe(Arc::new(move |message| {
+            let _ = sender.lock().send(message);
+        }));
+        receiver
+    }
+
+    #[test]
+    fn test_frame_round_trip() {
+        let messages = [
+            BackplaneMessage::DownMsg {
+                session_id: SessionId::new(),
+                data: r#"{"down_msg":{"text":"a b"},"cor_id":"01"}"#.to_owned(),
+            },
+            BackplaneMessage::Broadcast {
+                data: r#"{"down_msg":"x y"}"#.to_owned(),
+            },
+        ];
+        for message in messages {
+            assert_eq!(
+                BackplaneMessage::from_frame(&message.to_frame()),
+                Some(message)
+            );
+        }
+        assert_eq!(BackplaneMessage::from_frame("unknown data\n"), None);
+    }
+
+    #[test]
+    fn test_in_process_backplane_skips_publisher() {
+        let a = InProcessBackplane::new();
+        let b = a.connect();
+        let c = b.connect();
+        let (a_messages, b_messages, c_messages) = (subscribe(&a), subscribe(&b), subscribe(&c));
+
+        let message = BackplaneMessage::Broadcast {
+            data: "{}".to_owned(),
+        };
+        b.publish(message.clone());
+
+        assert_eq!(a_messages.try_recv().ok(), Some(message.clone()));
+        assert_eq!(c_messages.try_recv().ok(), Some(message));
+        assert!(b_messages.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_socket_backplane_relays_between_instances() {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let address = format!("tcp://127.0.0.1:{port}");
+
+        let instances = [
+            SocketBackplane::new(&address).unwrap(),
+            SocketBackplane::new(&address).unwrap(),
+            SocketBackplane::new(&address).unwrap(),
+        ];
+        let receivers = instances.each_ref().map(subscribe);
+
+        let message = BackplaneMessage::Broadcast {
+            data: r#"{"down_msg":"ping"}"#.to_owned(),
+        };
+        // Messages are dropped until the instances elect the hub and connect to it.
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let (mut received_1, mut received_2) = (false, false);
+        while !(received_1 && received_2) {
+            assert!(Instant::now() < deadline, "instances haven't connected");
+            instances[0].publish(message.clone());
+            thread::sleep(Duration::from_millis(100));
+            received_1 |= receivers[1].try_iter().any(|received| received == message);
+            received_2 |= receivers[2].try_iter().any(|received| received == message);
+        }
+        assert!(receivers[0].try_recv().is_err());
+    }
+}
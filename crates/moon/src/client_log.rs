@@ -0,0 +1,53 @@
+use crate::Lazy;
+use log::Level;
+use moonlight::{ClientLogKind, ClientLogRecord, SessionId};
+use parking_lot::RwLock;
+
+type Sink = Box<dyn Fn(ClientLog) + Send + Sync>;
+
+static SINK: Lazy<RwLock<Sink>> = Lazy::new(|| RwLock::new(Box::new(log_client_log)));
+
+// ------ ClientLog ------
+
+/// A batch of frontend errors sent by Zoon's `client_log`.
+#[derive(Debug)]
+pub struct ClientLog {
+    /// `None` when the frontend hasn't created a `Connection` yet.
+    pub session_id: Option<SessionId>,
+    pub records: Vec<ClientLogRecord>,
+}
+
+// ------ public ------
+
+/// Replaces the default sink that writes frontend errors to the backend log
+/// (filtered by `BACKEND_LOG_LEVEL`).
+pub fn set_client_log_sink(sink: impl Fn(ClientLog) + Send + Sync + 'static) {
+    *SINK.write() = Box::new(sink);
+}
+
+// ------ private ------
+
+pub(crate) fn handle_client_log(client_log: ClientLog) {
+    (SINK.read())(client_log)
+}
+
+fn log_client_log(client_log: ClientLog) {
+    let session_id = client_log
+        .session_id
+        .map(|session_id| session_id.to_string())
+        .unwrap_or_else(|| "-".to_owned());
+
+    for ClientLogRecord {
+        kind,
+        message,
+        url,
+        timestamp: _,
+    } in client_log.records
+    {
+        let level = match kind {
+            ClientLogKind::Panic | ClientLogKind::ConsoleError => Level::Error,
+            ClientLogKind::RequestFailed => Level::Warn,
+        };
+        log::log!(target: "frontend", level, "[session {session_id}] {kind:?} at {url}: {message}");
+    }
+}
@@ -1,10 +1,15 @@
 use crate::from_env_vars::FromEnvVars;
+use config_schema::{Settings, Severity, Source};
 use log::LevelFilter;
 pub use once_cell::sync::Lazy;
 use serde::Deserialize;
 use std::{borrow::Cow, collections::BTreeSet};
 
-pub static CONFIG: Lazy<Config> = Lazy::new(Config::from_env_vars);
+pub static CONFIG: Lazy<Config> = Lazy::new(|| {
+    let config = Config::from_env_vars();
+    config.check_combinations();
+    config
+});
 
 #[derive(Debug, Deserialize)]
 #[serde(default)]
@@ -25,18 +30,49 @@ pub struct Config {
     pub frontend_auto_reload: bool,
     // FRONTEND_MULTITHREADING
     pub frontend_multithreading: bool,
+    // PROFILE
+    pub profile: Option<String>,
 
     #[serde(default = "Redirect::from_env_vars")]
     pub redirect: Redirect,
 
     #[serde(default = "Cors::from_env_vars")]
     pub cors: Cors,
+
+    #[serde(default = "Otlp::from_env_vars")]
+    pub otlp: Otlp,
+
+    #[serde(default = "Backplane::from_env_vars")]
+    pub backplane: Backplane,
 }
 
 impl FromEnvVars for Config {
     const ENTITY_NAME: &'static str = "Config";
 }
 
+impl Config {
+    fn check_combinations(&self) {
+        let settings = Settings {
+            port: self.port,
+            https: self.https,
+            redirect_port: self.redirect.port,
+            redirect_enabled: self.redirect.enabled,
+            cors_origins_empty: self.cors.origins.is_empty(),
+        };
+        let mut errors = Vec::new();
+        for problem in settings.check_combinations() {
+            let description = problem.describe(Source::EnvVars);
+            match problem.severity {
+                Severity::Error => errors.push(description),
+                Severity::Warning => eprintln!("Warning: {description}"),
+            }
+        }
+        if !errors.is_empty() {
+            panic!("invalid Config:\n{}", errors.join("\n"));
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -47,8 +83,11 @@ impl Default for Config {
             backend_log_level: LevelFilter::Warn,
             frontend_dist: false,
             frontend_multithreading: false,
+            profile: None,
             redirect: Redirect::default(),
             cors: Cors::default(),
+            otlp: Otlp::default(),
+            backplane: Backplane::default(),
             frontend_auto_reload: false,
         }
     }
@@ -96,3 +135,40 @@ impl Default for Cors {
         }
     }
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Otlp {
+    // OTLP_ENDPOINT="http://localhost:4318"
+    pub endpoint: Option<String>,
+    // OTLP_SERVICE_NAME
+    pub service_name: String,
+}
+
+impl FromEnvVars for Otlp {
+    const ENTITY_NAME: &'static str = "Otlp";
+    const ENV_PREFIX: &'static str = "OTLP_";
+}
+
+impl Default for Otlp {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            service_name: "moon".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Backplane {
+    // BACKPLANE_ADDRESS="tcp://127.0.0.1:9100" or "unix:///tmp/moon_backplane.sock"
+    pub address: Option<String>,
+    // BACKPLANE_INSTANCE_ID
+    pub instance_id: Option<String>,
+}
+
+impl FromEnvVars for Backplane {
+    const ENTITY_NAME: &'static str = "Backplane";
+    const ENV_PREFIX: &'static str = "BACKPLANE_";
+}
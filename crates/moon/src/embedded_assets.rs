@@ -0,0 +1,175 @@
+use crate::SharedData;
+use actix_http::{header, ContentEncoding};
+use actix_web::http::header::{CacheControl, CacheDirective, ETag, EntityTag, IfNoneMatch};
+use actix_web::{error, web, Error, HttpMessage, HttpRequest, HttpResponse};
+use once_cell::sync::Lazy;
+use std::collections::{BTreeSet, HashMap};
+
+// The tar archive is created by `mzoon build --embed-assets`.
+// It contains `frontend/pkg`, `public` and `web_workers/{crate_name}/pkg` folders.
+static ASSETS_TAR: &[u8] = include_bytes!(env!(
+    "MOON_EMBEDDED_ASSETS",
+    "the feature `embedded_assets` requires env variable `MOON_EMBEDDED_ASSETS`, build the app with `mzoon build --embed-assets`"
+));
+
+static ASSETS: Lazy<HashMap<String, &'static [u8]>> = Lazy::new(|| {
+    let mut assets = HashMap::new();
+    let mut archive = tar::Archive::new(ASSETS_TAR);
+    for entry in archive
+        .entries()
+        .expect("embedded assets archive is invalid")
+    {
+        let entry = entry.expect("embedded assets archive entry is invalid");
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry
+            .path()
+            .expect("embedded asset path is invalid")
+            .to_string_lossy()
+            .replace('\\', "/");
+        // File contents are stored uncompressed in the archive
+        // so we can borrow them without copying.
+        let start = entry.raw_file_position() as usize;
+        let end = start + entry.size() as usize;
+        assets.insert(path, &ASSETS_TAR[start..end]);
+    }
+    assets
+});
+
+// -- public --
+
+pub(crate) fn asset(path: &str) -> Option<&'static [u8]> {
+    ASSETS.get(path).copied()
+}
+
+// ------ pkg_responder ------
+
+pub(crate) async fn pkg_responder(
+    req: HttpRequest,
+    file: web::Path<String>,
+    shared_data: web::Data<SharedData>,
+) -> Result<HttpResponse, Error> {
+    pkg_file_response(&req, &format!("frontend/pkg/{file}"), &shared_data)
+}
+
+// ------ web_workers_responder ------
+
+pub(crate) async fn web_workers_responder(
+    req: HttpRequest,
+    path_parameters: web::Path<(String, String)>,
+    shared_data: web::Data<SharedData>,
+) -> Result<HttpResponse, Error> {
+    let (crate_name, file) = path_parameters.into_inner();
+    pkg_file_response(
+        &req,
+        &format!("web_workers/{crate_name}/pkg/{file}"),
+        &shared_data,
+    )
+}
+
+// ------ public_responder ------
+
+pub(crate) async fn public_responder(
+    req: HttpRequest,
+    file: web::Path<String>,
+    shared_data: web::Data<SharedData>,
+) -> Result<HttpResponse, Error> {
+    let path = format!("public/{file}");
+    let content = asset(&path).ok_or_else(|| error::ErrorNotFound("File Not Found"))?;
+    let mime = mime_guess::from_path(&path).first_or_octet_stream();
+
+    // Public files aren't cache-busted, so browsers have to revalidate them on every use.
+    let etag = build_etag(shared_data.get_ref());
+    let cache_control = CacheControl(vec![CacheDirective::NoCache]);
+    if is_not_modified(&req, &etag) {
+        return Ok(HttpResponse::NotModified()
+            .insert_header(ETag(etag))
+            .insert_header(cache_control)
+            .finish());
+    }
+    Ok(HttpResponse::Ok()
+        .content_type(mime)
+        .insert_header(ETag(etag))
+        .insert_header(cache_control)
+        .body(content))
+}
+
+// -- private --
+
+fn pkg_file_response(
+    req: &HttpRequest,
+    path: &str,
+    shared_data: &SharedData,
+) -> Result<HttpResponse, Error> {
+    if path.contains("..") {
+        Err(error::ErrorForbidden(
+            "It is not allowed to use '..' in the requested path",
+        ))?;
+    }
+
+    let etag = build_etag(shared_data);
+    if !shared_data.cache_busting && is_not_modified(req, &etag) {
+        return Ok(HttpResponse::NotModified()
+            .insert_header(ETag(etag))
+            .finish());
+    }
+
+    let mime = mime_guess::from_path(path).first_or_octet_stream();
+    let (content, encoding) = content_and_encoding(req, path, shared_data)
+        .ok_or_else(|| error::ErrorNotFound(format!("Embedded file '{path}' not found")))?;
+
+    let mut responder = HttpResponse::Ok();
+    responder.content_type(mime);
+
+    if shared_data.cache_busting {
+        responder.insert_header(CacheControl(vec![CacheDirective::MaxAge(31536000)]));
+    } else {
+        responder.insert_header(ETag(etag));
+    }
+
+    if let Some(encoding) = encoding {
+        responder.insert_header(encoding);
+    }
+    Ok(responder.body(content))
+}
+
+fn build_etag(shared_data: &SharedData) -> EntityTag {
+    EntityTag::new(false, shared_data.frontend_build_id.to_string())
+}
+
+fn is_not_modified(req: &HttpRequest, etag: &EntityTag) -> bool {
+    match req.get_header::<IfNoneMatch>() {
+        Some(IfNoneMatch::Any) => true,
+        Some(IfNoneMatch::Items(items)) => items.iter().any(|item| item.weak_eq(etag)),
+        None => false,
+    }
+}
+
+fn content_and_encoding(
+    req: &HttpRequest,
+    path: &str,
+    shared_data: &SharedData,
+) -> Option<(&'static [u8], Option<ContentEncoding>)> {
+    if !shared_data.compressed_pkg {
+        return Some((asset(path)?, None));
+    }
+    let accept_encodings = req
+        .headers()
+        .get(header::ACCEPT_ENCODING)
+        .and_then(|accept_encoding| accept_encoding.to_str().ok())
+        .map(|accept_encoding| accept_encoding.split(", ").collect::<BTreeSet<_>>())
+        .unwrap_or_default();
+
+    for (encoding, extension) in [
+        (ContentEncoding::Brotli, "br"),
+        (ContentEncoding::Gzip, "gz"),
+    ] {
+        if accept_encodings.contains(encoding.as_str()) {
+            if let Some(content) = asset(&format!("{path}.{extension}")) {
+                return Some((content, Some(encoding)));
+            }
+        }
+    }
+    Some((asset(path)?, None))
+}
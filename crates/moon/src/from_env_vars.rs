@@ -1,4 +1,6 @@
+use config_schema::{setting_by_env_var, ENV_VAR_TABLE_PREFIXES, SETTINGS};
 use serde::Deserialize;
+use std::env;
 
 pub trait FromEnvVars
 where
@@ -8,9 +10,25 @@ where
     const ENV_PREFIX: &'static str = "";
 
     fn from_env_vars() -> Self {
+        let errors = invalid_env_vars(Self::ENV_PREFIX);
+        if !errors.is_empty() {
+            panic!(
+                "cannot load {} from env variables:\n{}",
+                Self::ENTITY_NAME,
+                errors.join("\n")
+            );
+        }
         envy::prefixed(Self::ENV_PREFIX)
             .from_env()
             .unwrap_or_else(|error| {
+                let error = match error {
+                    envy::Error::MissingValue(field) => format!(
+                        "missing env variable '{}{}'",
+                        Self::ENV_PREFIX,
+                        field.to_uppercase()
+                    ),
+                    envy::Error::Custom(message) => message,
+                };
                 panic!(
                     "cannot load {} from env variables: {}",
                     Self::ENTITY_NAME,
@@ -19,3 +37,26 @@ where
             })
     }
 }
+
+/// Validates known settings with the given prefix against the shared schema
+/// and warns about unknown env variables in known tables (e.g. a typo in `REDIRECT_PORT`).
+fn invalid_env_vars(prefix: &str) -> Vec<String> {
+    let errors = SETTINGS
+        .iter()
+        .filter(|setting| setting.env_var.starts_with(prefix))
+        .filter_map(|setting| {
+            let value = env::var(setting.env_var).ok()?;
+            let error = setting.kind.validate(&value).err()?;
+            Some(format!("  {}: {error}", setting.env_var))
+        })
+        .collect();
+
+    if ENV_VAR_TABLE_PREFIXES.contains(&prefix) {
+        for (name, _) in env::vars() {
+            if name.starts_with(prefix) && setting_by_env_var(&name).is_none() {
+                eprintln!("Warning: unknown env variable '{name}'");
+            }
+        }
+    }
+    errors
+}
@@ -1,6 +1,7 @@
 use crate::CONFIG;
 use lang::Lang;
 use std::borrow::Cow;
+#[cfg(not(feature = "embedded_assets"))]
 use tokio::fs;
 
 pub struct Frontend {
@@ -27,13 +28,54 @@ impl Default for Frontend {
 
 impl Frontend {
     pub(crate) async fn build_id() -> u128 {
-        fs::read_to_string("frontend/pkg/build_id")
-            .await
-            .ok()
+        #[cfg(feature = "embedded_assets")]
+        let build_id = crate::embedded_assets::asset("frontend/pkg/build_id")
+            .and_then(|build_id| std::str::from_utf8(build_id).ok());
+        #[cfg(not(feature = "embedded_assets"))]
+        let build_id = fs::read_to_string("frontend/pkg/build_id").await.ok();
+
+        build_id
             .and_then(|uuid| uuid.parse().ok())
             .unwrap_or_default()
     }
 
+    /// `<script>` with the public assets manifest created by mzoon (see `zoon::public_url`).
+    async fn public_assets_script() -> String {
+        #[cfg(feature = "embedded_assets")]
+        let manifest = crate::embedded_assets::asset("frontend/pkg/public_manifest.json")
+            .and_then(|manifest| std::str::from_utf8(manifest).ok());
+        #[cfg(not(feature = "embedded_assets"))]
+        let manifest = fs::read_to_string("frontend/pkg/public_manifest.json")
+            .await
+            .ok();
+
+        match manifest {
+            Some(manifest) => format!(
+                "<script>window.MOON_PUBLIC_ASSETS = {};</script>",
+                manifest.replace("</", "<\\/")
+            ),
+            None => String::new(),
+        }
+    }
+
+    /// `<script>` with the symbol map URL created by mzoon in dev and profiling builds
+    /// (see Zoon's `panic_hook`).
+    async fn symbol_map_script() -> String {
+        #[cfg(feature = "embedded_assets")]
+        let exists = crate::embedded_assets::asset("frontend/pkg/frontend.symbols.json").is_some();
+        #[cfg(not(feature = "embedded_assets"))]
+        let exists = fs::metadata("frontend/pkg/frontend.symbols.json")
+            .await
+            .is_ok();
+
+        if exists {
+            r#"<script>window.MOON_SYMBOL_MAP_URL = "/_api/pkg/frontend.symbols.json";</script>"#
+                .to_owned()
+        } else {
+            String::new()
+        }
+    }
+
     pub fn new() -> Self {
         Self::default()
     }
@@ -84,6 +126,14 @@ impl Frontend {
             Cow::from("")
         };
 
+        let public_assets_script = if CONFIG.cache_busting {
+            Self::public_assets_script().await
+        } else {
+            String::new()
+        };
+
+        let symbol_map_script = Self::symbol_map_script().await;
+
         let meta_robots = if index_by_robots {
             ""
         } else {
@@ -158,6 +208,8 @@ impl Frontend {
           <link rel="preload" href="/_api/pkg/frontend_bg{cache_busting_string}.wasm" as="fetch" type="application/wasm" crossorigin>
           <link rel="modulepreload" href="/_api/pkg/frontend{cache_busting_string}.js" crossorigin>
           {default_styles}
+          {public_assets_script}
+          {symbol_map_script}
           {append_to_head}
         </head>
 
@@ -1,10 +1,6 @@
 use actix_cors::Cors;
-use actix_files::{Files, NamedFile};
-use actix_http::{
-    header::{self, HeaderMap},
-    ContentEncoding,
-};
-use actix_web::http::header::{CacheControl, CacheDirective, ContentType, ETag, EntityTag};
+use actix_http::header::HeaderMap;
+use actix_web::http::header::{CacheControl, CacheDirective, ContentType};
 use actix_web::{
     body::MessageBody,
     dev::{ServiceFactory, ServiceRequest, ServiceResponse},
@@ -13,18 +9,27 @@ use actix_web::{
     middleware::{Compat, Condition, ErrorHandlers, Logger},
     web, App, HttpRequest, HttpResponse, HttpServer, Responder, Result,
 };
-use cargo_metadata::MetadataCommand;
 use rustls::{Certificate, PrivateKey, ServerConfig as RustlsServerConfig};
 use rustls_pemfile::{certs, pkcs8_private_keys};
-use std::collections::BTreeSet;
 use std::fs::File;
 use std::io::{self, BufReader};
 use std::net::SocketAddr;
 use std::ops::Deref;
-use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::fs;
 
+#[cfg(not(feature = "embedded_assets"))]
+use {
+    actix_files::{Files, NamedFile},
+    actix_http::{header, ContentEncoding},
+    actix_web::http::header::{ETag, EntityTag},
+    cargo_metadata::MetadataCommand,
+    std::{
+        collections::BTreeSet,
+        path::{Path, PathBuf},
+    },
+};
+
 pub use actix_cors;
 pub use actix_files;
 pub use actix_http;
@@ -35,7 +40,7 @@ pub use chashmap;
 pub use enclose::enc as clone;
 pub use futures;
 pub use futures_signals_ext::{self, *};
-pub use lang::Lang;
+pub use lang::{self, Lang};
 pub use mime;
 pub use mime_guess;
 pub use moon_entry_macros::{main, test};
@@ -47,38 +52,60 @@ pub use rustls_pemfile;
 pub use serde;
 pub use tokio;
 pub use tokio_stream;
+pub use tracing;
 pub use trait_set::trait_set;
 pub use uuid;
 
 mod actor;
+mod backplane;
+mod client_log;
 pub mod config;
+#[cfg(feature = "embedded_assets")]
+mod embedded_assets;
 pub mod error_handler;
 mod from_env_vars;
 mod frontend;
 mod lazy_message_writer;
 mod not;
 mod redirect;
+pub mod scheduler;
 mod sse;
+mod telemetry;
 mod up_msg_request;
+mod upload;
 
 use config::CONFIG;
 use lazy_message_writer::LazyMessageWriter;
 use sse::{ShareableSSE, ShareableSSEMethods, SSE};
 
+#[cfg(feature = "embedded_assets")]
+use embedded_assets::{pkg_responder, web_workers_responder};
+
 pub use actor::{
     sessions::{self, SessionActor},
-    ActorId, ActorInstance, Index, PVar,
+    ActorId, ActorInstance, Index, PVar, WAIT_FOR_TIMEOUT,
 };
+pub use backplane::{
+    instance_id, set_backplane, Backplane, BackplaneMessage, InProcessBackplane, MessageHandler,
+    SocketBackplane,
+};
+pub use client_log::{set_client_log_sink, ClientLog};
 pub use from_env_vars::FromEnvVars;
 pub use frontend::Frontend;
 pub use not::not;
 pub use redirect::Redirect;
 pub use up_msg_request::UpMsgRequest;
+pub use upload::{
+    download_response, set_max_upload_bytes, set_upload_handler, Upload, UploadError, UploadStream,
+};
 
 // @TODO make it configurable
 // const MAX_UP_MSG_BYTES: usize = 2 * 1_048_576;
 const MAX_UP_MSG_BYTES: usize = usize::MAX;
 
+// NOTE: Sync with Zoon's `Connection`.
+const INSTANCE_HEADER: &str = "X-Moon-Instance";
+
 #[derive(Copy, Clone)]
 struct SharedData {
     backend_build_id: u128,
@@ -146,7 +173,9 @@ where
                 Compat::new(redirect),
             ))
             // https://docs.rs/actix-web/4.0.0-beta.8/actix_web/middleware/struct.Logger.html
-            .wrap(Logger::new(r#""%r" %s %b "%{Referer}i" %T"#))
+            .wrap(Logger::new(
+                r#""%r" %s %b "%{Referer}i" %T cor_id=%{X-Correlation-ID}i session_id=%{X-Session-ID}i instance=%{X-Moon-Instance}i"#,
+            ))
             .wrap(Cors::default().allowed_origin_fn(move |origin, _| {
                 if CONFIG.cors.origins.contains("*") {
                     return true;
@@ -197,9 +226,7 @@ where
 
     println!("Moon config: {:?}", *CONFIG);
 
-    env_logger::builder()
-        .filter_level(CONFIG.backend_log_level)
-        .init();
+    telemetry::init(CONFIG.backend_log_level, &CONFIG.otlp);
 
     let shared_data = SharedData {
         backend_build_id: backend_build_id().await,
@@ -210,6 +237,7 @@ where
     };
     let reload_sse = ReloadSSE(SSE::start());
     let message_sse = MessageSSE(SSE::start());
+    backplane::init(message_sse.clone());
     let address = SocketAddr::from(([0, 0, 0, 0], CONFIG.port));
 
     let mut lazy_message_writer = LazyMessageWriter::new();
@@ -234,11 +262,7 @@ where
             .app_data(data_reload_sse.clone())
             .app_data(data_message_sse.clone())
             .configure(service_config.clone())
-            .service(
-                Files::new("_api/public", "public").default_handler(web::to(|| async {
-                    HttpResponse::NotFound().reason("File Not Found").finish()
-                })),
-            )
+            .service(public_service())
             .service(
                 web::scope("_api")
                     .route(
@@ -246,6 +270,9 @@ where
                         web::post().to(up_msg_handler_responder::<UPH, UPHO, UMsg>),
                     )
                     .route("reload", web::post().to(reload_responder))
+                    .route("panic_report", web::post().to(panic_report_responder))
+                    .route("client_log", web::post().to(client_log_responder))
+                    .route("upload", web::post().to(upload::upload_responder))
                     .route("pkg/{file:.*}", web::get().to(pkg_responder))
                     .route(
                         "web_workers/{crate_name}/pkg/{file:.*}",
@@ -291,6 +318,7 @@ where
         lazy_message_writer.write_all()?;
     }
     server.await?;
+    scheduler::shutdown().await;
 
     Ok(println!("Stop Moon"))
 }
@@ -353,8 +381,15 @@ where
         cor_id: parse_cor_id(headers)?,
         auth_token: parse_auth_token(headers)?,
     };
-    up_msg_handler.get_ref()(up_msg_request).await;
-    Ok(HttpResponse::Ok().finish())
+    telemetry::in_up_msg_span(
+        up_msg_request.session_id,
+        up_msg_request.cor_id,
+        up_msg_handler.get_ref()(up_msg_request),
+    )
+    .await;
+    Ok(HttpResponse::Ok()
+        .insert_header((INSTANCE_HEADER, backplane::instance_id()))
+        .finish())
 }
 
 #[cfg(feature = "serde")]
@@ -410,8 +445,35 @@ async fn reload_responder(sse: web::Data<ReloadSSE>) -> impl Responder {
     HttpResponse::Ok()
 }
 
+// ------ panic_report_responder ------
+
+/// Logs symbolicated frontend panics sent by Zoon's `panic_hook::report_to_backend`.
+async fn panic_report_responder(report: String) -> impl Responder {
+    log::error!("Frontend panic:\n{report}");
+    HttpResponse::Ok()
+}
+
+// ------ client_log_responder ------
+
+async fn client_log_responder(req: HttpRequest, body: web::Bytes) -> Result<HttpResponse, Error> {
+    // `sendBeacon` can't set headers so the session id is passed in the query.
+    let session_id = req
+        .query_string()
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("session_id="))
+        .map(|session_id| session_id.parse().map_err(error::ErrorBadRequest))
+        .transpose()?;
+    let records = serde_json::from_slice(&body).map_err(error::ErrorBadRequest)?;
+    client_log::handle_client_log(ClientLog {
+        session_id,
+        records,
+    });
+    Ok(HttpResponse::Ok().finish())
+}
+
 // ------ pkg_responder ------
 
+#[cfg(not(feature = "embedded_assets"))]
 async fn pkg_responder(
     req: HttpRequest,
     file: web::Path<String>,
@@ -449,6 +511,7 @@ async fn pkg_responder(
     Ok::<_, Error>(responder)
 }
 
+#[cfg(not(feature = "embedded_assets"))]
 fn named_file_and_encoding(
     req: &HttpRequest,
     file: &web::Path<String>,
@@ -486,6 +549,7 @@ fn named_file_and_encoding(
 
 // ------ web_workers_responder ------
 
+#[cfg(not(feature = "embedded_assets"))]
 async fn web_workers_responder(
     req: HttpRequest,
     path_parameters: web::Path<(String, String)>,
@@ -529,21 +593,14 @@ async fn web_workers_responder(
     Ok::<_, Error>(responder)
 }
 
+#[cfg(not(feature = "embedded_assets"))]
 fn web_worker_named_file_and_encoding(
     req: &HttpRequest,
     file: &str,
     shared_data: &web::Data<SharedData>,
     crate_name: &str,
 ) -> Result<(NamedFile, Option<ContentEncoding>), Error> {
-    let WorkspaceMember { mut path, .. } = web_worker_workspace_members()?
-        .into_iter()
-        .find(|member| member.name == crate_name)
-        .ok_or_else(|| {
-            error::ErrorNotFound(format!(
-                "Failed to find Web Worker '{crate_name}' in the project workspace"
-            ))
-        })?;
-    path.push("pkg");
+    let mut path = web_worker_pkg_path(crate_name)?;
     path.push(file);
 
     if !shared_data.compressed_pkg {
@@ -577,12 +634,39 @@ fn web_worker_named_file_and_encoding(
     Ok((NamedFile::open(path)?, None))
 }
 
+#[cfg(not(feature = "embedded_assets"))]
+fn web_worker_pkg_path(crate_name: &str) -> Result<PathBuf> {
+    if crate_name.contains("..") {
+        Err(error::ErrorForbidden(
+            "It is not allowed to use '..' in the requested path",
+        ))?;
+    }
+    // Bundles created by `mzoon build --bundle` don't contain the Cargo workspace,
+    // so their Web Worker pkgs are copied to `web_workers/{crate_name}/pkg`.
+    let bundled_path = Path::new("web_workers").join(crate_name).join("pkg");
+    if bundled_path.is_dir() {
+        return Ok(bundled_path);
+    }
+    let WorkspaceMember { mut path, .. } = web_worker_workspace_members()?
+        .into_iter()
+        .find(|member| member.name == crate_name)
+        .ok_or_else(|| {
+            error::ErrorNotFound(format!(
+                "Failed to find Web Worker '{crate_name}' in the project workspace"
+            ))
+        })?;
+    path.push("pkg");
+    Ok(path)
+}
+
+#[cfg(not(feature = "embedded_assets"))]
 #[derive(Debug)]
 struct WorkspaceMember {
     name: String,
     path: PathBuf,
 }
 
+#[cfg(not(feature = "embedded_assets"))]
 fn web_worker_workspace_members() -> Result<Vec<WorkspaceMember>> {
     let members = MetadataCommand::new()
         .no_deps()
@@ -606,6 +690,20 @@ fn web_worker_workspace_members() -> Result<Vec<WorkspaceMember>> {
     Ok(members)
 }
 
+// ------ public_service ------
+
+#[cfg(not(feature = "embedded_assets"))]
+fn public_service() -> Files {
+    Files::new("_api/public", "public").default_handler(web::to(|| async {
+        HttpResponse::NotFound().reason("File Not Found").finish()
+    }))
+}
+
+#[cfg(feature = "embedded_assets")]
+fn public_service() -> actix_web::Resource {
+    web::resource("_api/public/{file:.*}").route(web::get().to(embedded_assets::public_responder))
+}
+
 // ------ reload_sse_responder ------
 
 async fn reload_sse_responder(
@@ -637,12 +735,16 @@ async fn message_sse_responder(
     sse: web::Data<MessageSSE>,
 ) -> Result<HttpResponse, Error> {
     let session_id = session_id.parse().map_err(error::ErrorBadRequest)?;
-    let (_, event_stream) = sse.new_connection(Some(session_id));
+    let (connection, event_stream) = sse.new_connection(Some(session_id));
+    // Zoon sends the instance id back in UpMsg requests as a sticky-session hint.
+    let _ = connection.send("instance", backplane::instance_id());
+    backplane::deliver_pending_down_msgs(session_id, &sse);
     SessionActor::create(session_id, MessageSSE::clone(&sse));
 
     Ok(HttpResponse::Ok()
         .insert_header(ContentType(mime::TEXT_EVENT_STREAM))
         .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+        .insert_header((INSTANCE_HEADER, backplane::instance_id()))
         .streaming(event_stream))
 }
 
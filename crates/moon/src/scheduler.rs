@@ -0,0 +1,556 @@
+//! Interval, cron and delayed jobs and persistent retryable jobs.
+//!
+//! Jobs run on a dedicated Actix arbiter (a thread with its own async runtime),
+//! so they don't depend on the server workers and their futures don't have to be `Send`.
+//! They can send DownMsgs with `sessions::send_down_msg` and `sessions::broadcast_down_msg`.
+//!
+//! When the server stops, no new job runs are started and Moon waits
+//! up to `SHUTDOWN_TIMEOUT` for the running ones.
+
+use crate::actor::{storage, ActorId};
+use actix_rt::{Arbiter, ArbiterHandle};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    error::Error,
+    fmt,
+    future::{self, Future},
+    io,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    sync::{watch, Notify},
+    time::{sleep_until, timeout, Duration, Instant},
+};
+
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+static ARBITER: Lazy<ArbiterHandle> = Lazy::new(|| Arbiter::new().handle());
+static SHUTDOWN: Lazy<watch::Sender<bool>> = Lazy::new(|| watch::channel(false).0);
+static RUNNING_JOBS: AtomicUsize = AtomicUsize::new(0);
+static JOB_FINISHED: Lazy<Notify> = Lazy::new(Notify::new);
+
+// ------ JobHandle ------
+
+/// Dropping the handle doesn't cancel the job.
+pub struct JobHandle {
+    cancel: watch::Sender<bool>,
+}
+
+impl JobHandle {
+    /// Stops scheduling next runs, the running one isn't interrupted.
+    pub fn cancel(&self) {
+        self.cancel.send_replace(true);
+    }
+}
+
+// ------ every / cron / after ------
+
+/// Runs the job periodically, the first run is after one `period`.
+/// Runs missed because the previous run took too long are skipped.
+pub fn every<J, JO>(period: Duration, job: J) -> JobHandle
+where
+    J: Fn() -> JO + Send + Sync + 'static,
+    JO: Future<Output = ()> + 'static,
+{
+    every_from(Instant::now() + period, period, job)
+}
+
+/// Runs the job periodically like `every`, but the first run is immediate.
+pub fn every_immediate<J, JO>(period: Duration, job: J) -> JobHandle
+where
+    J: Fn() -> JO + Send + Sync + 'static,
+    JO: Future<Output = ()> + 'static,
+{
+    every_from(Instant::now(), period, job)
+}
+
+/// Runs the job at times matching the cron expression in UTC,
+/// e.g. `"*/15 * * * *"` (every 15 minutes) or `"0 3 * * 1-5"` (3:00 on working days).
+///
+/// Fields: minute (0-59), hour (0-23), day of month (1-31), month (1-12), day of week (0-7, 0 and 7 are Sunday).
+/// Each field supports `*`, numbers, ranges `a-b`, steps `*/n` or `a-b/n` and lists `a,b`.
+pub fn cron<J, JO>(expression: &str, job: J) -> Result<JobHandle, CronError>
+where
+    J: Fn() -> JO + Send + Sync + 'static,
+    JO: Future<Output = ()> + 'static,
+{
+    let cron_schedule = CronSchedule::parse(expression)?;
+    Ok(schedule(
+        move || {
+            let now = SystemTime::now();
+            let now_secs = now.duration_since(UNIX_EPOCH).ok()?.as_secs();
+            let next_secs = cron_schedule.next_after(now_secs)?;
+            let delay = (UNIX_EPOCH + Duration::from_secs(next_secs))
+                .duration_since(now)
+                .unwrap_or_default();
+            Some(Instant::now() + delay)
+        },
+        job,
+    ))
+}
+
+/// Runs the job once after the `delay`.
+pub fn after<J, JO>(delay: Duration, job: J) -> JobHandle
+where
+    J: Fn() -> JO + Send + Sync + 'static,
+    JO: Future<Output = ()> + 'static,
+{
+    let mut run_at = Some(Instant::now() + delay);
+    schedule(move || run_at.take(), job)
+}
+
+/// Wraps a synchronous job for `every`, `cron` or `after`,
+/// e.g. `scheduler::every(period, scheduler::sync_job(|| cleanup()))`.
+pub fn sync_job(
+    job: impl Fn() + Send + Sync + 'static,
+) -> impl Fn() -> future::Ready<()> + Send + Sync + 'static {
+    move || future::ready(job())
+}
+
+// ------ PersistentJob ------
+
+/// A job stored in the actor storage (`backend/private/actors/<KEY>`) until it succeeds
+/// or fails `MAX_ATTEMPTS` times, so it survives restarts.
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize)]
+/// #[serde(crate = "serde")]
+/// struct SendInvoice { order_id: u64 }
+///
+/// #[async_trait(?Send)]
+/// impl PersistentJob for SendInvoice {
+///     const KEY: &'static str = "send_invoice";
+///
+///     async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
+///         send_invoice(self.order_id).await?;
+///         Ok(())
+///     }
+/// }
+///
+/// // in `main` before `start`
+/// scheduler::resume::<SendInvoice>().await?;
+/// // anywhere
+/// scheduler::enqueue(SendInvoice { order_id: 42 }).await?;
+/// ```
+#[async_trait(?Send)]
+pub trait PersistentJob: Serialize + DeserializeOwned + Send + 'static {
+    /// Unique name of the job type, used as the storage folder name.
+    const KEY: &'static str;
+    const MAX_ATTEMPTS: u32 = 5;
+    /// Delay before the first retry, doubled with each next one (up to a day).
+    const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+    async fn run(&self) -> Result<(), Box<dyn Error>>;
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredJob<PJ> {
+    payload: PJ,
+    attempt: u32,
+    /// Unix time in milliseconds.
+    run_at: u64,
+}
+
+/// Stores the job and runs it as soon as possible.
+pub async fn enqueue<PJ: PersistentJob>(job: PJ) -> io::Result<ActorId> {
+    enqueue_after(job, Duration::ZERO).await
+}
+
+/// Stores the job and runs it after the `delay`.
+pub async fn enqueue_after<PJ: PersistentJob>(job: PJ, delay: Duration) -> io::Result<ActorId> {
+    let actor_id = ActorId::new();
+    let stored_job = StoredJob {
+        payload: job,
+        attempt: 0,
+        run_at: unix_millis(SystemTime::now() + delay),
+    };
+    storage::write(PJ::KEY, actor_id, &stored_job).await?;
+    ARBITER.spawn_fn(move || {
+        actix_rt::spawn(run_persistent(actor_id, stored_job));
+    });
+    Ok(actor_id)
+}
+
+/// Schedules jobs of the given type stored before the last shutdown or crash.
+/// Call it for each `PersistentJob` type before `start`.
+pub async fn resume<PJ: PersistentJob>() -> io::Result<()> {
+    let stored_jobs = storage::read_all::<StoredJob<PJ>>(PJ::KEY).await?;
+    if !stored_jobs.is_empty() {
+        log::info!("Resuming {} '{}' jobs", stored_jobs.len(), PJ::KEY);
+    }
+    for (actor_id, stored_job) in stored_jobs {
+        ARBITER.spawn_fn(move || {
+            actix_rt::spawn(run_persistent(actor_id, stored_job));
+        });
+    }
+    Ok(())
+}
+
+// ------ CronError ------
+
+#[derive(Debug)]
+pub struct CronError(String);
+
+impl fmt::Display for CronError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid cron expression: {}", self.0)
+    }
+}
+
+impl Error for CronError {}
+
+// ------ shutdown ------
+
+/// Called by Moon when the server has been stopped.
+pub(crate) async fn shutdown() {
+    SHUTDOWN.send_replace(true);
+    let running_jobs_finished = async {
+        loop {
+            // Create the future before the check to not miss the notification.
+            let job_finished = JOB_FINISHED.notified();
+            if RUNNING_JOBS.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            job_finished.await;
+        }
+    };
+    if timeout(SHUTDOWN_TIMEOUT, running_jobs_finished)
+        .await
+        .is_err()
+    {
+        log::warn!(
+            "{} jobs haven't finished in {SHUTDOWN_TIMEOUT:?}",
+            RUNNING_JOBS.load(Ordering::SeqCst)
+        );
+    }
+    if let Some(arbiter) = Lazy::get(&ARBITER) {
+        arbiter.stop();
+    }
+}
+
+// ------ private ------
+
+fn every_from<J, JO>(first_run: Instant, period: Duration, job: J) -> JobHandle
+where
+    J: Fn() -> JO + Send + Sync + 'static,
+    JO: Future<Output = ()> + 'static,
+{
+    let mut next_run = None;
+    schedule(
+        move || {
+            let run_at = match next_run {
+                Some(previous_run) => (previous_run + period).max(Instant::now()),
+                None => first_run,
+            };
+            next_run = Some(run_at);
+            Some(run_at)
+        },
+        job,
+    )
+}
+
+fn schedule<J, JO>(
+    mut next_run: impl FnMut() -> Option<Instant> + Send + 'static,
+    job: J,
+) -> JobHandle
+where
+    J: Fn() -> JO + Send + Sync + 'static,
+    JO: Future<Output = ()> + 'static,
+{
+    let (cancel, mut cancelled) = watch::channel(false);
+    ARBITER.spawn_fn(move || {
+        actix_rt::spawn(async move {
+            while let Some(run_at) = next_run() {
+                if !wait_until(run_at, Some(&mut cancelled)).await {
+                    return;
+                }
+                let Some(_running_job) = RunningJob::start() else {
+                    return;
+                };
+                job().await;
+            }
+        });
+    });
+    JobHandle { cancel }
+}
+
+async fn run_persistent<PJ: PersistentJob>(actor_id: ActorId, mut stored_job: StoredJob<PJ>) {
+    loop {
+        let delay = Duration::from_millis(
+            stored_job
+                .run_at
+                .saturating_sub(unix_millis(SystemTime::now())),
+        );
+        // The job stays in the storage and it's resumed after the restart.
+        if !wait_until(Instant::now() + delay, None).await {
+            return;
+        }
+        // Counted until the storage is updated.
+        let Some(_running_job) = RunningJob::start() else {
+            return;
+        };
+        let error = match stored_job.payload.run().await {
+            Ok(()) => None,
+            Err(error) => Some(error.to_string()),
+        };
+        let Some(error) = error else {
+            if let Err(error) = storage::remove(PJ::KEY, actor_id).await {
+                log::error!(
+                    "Failed to remove the finished '{}' job {actor_id}: {error}",
+                    PJ::KEY
+                );
+            }
+            return;
+        };
+
+        stored_job.attempt += 1;
+        if stored_job.attempt >= PJ::MAX_ATTEMPTS {
+            log::error!(
+                "'{}' job {actor_id} failed {} times, giving up: {error}",
+                PJ::KEY,
+                stored_job.attempt
+            );
+            if let Err(error) = storage::remove(PJ::KEY, actor_id).await {
+                log::error!(
+                    "Failed to remove the failed '{}' job {actor_id}: {error}",
+                    PJ::KEY
+                );
+            }
+            return;
+        }
+        let retry_delay = PJ::RETRY_DELAY
+            .checked_mul(2_u32.saturating_pow(stored_job.attempt - 1))
+            .unwrap_or(MAX_RETRY_DELAY)
+            .min(MAX_RETRY_DELAY);
+        log::warn!(
+            "'{}' job {actor_id} failed (attempt {}), retrying in {retry_delay:?}: {error}",
+            PJ::KEY,
+            stored_job.attempt
+        );
+        stored_job.run_at = unix_millis(SystemTime::now() + retry_delay);
+        if let Err(error) = storage::write(PJ::KEY, actor_id, &stored_job).await {
+            log::error!("Failed to store the '{}' job {actor_id}: {error}", PJ::KEY);
+        }
+    }
+}
+
+/// Returns `false` when the job has been cancelled or Moon is shutting down.
+async fn wait_until(deadline: Instant, cancelled: Option<&mut watch::Receiver<bool>>) -> bool {
+    let mut shutdown = SHUTDOWN.subscribe();
+    let cancelled = async {
+        match cancelled {
+            // `Err` means the `JobHandle` has been dropped so the job can't be cancelled anymore.
+            Some(cancelled) => {
+                if cancelled.wait_for(|cancelled| *cancelled).await.is_err() {
+                    std::future::pending::<()>().await;
+                }
+            }
+            None => std::future::pending().await,
+        }
+    };
+    tokio::select! {
+        _ = sleep_until(deadline) => !*SHUTDOWN.borrow(),
+        _ = shutdown.wait_for(|shutdown| *shutdown) => false,
+        _ = cancelled => false,
+    }
+}
+
+/// Counts running jobs for `shutdown`.
+struct RunningJob;
+
+impl RunningJob {
+    /// `None` when Moon is shutting down.
+    fn start() -> Option<Self> {
+        RUNNING_JOBS.fetch_add(1, Ordering::SeqCst);
+        let running_job = Self;
+        (!*SHUTDOWN.borrow()).then_some(running_job)
+    }
+}
+
+impl Drop for RunningJob {
+    fn drop(&mut self) {
+        RUNNING_JOBS.fetch_sub(1, Ordering::SeqCst);
+        JOB_FINISHED.notify_waiters();
+    }
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+// ------ CronSchedule ------
+
+/// Allowed values as bit masks.
+#[derive(Debug, PartialEq)]
+struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Standard cron matches days by day of month OR day of week when both are restricted.
+    days_or: bool,
+}
+
+impl CronSchedule {
+    fn parse(expression: &str) -> Result<Self, CronError> {
+        let fields = expression.split_whitespace().collect::<Vec<_>>();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields[..] else {
+            return Err(CronError(format!(
+                "expected 5 fields, found {} in '{expression}'",
+                fields.len()
+            )));
+        };
+        let mut days_of_week = parse_field(days_of_week, 0, 7)?;
+        // 7 is also Sunday.
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+        }
+        Ok(Self {
+            minutes: parse_field(minutes, 0, 59)?,
+            hours: parse_field(hours, 0, 23)?,
+            days_of_month: parse_field(days_of_month, 1, 31)?,
+            months: parse_field(months, 1, 12)?,
+            days_of_week,
+            days_or: !days_of_month.starts_with('*') && !fields[4].starts_with('*'),
+        })
+    }
+
+    /// The first matching minute after `unix_secs` in Unix seconds,
+    /// `None` when nothing matches in the next 5 years (e.g. `0 0 31 2 *`).
+    fn next_after(&self, unix_secs: u64) -> Option<u64> {
+        let start_minute = unix_secs / 60 + 1;
+        let mut day = start_minute / 1440;
+        let mut minute_of_day = start_minute % 1440;
+        for _ in 0..(366 * 5) {
+            if self.matches_day(day) {
+                for minute_of_day in minute_of_day..1440 {
+                    if bit(self.hours, minute_of_day / 60) && bit(self.minutes, minute_of_day % 60)
+                    {
+                        return Some((day * 1440 + minute_of_day) * 60);
+                    }
+                }
+            }
+            day += 1;
+            minute_of_day = 0;
+        }
+        None
+    }
+
+    fn matches_day(&self, days_since_epoch: u64) -> bool {
+        let (month, day_of_month) = month_and_day(days_since_epoch);
+        // 1970-01-01 was Thursday.
+        let day_of_week = (days_since_epoch + 4) % 7;
+        if !bit(self.months, month) {
+            return false;
+        }
+        let day_of_month_matches = bit(self.days_of_month, day_of_month);
+        let day_of_week_matches = bit(self.days_of_week, day_of_week);
+        if self.days_or {
+            day_of_month_matches || day_of_week_matches
+        } else {
+            day_of_month_matches && day_of_week_matches
+        }
+    }
+}
+
+fn bit(mask: u64, value: u64) -> bool {
+    mask & (1 << value) != 0
+}
+
+/// `*`, `5`, `1-5`, `*/15`, `0-30/10` or their comma-separated list.
+fn parse_field(field: &str, min: u64, max: u64) -> Result<u64, CronError> {
+    let invalid = || {
+        CronError(format!(
+            "invalid field '{field}' (allowed values {min}-{max})"
+        ))
+    };
+    let mut mask = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u64>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (
+                    start.parse().map_err(|_| invalid())?,
+                    end.parse().map_err(|_| invalid())?,
+                ),
+                None => {
+                    let value = range.parse().map_err(|_| invalid())?;
+                    (value, value)
+                }
+            },
+        };
+        if step == 0 || start < min || end > max || start > end {
+            return Err(invalid());
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+/// Month (1-12) and day of month (1-31) in the proleptic Gregorian calendar.
+/// Based on `civil_from_days` from http://howardhinnant.github.io/date_algorithms.html
+fn month_and_day(days_since_epoch: u64) -> (u64, u64) {
+    let z = days_since_epoch + 719_468;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    (month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-02-28 23:59:30 UTC, Wednesday.
+    const NOW: u64 = 1_709_164_770;
+
+    #[test]
+    fn test_cron_parse() {
+        let schedule = CronSchedule::parse("*/15 3,4 * 1-6/2 7").unwrap();
+        assert_eq!(schedule.minutes, 1 | 1 << 15 | 1 << 30 | 1 << 45);
+        assert_eq!(schedule.hours, 1 << 3 | 1 << 4);
+        assert_eq!(schedule.months, 1 << 1 | 1 << 3 | 1 << 5);
+        assert_eq!(schedule.days_of_week, 1 | 1 << 7);
+        assert!(!schedule.days_or);
+
+        assert!(CronSchedule::parse("* * * *").is_err());
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("5-1 * * * *").is_err());
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        let next = |expression| CronSchedule::parse(expression).unwrap().next_after(NOW);
+        // 2024-02-29 00:00
+        assert_eq!(next("* * * * *"), Some(1_709_164_800));
+        // 2024-02-29 03:00 - the leap day
+        assert_eq!(next("0 3 29 2 *"), Some(1_709_175_600));
+        // 2024-03-01 00:00 - Friday
+        assert_eq!(next("0 0 * * 5"), Some(1_709_251_200));
+        // 2024-02-29 00:00 - the day of month OR Thursday
+        assert_eq!(next("0 0 1 * 4"), Some(1_709_164_800));
+        assert_eq!(next("0 0 31 2 *"), None);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::actor::{sessions, Index};
+use crate::scheduler;
 use actix_web::web::Bytes;
-use actix_web::{rt, Error};
+use actix_web::Error;
 use chashmap::CHashMap;
 use futures::Stream;
 use moonlight::SessionId;
@@ -10,7 +11,6 @@ use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::Duration;
 use tokio::sync::mpsc::{error::SendError, unbounded_channel, UnboundedReceiver, UnboundedSender};
-use tokio::time::{interval_at, Instant};
 
 pub type ShareableSSE = Arc<SSE>;
 
@@ -100,10 +100,10 @@ pub trait ShareableSSEMethods {
 impl ShareableSSEMethods for ShareableSSE {
     fn spawn_connection_remover(&self) {
         let this = self.clone();
-        rt::spawn(async move {
-            let mut interval = interval_at(Instant::now(), Duration::from_secs(10));
-            loop {
-                interval.tick().await;
+        // The first ping is sent right away, not after one period.
+        scheduler::every_immediate(
+            Duration::from_secs(10),
+            scheduler::sync_job(move || {
                 this.connections.retain(|session_id, connection| {
                     let active = connection.send("ping", "").is_ok();
                     if !active && connection.remove_session_actor_on_remove {
@@ -113,8 +113,8 @@ impl ShareableSSEMethods for ShareableSSE {
                     }
                     active
                 });
-            }
-        });
+            }),
+        );
     }
 
     fn new_connection(&self, session_id: Option<SessionId>) -> (Arc<Connection>, EventStream) {
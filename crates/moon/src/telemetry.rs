@@ -0,0 +1,409 @@
+use crate::config::Otlp;
+use chashmap::CHashMap;
+use moonlight::{serde_json, CorId, SessionId, Ulid};
+use once_cell::sync::Lazy;
+use std::{
+    future::Future,
+    io::{Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    str::FromStr,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::{
+    field::{Field, Visit},
+    span, Instrument, Span, Subscriber,
+};
+use tracing_subscriber::{
+    filter::LevelFilter, layer::Context, prelude::*, registry::LookupSpan, Layer,
+};
+use uuid::Uuid;
+
+const MAX_BATCH_LEN: usize = 256;
+const BATCH_TIMEOUT: Duration = Duration::from_secs(2);
+// Spans are dropped when the exporter falls behind, e.g. because of a slow collector.
+const MAX_QUEUED_SPANS: usize = 16 * MAX_BATCH_LEN;
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(5);
+// `SPAN_KIND_INTERNAL` in the OTLP spec.
+const SPAN_KIND_INTERNAL: u8 = 1;
+
+// ------ init ------
+
+/// Logs `log` records and `tracing` events filtered by `BACKEND_LOG_LEVEL`
+/// with the current span context (e.g. `session_id` and `cor_id` of the handled `UpMsg`)
+/// and exports spans to an OTLP collector when `OTLP_ENDPOINT` is set.
+pub(crate) fn init(backend_log_level: log::LevelFilter, otlp: &Otlp) {
+    let level_filter = match backend_log_level {
+        log::LevelFilter::Off => LevelFilter::OFF,
+        log::LevelFilter::Error => LevelFilter::ERROR,
+        log::LevelFilter::Warn => LevelFilter::WARN,
+        log::LevelFilter::Info => LevelFilter::INFO,
+        log::LevelFilter::Debug => LevelFilter::DEBUG,
+        log::LevelFilter::Trace => LevelFilter::TRACE,
+    };
+    let otlp_layer = otlp
+        .endpoint
+        .as_ref()
+        .map(|endpoint| OtlpLayer::new(endpoint.clone(), otlp.service_name.clone()));
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_filter(level_filter))
+        .with(otlp_layer)
+        .init();
+}
+
+// ------ UpMsg / DownMsg spans ------
+
+static UP_MSG_SPANS: Lazy<CHashMap<CorId, Span>> = Lazy::new(CHashMap::new);
+
+/// Runs the `UpMsg` handler in a span with `session_id` and `cor_id`.
+/// DownMsgs sent with the same `CorId` while the handler runs are linked to it.
+pub(crate) async fn in_up_msg_span<F: Future>(
+    session_id: SessionId,
+    cor_id: CorId,
+    handler: F,
+) -> F::Output {
+    struct Registration(CorId);
+    impl Drop for Registration {
+        fn drop(&mut self) {
+            UP_MSG_SPANS.remove(&self.0);
+        }
+    }
+
+    let span = tracing::info_span!("up_msg", session_id = %session_id, cor_id = %cor_id);
+    UP_MSG_SPANS.insert(cor_id, span.clone());
+    let _registration = Registration(cor_id);
+    handler.instrument(span).await
+}
+
+pub(crate) fn down_msg_span(session_id: SessionId, cor_id: CorId) -> Span {
+    let span = tracing::info_span!("down_msg", session_id = %session_id, cor_id = %cor_id);
+    if let Some(up_msg_span) = UP_MSG_SPANS.get(&cor_id) {
+        span.follows_from(&*up_msg_span);
+    }
+    span
+}
+
+// ------ SpanData ------
+
+#[derive(Debug, Clone)]
+struct SpanData {
+    trace_id: u128,
+    span_id: u64,
+    parent_span_id: Option<u64>,
+    name: &'static str,
+    start: SystemTime,
+    end: SystemTime,
+    attributes: Vec<(&'static str, String)>,
+    links: Vec<(u128, u64)>,
+}
+
+struct AttributeVisitor<'a>(&'a mut Vec<(&'static str, String)>);
+
+impl Visit for AttributeVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name(), value.to_owned()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name(), format!("{value:?}")));
+    }
+}
+
+// ------ OtlpLayer ------
+
+struct OtlpLayer {
+    sender: SyncSender<SpanData>,
+}
+
+impl OtlpLayer {
+    fn new(endpoint: String, service_name: String) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(MAX_QUEUED_SPANS);
+        thread::Builder::new()
+            .name("otlp_exporter".to_owned())
+            .spawn(move || run_exporter(receiver, &endpoint, &service_name))
+            .expect("failed to spawn the OTLP exporter thread");
+        Self { sender }
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for OtlpLayer {
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut attributes = Vec::new();
+        attrs.record(&mut AttributeVisitor(&mut attributes));
+
+        let parent = span
+            .parent()
+            .and_then(|parent| parent.extensions().get::<SpanData>().cloned());
+        // The `CorId` becomes the trace id to find traces by ids logged in the frontend.
+        let cor_id_trace_id = attributes
+            .iter()
+            .find(|(key, _)| *key == "cor_id")
+            .and_then(|(_, cor_id)| Ulid::from_str(cor_id).ok())
+            .map(u128::from);
+        let trace_id = parent
+            .as_ref()
+            .map(|parent| parent.trace_id)
+            .or(cor_id_trace_id)
+            .unwrap_or_else(|| Uuid::new_v4().as_u128());
+
+        span.extensions_mut().insert(SpanData {
+            trace_id,
+            span_id: new_span_id(),
+            parent_span_id: parent.map(|parent| parent.span_id),
+            name: attrs.metadata().name(),
+            start: SystemTime::now(),
+            end: SystemTime::now(),
+            attributes,
+            links: Vec::new(),
+        });
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(span_data) = span.extensions_mut().get_mut::<SpanData>() {
+                values.record(&mut AttributeVisitor(&mut span_data.attributes));
+            }
+        }
+    }
+
+    fn on_follows_from(&self, id: &span::Id, follows: &span::Id, ctx: Context<'_, S>) {
+        let Some(follows) = ctx
+            .span(follows)
+            .and_then(|follows| follows.extensions().get::<SpanData>().cloned())
+        else {
+            return;
+        };
+        if let Some(span) = ctx.span(id) {
+            if let Some(span_data) = span.extensions_mut().get_mut::<SpanData>() {
+                span_data.links.push((follows.trace_id, follows.span_id));
+            }
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(mut span_data) = span.extensions_mut().remove::<SpanData>() else {
+            return;
+        };
+        span_data.end = SystemTime::now();
+        // Never blocks, the span is dropped when the queue is full.
+        let _ = self.sender.try_send(span_data);
+    }
+}
+
+fn new_span_id() -> u64 {
+    Uuid::new_v4().as_u64_pair().0
+}
+
+// ------ exporter ------
+
+fn run_exporter(receiver: Receiver<SpanData>, endpoint: &str, service_name: &str) {
+    let mut batch = Vec::new();
+    let mut batch_started = Instant::now();
+    loop {
+        let disconnected = match receiver.recv_timeout(BATCH_TIMEOUT) {
+            Ok(span_data) => {
+                if batch.is_empty() {
+                    batch_started = Instant::now();
+                }
+                batch.push(span_data);
+                false
+            }
+            Err(RecvTimeoutError::Timeout) => false,
+            Err(RecvTimeoutError::Disconnected) => true,
+        };
+        let batch_ready = batch.len() >= MAX_BATCH_LEN || batch_started.elapsed() >= BATCH_TIMEOUT;
+        if !batch.is_empty() && (batch_ready || disconnected) {
+            // Spans are dropped when the collector isn't available.
+            if let Err(error) = export_spans(endpoint, service_name, &batch) {
+                eprintln!("OTLP export to '{endpoint}' failed: {error}");
+            }
+            batch.clear();
+        }
+        if disconnected {
+            return;
+        }
+    }
+}
+
+/// Sends spans in the OTLP/HTTP JSON format to `<endpoint>/v1/traces`.
+/// Only plain `http://` endpoints are supported - e.g. a local collector or an agent sidecar.
+fn export_spans(endpoint: &str, service_name: &str, spans: &[SpanData]) -> Result<(), String> {
+    let authority = endpoint
+        .strip_prefix("http://")
+        .ok_or("only 'http://' endpoints are supported")?
+        .trim_end_matches('/');
+    let (host, base_path) = match authority.split_once('/') {
+        Some((host, base_path)) => (host, format!("/{base_path}")),
+        None => (authority, String::new()),
+    };
+    let body = traces_json(service_name, spans).to_string();
+
+    let mut stream = connect(host)?;
+    write!(
+        stream,
+        "POST {base_path}/v1/traces HTTP/1.1\r\n\
+        Host: {host}\r\n\
+        Content-Type: application/json\r\n\
+        Content-Length: {}\r\n\
+        Connection: close\r\n\r\n\
+        {body}",
+        body.len()
+    )
+    .map_err(|error| error.to_string())?;
+
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .map_err(|error| error.to_string())?;
+    let status_line = response.lines().next().unwrap_or_default();
+    if status_line
+        .split(' ')
+        .nth(1)
+        .is_some_and(|status| status.starts_with('2'))
+    {
+        Ok(())
+    } else {
+        Err(format!("unexpected response '{status_line}'"))
+    }
+}
+
+fn connect(host: &str) -> Result<TcpStream, String> {
+    let mut last_error = format!("'{host}' doesn't resolve to any address");
+    for address in host.to_socket_addrs().map_err(|error| error.to_string())? {
+        match TcpStream::connect_timeout(&address, EXPORT_TIMEOUT) {
+            Ok(stream) => {
+                stream
+                    .set_read_timeout(Some(EXPORT_TIMEOUT))
+                    .map_err(|error| error.to_string())?;
+                stream
+                    .set_write_timeout(Some(EXPORT_TIMEOUT))
+                    .map_err(|error| error.to_string())?;
+                return Ok(stream);
+            }
+            Err(error) => last_error = error.to_string(),
+        }
+    }
+    Err(last_error)
+}
+
+fn traces_json(service_name: &str, spans: &[SpanData]) -> serde_json::Value {
+    let unix_nanos = |time: SystemTime| {
+        time.duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()
+            .to_string()
+    };
+    let spans = spans
+        .iter()
+        .map(|span| {
+            let mut span_json = serde_json::json!({
+                "traceId": format!("{:032x}", span.trace_id),
+                "spanId": format!("{:016x}", span.span_id),
+                "name": span.name,
+                "kind": SPAN_KIND_INTERNAL,
+                "startTimeUnixNano": unix_nanos(span.start),
+                "endTimeUnixNano": unix_nanos(span.end),
+                "attributes": span.attributes.iter().map(|(key, value)| serde_json::json!({
+                    "key": key,
+                    "value": { "stringValue": value },
+                })).collect::<Vec<_>>(),
+                "links": span.links.iter().map(|(trace_id, span_id)| serde_json::json!({
+                    "traceId": format!("{trace_id:032x}"),
+                    "spanId": format!("{span_id:016x}"),
+                })).collect::<Vec<_>>(),
+            });
+            if let Some(parent_span_id) = span.parent_span_id {
+                span_json["parentSpanId"] = format!("{parent_span_id:016x}").into();
+            }
+            span_json
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [{
+                    "key": "service.name",
+                    "value": { "stringValue": service_name },
+                }],
+            },
+            "scopeSpans": [{
+                "scope": { "name": "moon" },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_export_spans_to_collector_stand_in() {
+        let collector = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", collector.local_addr().unwrap());
+
+        let collector = thread::spawn(move || {
+            let (mut stream, _) = collector.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 4096];
+            loop {
+                let read = stream.read(&mut buffer).unwrap();
+                assert!(
+                    read > 0,
+                    "connection closed before the whole request was received"
+                );
+                request.extend_from_slice(&buffer[..read]);
+
+                let request = String::from_utf8_lossy(&request);
+                let Some((head, body)) = request.split_once("\r\n\r\n") else {
+                    continue;
+                };
+                let content_length = head
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Content-Length: "))
+                    .unwrap()
+                    .parse::<usize>()
+                    .unwrap();
+                if body.len() >= content_length {
+                    break;
+                }
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let cor_id = CorId::new();
+        let span = SpanData {
+            trace_id: u128::from(Ulid::from_str(&cor_id.to_string()).unwrap()),
+            span_id: 42,
+            parent_span_id: None,
+            name: "up_msg",
+            start: SystemTime::now(),
+            end: SystemTime::now(),
+            attributes: vec![("cor_id", cor_id.to_string())],
+            links: Vec::new(),
+        };
+        export_spans(&endpoint, "moon_test", &[span]).unwrap();
+
+        let request = collector.join().unwrap();
+        assert!(request.starts_with("POST /v1/traces HTTP/1.1\r\n"));
+        assert!(request.contains(r#""spanId":"000000000000002a""#));
+        assert!(request.contains(&cor_id.to_string()));
+        assert!(request.contains("moon_test"));
+    }
+}
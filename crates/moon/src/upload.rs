@@ -0,0 +1,295 @@
+use crate::sse::ShareableSSEMethods;
+use crate::{backplane, parse_auth_token, parse_cor_id, parse_session_id, MessageSSE};
+use crate::{Lazy, INSTANCE_HEADER};
+use actix_multipart::Multipart;
+use actix_web::{
+    error::{self, Error, ResponseError},
+    http::{
+        header::{self, ContentDisposition, DispositionParam, DispositionType},
+        StatusCode,
+    },
+    web::{self, Bytes},
+    HttpMessage, HttpRequest, HttpResponse,
+};
+use futures::{Stream, StreamExt};
+use moonlight::{serde_json, AuthToken, CorId, SessionId, UploadProgress};
+use parking_lot::RwLock;
+use std::{
+    cell::Cell,
+    fmt,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+// NOTE: Sync with Zoon's `Connection::upload_file`.
+const FILE_NAME_HEADER: &str = "X-Upload-File-Name";
+const DEFAULT_MAX_UPLOAD_BYTES: u64 = 100 * 1_048_576;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+type Handler =
+    Box<dyn Fn(Upload) -> Pin<Box<dyn Future<Output = Result<(), Error>>>> + Send + Sync>;
+
+static HANDLER: Lazy<RwLock<Option<Handler>>> = Lazy::new(<_>::default);
+static MAX_UPLOAD_BYTES: AtomicU64 = AtomicU64::new(DEFAULT_MAX_UPLOAD_BYTES);
+
+pub type UploadStream = Pin<Box<dyn Stream<Item = Result<Bytes, UploadError>>>>;
+
+// ------ Upload ------
+
+/// A file streamed to `/_api/upload`, either as the raw request body
+/// or as a file field of a `multipart/form-data` request.
+pub struct Upload {
+    pub session_id: SessionId,
+    pub cor_id: CorId,
+    pub auth_token: Option<AuthToken>,
+    /// The `X-Upload-File-Name` header or the `filename` of the multipart field.
+    pub file_name: String,
+    pub content_type: Option<String>,
+    /// Fails with `UploadError::TooLarge` when the request exceeds the limit.
+    pub chunks: UploadStream,
+}
+
+impl fmt::Debug for Upload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Upload")
+            .field("session_id", &self.session_id)
+            .field("cor_id", &self.cor_id)
+            .field("file_name", &self.file_name)
+            .field("content_type", &self.content_type)
+            .finish_non_exhaustive()
+    }
+}
+
+// ------ UploadError ------
+
+#[derive(Debug)]
+pub enum UploadError {
+    TooLarge { limit: u64 },
+    Payload(String),
+}
+
+impl fmt::Display for UploadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge { limit } => write!(f, "upload is larger than {limit} bytes"),
+            Self::Payload(error) => write!(f, "failed to read the upload: {error}"),
+        }
+    }
+}
+
+impl ResponseError for UploadError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::TooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Payload(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl UploadError {
+    fn payload(error: impl fmt::Display) -> Self {
+        Self::Payload(error.to_string())
+    }
+}
+
+// ------ public ------
+
+/// Handles files uploaded to `/_api/upload`, e.g. by Zoon's `Connection::upload_file`.
+/// Requests fail with `404` until the handler is set.
+///
+/// The handler is called once for a raw body and once for each file field of a multipart request.
+/// Return an error (e.g. `error::ErrorForbidden`) to fail the request.
+pub fn set_upload_handler<F>(handler: impl Fn(Upload) -> F + Send + Sync + 'static)
+where
+    F: Future<Output = Result<(), Error>> + 'static,
+{
+    *HANDLER.write() = Some(Box::new(move |upload| Box::pin(handler(upload))));
+}
+
+/// Maximum size of the upload request body, 100 MiB by default.
+pub fn set_max_upload_bytes(max_upload_bytes: u64) {
+    MAX_UPLOAD_BYTES.store(max_upload_bytes, Ordering::SeqCst);
+}
+
+/// Streams the file to the browser as an attachment. Use it in your own routes.
+pub fn download_response<E>(
+    file_name: &str,
+    content_type: mime::Mime,
+    chunks: impl Stream<Item = Result<Bytes, E>> + 'static,
+) -> HttpResponse
+where
+    E: Into<Box<dyn std::error::Error>> + 'static,
+{
+    HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(file_name.to_owned())],
+        })
+        .streaming(chunks)
+}
+
+// ------ private ------
+
+pub(crate) async fn upload_responder(
+    req: HttpRequest,
+    payload: web::Payload,
+    message_sse: web::Data<MessageSSE>,
+) -> Result<HttpResponse, Error> {
+    if HANDLER.read().is_none() {
+        Err(error::ErrorNotFound("upload handler isn't set"))?
+    }
+    let headers = req.headers();
+    let session_id = parse_session_id(headers)?;
+    let cor_id = parse_cor_id(headers)?;
+    let auth_token = parse_auth_token(headers)?;
+
+    let limit = MAX_UPLOAD_BYTES.load(Ordering::SeqCst);
+    let total = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|length| length.to_str().ok()?.parse::<u64>().ok());
+    if total.is_some_and(|total| total > limit) {
+        Err(UploadError::TooLarge { limit })?
+    }
+    let progress = Rc::new(Progress {
+        message_sse: MessageSSE::clone(&message_sse),
+        session_id,
+        cor_id,
+        limit,
+        total,
+        received: Cell::new(0),
+        last_report: Cell::new(Instant::now()),
+    });
+    let upload = |file_name, content_type, chunks| Upload {
+        session_id,
+        cor_id,
+        auth_token: auth_token.clone(),
+        file_name,
+        content_type,
+        chunks,
+    };
+
+    let is_multipart = req.mime_type().ok().flatten().is_some_and(|content_type| {
+        content_type.type_() == mime::MULTIPART && content_type.subtype() == mime::FORM_DATA
+    });
+    if is_multipart {
+        let mut multipart = Multipart::new(headers, payload);
+        while let Some(field) = multipart.next().await {
+            let mut field = field.map_err(UploadError::payload)?;
+            let Some(file_name) = field
+                .content_disposition()
+                .get_filename()
+                .map(str::to_owned)
+            else {
+                // Skip non-file fields.
+                while let Some(chunk) = field.next().await {
+                    chunk.map_err(UploadError::payload)?;
+                }
+                continue;
+            };
+            let content_type = field.content_type().map(ToString::to_string);
+            let chunks = Rc::clone(&progress).count(field);
+            call_handler(upload(file_name, content_type, chunks)).await?;
+        }
+    } else {
+        let file_name = headers
+            .get(FILE_NAME_HEADER)
+            .and_then(|file_name| file_name.to_str().ok())
+            .map(percent_decode)
+            .unwrap_or_default();
+        let content_type = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .map(str::to_owned);
+        let chunks = Rc::clone(&progress).count(payload);
+        call_handler(upload(file_name, content_type, chunks)).await?;
+    }
+    progress.report(true);
+
+    Ok(HttpResponse::Ok()
+        .insert_header((INSTANCE_HEADER, backplane::instance_id()))
+        .finish())
+}
+
+async fn call_handler(upload: Upload) -> Result<(), Error> {
+    // Don't hold the lock while the handler runs.
+    let future = match HANDLER.read().as_ref() {
+        Some(handler) => handler(upload),
+        None => Err(error::ErrorNotFound("upload handler isn't set"))?,
+    };
+    future.await
+}
+
+// ------ Progress ------
+
+struct Progress {
+    message_sse: MessageSSE,
+    session_id: SessionId,
+    cor_id: CorId,
+    limit: u64,
+    total: Option<u64>,
+    received: Cell<u64>,
+    last_report: Cell<Instant>,
+}
+
+impl Progress {
+    /// Counts received bytes, enforces the limit and reports progress.
+    fn count<E: fmt::Display>(
+        self: Rc<Self>,
+        chunks: impl Stream<Item = Result<Bytes, E>> + 'static,
+    ) -> UploadStream {
+        Box::pin(chunks.map(move |chunk| {
+            let chunk = chunk.map_err(UploadError::payload)?;
+            let received = self.received.get() + chunk.len() as u64;
+            if received > self.limit {
+                Err(UploadError::TooLarge { limit: self.limit })?
+            }
+            self.received.set(received);
+            if self.last_report.get().elapsed() >= PROGRESS_INTERVAL {
+                self.report(false);
+            }
+            Ok(chunk)
+        }))
+    }
+
+    fn report(&self, done: bool) {
+        self.last_report.set(Instant::now());
+        let progress = UploadProgress {
+            cor_id: self.cor_id,
+            received: self.received.get(),
+            total: self.total,
+            done,
+        };
+        let progress = serde_json::to_string(&progress).unwrap();
+        let _ = self
+            .message_sse
+            .send(&self.session_id, "upload_progress", &progress);
+    }
+}
+
+/// Decodes file names encoded by `encodeURIComponent` in the browser.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .filter(|_| bytes[index] == b'%')
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -0,0 +1,23 @@
+use crate::*;
+
+// ------ ClientLogRecord ------
+
+/// A frontend error shipped by Zoon's `client_log` to Moon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientLogRecord {
+    pub kind: ClientLogKind,
+    pub message: String,
+    /// The page URL.
+    pub url: String,
+    /// Milliseconds since the Unix epoch (`Date.now()` in the browser).
+    pub timestamp: f64,
+}
+
+// ------ ClientLogKind ------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClientLogKind {
+    Panic,
+    ConsoleError,
+    RequestFailed,
+}
@@ -16,6 +16,9 @@ pub use chrono::{self, prelude::*, Duration};
 mod auth_token;
 pub use auth_token::AuthToken;
 
+mod client_log;
+pub use client_log::{ClientLogKind, ClientLogRecord};
+
 mod cor_id;
 pub use cor_id::CorId;
 
@@ -27,3 +30,6 @@ pub use entity_id::EntityId;
 
 mod session_id;
 pub use session_id::SessionId;
+
+mod upload_progress;
+pub use upload_progress::UploadProgress;
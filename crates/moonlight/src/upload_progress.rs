@@ -0,0 +1,30 @@
+use crate::*;
+
+// ------ UploadProgress ------
+
+/// Sent by Moon through the session's SSE stream (event `upload_progress`) while receiving
+/// a file uploaded by Zoon's `Connection::upload_file`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UploadProgress {
+    /// `CorId` of the upload request.
+    pub cor_id: CorId,
+    /// Received bytes.
+    pub received: u64,
+    /// `Content-Length` of the request, `None` when unknown.
+    pub total: Option<u64>,
+    /// `true` in the last message sent after the upload handler has finished successfully.
+    pub done: bool,
+}
+
+impl UploadProgress {
+    /// `received / total` in the range `0.0..=1.0`, `None` when `total` is unknown.
+    pub fn ratio(&self) -> Option<f64> {
+        match self.total {
+            _ if self.done => Some(1.),
+            Some(0) => Some(1.),
+            Some(total) => Some((self.received as f64 / total as f64).min(1.)),
+            None => None,
+        }
+    }
+}
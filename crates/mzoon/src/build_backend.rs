@@ -1,3 +1,4 @@
+use crate::embedded_assets::create_embedded_assets_archive;
 use crate::BuildMode;
 use anyhow::{anyhow, Context, Error};
 use apply::Apply;
@@ -12,7 +13,7 @@ use uuid::Uuid;
 // -- public --
 
 #[throws]
-pub async fn build_backend(build_mode: BuildMode, https: bool) {
+pub async fn build_backend(build_mode: BuildMode, https: bool, embed_assets: bool) {
     println!("Building backend...");
 
     if https {
@@ -26,6 +27,16 @@ pub async fn build_backend(build_mode: BuildMode, https: bool) {
         BuildMode::Release => args.push("--release"),
     }
 
+    let mut envs = Vec::new();
+    if embed_assets {
+        let archive_path = create_embedded_assets_archive().await?;
+        args.extend(["--features", "moon/embedded_assets"]);
+        envs.push((
+            "MOON_EMBEDDED_ASSETS".to_owned(),
+            archive_path.into_os_string(),
+        ));
+    }
+
     // https://doc.rust-lang.org/cargo/reference/environment-variables.html#configuration-environment-variables
     let mut cargo_configs = Vec::new();
     if build_mode.is_not_dev() {
@@ -38,7 +49,13 @@ pub async fn build_backend(build_mode: BuildMode, https: bool) {
     let profile_env_name = build_mode.env_name();
     let envs = cargo_configs
         .into_iter()
-        .map(|(key, value)| (format!("CARGO_PROFILE_{profile_env_name}_{key}"), value));
+        .map(|(key, value)| {
+            (
+                format!("CARGO_PROFILE_{profile_env_name}_{key}"),
+                value.into(),
+            )
+        })
+        .chain(envs);
 
     Command::new("cargo")
         .args(&args)
@@ -1,8 +1,11 @@
+use crate::config::Tools;
 use crate::helper::{
-    visit_files,
+    create_compressed_files, visit_files,
     workspace_member::{web_worker_workspace_members, WorkspaceMember},
-    AsyncReadToVec, BrotliFileCompressor, FileCompressor, GzipFileCompressor,
 };
+use crate::public_assets::process_public_assets;
+use crate::size_report::SizeReport;
+use crate::symbol_map::create_symbol_map;
 use crate::wasm_bindgen::{build_with_wasm_bindgen, check_or_install_wasm_bindgen};
 use crate::wasm_opt::{check_or_install_wasm_opt, optimize_with_wasm_opt};
 use crate::BuildMode;
@@ -14,7 +17,6 @@ use std::{
     env, future,
     path::{Path, PathBuf},
     str,
-    sync::Arc,
 };
 use tokio::{fs, process::Command, select, sync::watch, try_join};
 use uuid::Uuid;
@@ -27,6 +29,8 @@ pub async fn build_frontend(
     cache_busting: bool,
     frontend_dist: bool,
     frontend_multithreading: bool,
+    tools: &Tools,
+    mut size_report: Option<&mut SizeReport>,
     compilation_killer: Option<watch::Receiver<()>>,
 ) {
     println!("Building frontend...");
@@ -58,9 +62,10 @@ pub async fn build_frontend(
         remove_pkg(&path.join("pkg")).await?;
     }
 
-    check_or_install_wasm_bindgen().await?;
+    let wasm_bindgen_path = check_or_install_wasm_bindgen(tools).await?;
 
     build_with_wasm_bindgen(
+        &wasm_bindgen_path,
         build_mode,
         "frontend",
         Path::new("frontend"),
@@ -72,16 +77,44 @@ pub async fn build_frontend(
     )
     .await?;
     for WorkspaceMember { name, path, .. } in &web_workers {
-        build_with_wasm_bindgen(build_mode, name, path, "no-modules").await?;
+        build_with_wasm_bindgen(&wasm_bindgen_path, build_mode, name, path, "no-modules").await?;
+    }
+
+    if let Some(size_report) = size_report.as_deref_mut() {
+        size_report
+            .record_raw_sizes("frontend", Path::new("frontend"))
+            .await?;
+        for WorkspaceMember { name, path, .. } in &web_workers {
+            size_report.record_raw_sizes(name, path).await?;
+        }
     }
 
     write_build_id(build_id).await?;
 
     if build_mode.is_not_dev() {
-        check_or_install_wasm_opt().await?;
-        optimize_with_wasm_opt(build_mode, "frontend", Path::new("frontend")).await?;
+        let wasm_opt_path = check_or_install_wasm_opt(tools).await?;
+        optimize_with_wasm_opt(
+            &wasm_opt_path,
+            build_mode,
+            "frontend",
+            Path::new("frontend"),
+        )
+        .await?;
         for WorkspaceMember { name, path, .. } in &web_workers {
-            optimize_with_wasm_opt(build_mode, name, path).await?;
+            optimize_with_wasm_opt(&wasm_opt_path, build_mode, name, path).await?;
+        }
+    }
+
+    if build_mode.is_not_release() {
+        create_symbol_map("frontend", Path::new("frontend")).await?;
+    }
+
+    if let Some(size_report) = size_report {
+        size_report
+            .record_optimized_sizes("frontend", Path::new("frontend"))
+            .await?;
+        for WorkspaceMember { name, path, .. } in &web_workers {
+            size_report.record_optimized_sizes(name, path).await?;
         }
     }
 
@@ -106,6 +139,10 @@ pub async fn build_frontend(
         .await?;
     }
 
+    if cache_busting {
+        process_public_assets(build_mode.is_not_dev() && !frontend_dist).await?;
+    }
+
     println!("Frontend built");
 }
 
@@ -198,7 +235,8 @@ async fn compile_with_cargo(
     // https://doc.rust-lang.org/cargo/reference/environment-variables.html#configuration-environment-variables
     let mut cargo_configs = Vec::new();
     if build_mode.is_dev() {
-        cargo_configs.push(("DEBUG", "false"));
+        // Enough for symbolicated panics, see `symbol_map.rs`.
+        cargo_configs.push(("DEBUG", "line-tables-only"));
     } else {
         cargo_configs.extend([("OPT_LEVEL", "z"), ("CODEGEN_UNITS", "1")]);
         if !frontend_multithreading {
@@ -389,15 +427,3 @@ async fn compress_pkg(
         }
     )?
 }
-
-#[throws]
-async fn create_compressed_files(file_path: impl AsRef<Path>) {
-    let file_path = file_path.as_ref();
-    let content = Arc::new(fs::File::open(&file_path).await?.read_to_vec().await?);
-
-    try_join!(
-        BrotliFileCompressor::compress_file(Arc::clone(&content), file_path, "br"),
-        GzipFileCompressor::compress_file(content, file_path, "gz"),
-    )
-    .with_context(|| format!("Failed to create compressed files for {file_path:?}"))?
-}
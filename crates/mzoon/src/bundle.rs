@@ -0,0 +1,176 @@
+use crate::config::Config;
+use crate::helper::{
+    backend_binary_path,
+    workspace_member::{web_worker_workspace_members, WorkspaceMember},
+};
+use crate::set_env_vars::env_vars;
+use crate::BuildMode;
+use anyhow::{Context, Error};
+use const_format::concatcp;
+use fehler::throws;
+use flate2::{write::GzEncoder, Compression as GzCompression};
+use fs_extra::dir;
+use std::{env, fs::File, path::Path};
+use tokio::{fs, task};
+
+const BUNDLE_DIR: &str = "bundle";
+const BUNDLE_TAR_GZ: &str = "bundle.tar.gz";
+const BINARY_NAME: &str = concatcp!("moon_app", env::consts::EXE_SUFFIX);
+const ENV_FILE_NAME: &str = "moon.env";
+
+// -- public --
+
+/// Creates a self-contained folder with everything the Moon server reads at runtime
+/// and packs it into a tarball.
+#[throws]
+pub async fn create_bundle(build_mode: BuildMode, config: &Config, dockerfile: bool) {
+    println!("Creating bundle...");
+
+    recreate_bundle_dir().await?;
+    fs::copy(
+        backend_binary_path(build_mode)?,
+        Path::new(BUNDLE_DIR).join(BINARY_NAME),
+    )
+    .await
+    .context("Failed to copy the backend binary to the bundle")?;
+    task::spawn_blocking(copy_assets_sync).await??;
+    copy_private_files(config).await?;
+
+    let env_vars = env_vars(config, build_mode, false);
+    write_env_file(&env_vars).await?;
+    if dockerfile {
+        // Custom env variables may contain secrets, they shouldn't be baked into image layers.
+        let env_vars = env_vars
+            .into_iter()
+            .filter(|(key, _)| {
+                !config
+                    .custom_env_vars
+                    .iter()
+                    .any(|(custom_key, _)| custom_key == key)
+            })
+            .collect::<Vec<_>>();
+        write_dockerfile(&env_vars, config).await?;
+    }
+    task::spawn_blocking(create_tar_gz_sync).await??;
+
+    println!("Bundle created in '{BUNDLE_DIR}' and '{BUNDLE_TAR_GZ}'");
+}
+
+// -- private --
+
+#[throws]
+async fn recreate_bundle_dir() {
+    if fs::metadata(BUNDLE_DIR).await.is_ok() {
+        fs::remove_dir_all(BUNDLE_DIR)
+            .await
+            .context("Failed to remove the old bundle")?;
+    }
+    fs::create_dir_all(concatcp!(BUNDLE_DIR, "/frontend"))
+        .await
+        .context("Failed to create the bundle folder")?;
+    fs::create_dir_all(concatcp!(BUNDLE_DIR, "/backend/private")).await?;
+}
+
+#[throws]
+fn copy_assets_sync() {
+    let copy_options = dir::CopyOptions::new();
+
+    dir::copy(
+        "frontend/pkg",
+        concatcp!(BUNDLE_DIR, "/frontend"),
+        &copy_options,
+    )
+    .context("Failed to copy frontend/pkg to the bundle")?;
+    if Path::new("public").is_dir() {
+        dir::copy("public", BUNDLE_DIR, &copy_options)
+            .context("Failed to copy public to the bundle")?;
+    }
+
+    // Moon can't list workspace members without Cargo at runtime,
+    // so Web Worker pkgs are copied to the same layout as embedded assets.
+    for WorkspaceMember { name, path } in web_worker_workspace_members()? {
+        let destination = Path::new(BUNDLE_DIR).join("web_workers").join(&name);
+        dir::create_all(&destination, false)?;
+        dir::copy(path.join("pkg"), &destination, &copy_options)
+            .with_context(|| format!("Failed to copy Web Worker '{name}' pkg to the bundle"))?;
+    }
+}
+
+#[throws]
+async fn copy_private_files(config: &Config) {
+    let mut file_names = vec!["build_id"];
+    if config.https {
+        file_names.extend(["public.pem", "private.pem"]);
+    }
+    for file_name in file_names {
+        let source = Path::new("backend/private").join(file_name);
+        let destination = Path::new(BUNDLE_DIR)
+            .join("backend/private")
+            .join(file_name);
+        fs::copy(&source, &destination)
+            .await
+            .with_context(|| format!("Failed to copy {source:?} to the bundle"))?;
+    }
+}
+
+#[throws]
+async fn write_env_file(env_vars: &[(String, String)]) {
+    let content = env_vars
+        .iter()
+        .map(|(key, value)| format!("{key}={value}\n"))
+        .collect::<String>();
+
+    fs::write(Path::new(BUNDLE_DIR).join(ENV_FILE_NAME), content)
+        .await
+        .context("Failed to write the bundle env file")?;
+}
+
+#[throws]
+async fn write_dockerfile(env_vars: &[(String, String)], config: &Config) {
+    let envs = env_vars
+        .iter()
+        .map(|(key, value)| format!("ENV {key}=\"{}\"\n", value.replace('"', "\\\"")))
+        .collect::<String>();
+
+    let mut ports = config.port.to_string();
+    if config.redirect.enabled {
+        ports.push_str(&format!(" {}", config.redirect.port));
+    }
+
+    let dockerfile = format!(
+        r#"# Generated by `mzoon build --bundle --dockerfile`
+FROM debian:bookworm-slim
+
+RUN apt-get update \
+    && apt-get install -y --no-install-recommends ca-certificates \
+    && rm -rf /var/lib/apt/lists/*
+
+WORKDIR /app
+COPY . .
+
+{envs}
+EXPOSE {ports}
+
+ENTRYPOINT ["./{BINARY_NAME}"]
+"#
+    );
+
+    fs::write(Path::new(BUNDLE_DIR).join("Dockerfile"), dockerfile)
+        .await
+        .context("Failed to write the bundle Dockerfile")?;
+    fs::write(
+        Path::new(BUNDLE_DIR).join(".dockerignore"),
+        format!("{ENV_FILE_NAME}\n"),
+    )
+    .await
+    .context("Failed to write the bundle .dockerignore")?;
+    println!("Dockerfile added to the bundle");
+}
+
+#[throws]
+fn create_tar_gz_sync() {
+    let tar_gz = File::create(BUNDLE_TAR_GZ).context("Failed to create the bundle tarball")?;
+    let mut tar_builder = tar::Builder::new(GzEncoder::new(tar_gz, GzCompression::default()));
+    tar_builder.append_dir_all(BUNDLE_DIR, BUNDLE_DIR)?;
+    tar_builder.into_inner()?.finish()?;
+}
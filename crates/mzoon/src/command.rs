@@ -1,7 +1,9 @@
 mod build;
+mod check;
 mod new;
 mod start;
 
-pub use build::build;
+pub use build::{build, BuildOptions};
+pub use check::check;
 pub use new::new;
 pub use start::start;
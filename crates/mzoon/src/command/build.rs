@@ -1,28 +1,92 @@
 use crate::build_backend::build_backend;
 use crate::build_frontend::build_frontend;
+use crate::bundle::create_bundle;
 use crate::config::Config;
-use crate::frontend_dist::create_frontend_dist;
+use crate::frontend_dist::{create_frontend_dist, set_base_path_env_var};
 use crate::set_env_vars::set_env_vars;
+use crate::size_report::{SizeReport, SizeReportArgs, SIZE_REPORT_PATH};
 use crate::{BuildMode, Hosting};
 use anyhow::Error;
 use fehler::throws;
+use std::path::Path;
+
+// ------ BuildOptions ------
+
+pub struct BuildOptions {
+    pub build_mode: BuildMode,
+    pub frontend_dist: bool,
+    pub hosting: Option<Hosting>,
+    pub base_path: Option<String>,
+    pub bundle: bool,
+    pub dockerfile: bool,
+    pub embed_assets: bool,
+    pub profile: Option<String>,
+    pub size_report_args: Option<SizeReportArgs>,
+}
+
+// ------ build ------
 
 #[throws]
-pub async fn build(build_mode: BuildMode, frontend_dist: bool, hosting: Option<Hosting>) {
-    let config = Config::load_from_moonzoon_tomls().await?;
+pub async fn build(options: BuildOptions) {
+    let BuildOptions {
+        build_mode,
+        frontend_dist,
+        hosting,
+        base_path,
+        bundle,
+        dockerfile,
+        embed_assets,
+        profile,
+        size_report_args,
+    } = options;
+    let config = Config::load_from_moonzoon_tomls(profile.as_deref()).await?;
     set_env_vars(&config, build_mode, frontend_dist);
+    set_base_path_env_var(base_path.as_deref());
+
+    let mut size_report = size_report_args
+        .is_some()
+        .then(|| SizeReport::new(build_mode));
 
     build_frontend(
         build_mode,
         config.cache_busting,
         frontend_dist,
         config.frontend_multithreading == Some(true),
+        &config.tools,
+        size_report.as_mut(),
         None,
     )
     .await?;
-    build_backend(build_mode, config.https).await?;
+    build_backend(build_mode, config.https, embed_assets).await?;
 
     if frontend_dist {
-        create_frontend_dist(build_mode, &config, hosting).await?;
+        create_frontend_dist(build_mode, &config, hosting, base_path.as_deref()).await?;
+    }
+
+    if bundle {
+        create_bundle(build_mode, &config, dockerfile).await?;
+    }
+
+    if let (Some(size_report), Some(size_report_args)) = (size_report, size_report_args) {
+        write_size_report(size_report, size_report_args).await?;
+    }
+}
+
+// -- private --
+
+#[throws]
+async fn write_size_report(size_report: SizeReport, size_report_args: SizeReportArgs) {
+    size_report.print();
+    // Load the baseline before writing in case it's the same file.
+    let baseline = match &size_report_args.baseline {
+        Some(baseline_path) => Some(SizeReport::load(baseline_path).await?),
+        None => None,
+    };
+    size_report.write(Path::new(SIZE_REPORT_PATH)).await?;
+    println!("Size report written to {SIZE_REPORT_PATH}");
+
+    if let Some(baseline) = baseline {
+        println!("Size changes:");
+        size_report.check_against(&baseline, size_report_args.tolerance)?;
     }
 }
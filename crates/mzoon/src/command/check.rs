@@ -0,0 +1,45 @@
+use crate::config::{with_profile, Config, MOONZOON_TOML, SOURCE};
+use anyhow::{bail, Context, Error};
+use config_schema::{ProblemCode, Severity};
+use fehler::throws;
+use tokio::fs;
+
+#[throws]
+pub async fn check() {
+    let config = Config::load_from_moonzoon_tomls(None).await?;
+
+    let config_toml = fs::read_to_string(MOONZOON_TOML)
+        .await
+        .context("Failed to read MoonZoon.toml")?;
+
+    let mut errors = Vec::new();
+    let mut configs = vec![(None, config)];
+    for profile in configs[0].1.profiles.keys().cloned().collect::<Vec<_>>() {
+        match with_profile(&config_toml, &profile) {
+            Ok(config) => configs.push((Some(profile), config)),
+            Err(error) => errors.push(format!("{error:#}")),
+        }
+    }
+
+    for (profile, config) in &configs {
+        let profile = profile
+            .as_ref()
+            .map(|profile| format!(" (profile `{profile}`)"))
+            .unwrap_or_default();
+        for problem in config.check_combinations() {
+            let description = format!("{}{profile}", problem.describe(SOURCE));
+            match problem.severity {
+                _ if problem.code == ProblemCode::MissingPemFiles => {
+                    println!("Note: {description}; they will be generated by `mzoon start/build`")
+                }
+                Severity::Error => errors.push(description),
+                Severity::Warning => eprintln!("Warning: {description}"),
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        bail!("Invalid {MOONZOON_TOML}:\n{}", errors.join("\n"));
+    }
+    println!("{MOONZOON_TOML} is valid");
+}
@@ -15,8 +15,8 @@ use tokio::{join, process::Child, signal, time::Duration};
 const DEBOUNCE_TIME: Duration = Duration::from_millis(600);
 
 #[throws]
-pub async fn start(build_mode: BuildMode, open: bool) {
-    let config = Config::load_from_moonzoon_tomls().await?;
+pub async fn start(build_mode: BuildMode, open: bool, profile: Option<String>) {
+    let config = Config::load_from_moonzoon_tomls(profile.as_deref()).await?;
     set_env_vars(&config, build_mode, false);
 
     let server = Arc::new(Mutex::new(None));
@@ -45,6 +45,8 @@ async fn build_and_watch_frontend(config: &Config, build_mode: BuildMode) -> Fro
         config.cache_busting,
         false,
         config.frontend_multithreading == Some(true),
+        &config.tools,
+        None,
         None,
     )
     .await
@@ -73,7 +75,7 @@ async fn build_and_run_backend(
     build_mode: BuildMode,
     server: &Mutex<Option<Child>>,
 ) {
-    if let Err(error) = build_backend(build_mode, config.https).await {
+    if let Err(error) = build_backend(build_mode, config.https, false).await {
         eprintln!("{error:#}");
         return;
     }
@@ -2,13 +2,19 @@ use crate::helper::{
     tree_into_pairs::{tree_into_pairs, NodeContent},
     TryIntoString,
 };
-use anyhow::{Context, Error};
+use anyhow::{anyhow, bail, Context, Error};
+use config_schema::{Problem, ProblemCode, Settings, Severity, Source};
 use fehler::throws;
 use log::LevelFilter;
 use serde::Deserialize;
+use std::{collections::BTreeMap, env, path::PathBuf};
 use tokio::fs;
 
+pub const MOONZOON_TOML: &str = "MoonZoon.toml";
+pub const SOURCE: Source<'static> = Source::Toml(MOONZOON_TOML);
+
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
     pub port: u16,
     pub https: bool,
@@ -18,49 +24,159 @@ pub struct Config {
     pub redirect: Redirect,
     pub cors: Cors,
     pub watch: Watch,
+    #[serde(default)]
+    pub otlp: Otlp,
+    #[serde(default)]
+    pub backplane: Backplane,
+    #[serde(default)]
+    pub tools: Tools,
+    /// Tables `[profile.<name>]`, validated while applied.
+    #[serde(default, rename = "profile")]
+    pub profiles: BTreeMap<String, toml::Table>,
     #[serde(skip)]
     pub custom_env_vars: Vec<(String, String)>,
+    #[serde(skip)]
+    pub profile: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Redirect {
     pub port: u16,
     pub enabled: bool,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Cors {
     pub origins: Vec<String>,
 }
 
+/// Export of Moon's tracing spans to an OpenTelemetry collector.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Otlp {
+    pub endpoint: Option<String>,
+    pub service_name: Option<String>,
+}
+
+/// Routing of DownMsgs between Moon instances.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Backplane {
+    pub address: Option<String>,
+    pub instance_id: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Watch {
     pub frontend: Vec<String>,
     pub backend: Vec<String>,
 }
 
+/// External build tools. Env variables override the values from `MoonZoon.toml`.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Tools {
+    // MZOON_WASM_BINDGEN
+    pub wasm_bindgen: Option<PathBuf>,
+    // MZOON_WASM_OPT
+    pub wasm_opt: Option<PathBuf>,
+    /// Never download tools, e.g. on air-gapped CI machines.
+    // MZOON_OFFLINE
+    #[serde(default)]
+    pub offline: bool,
+    /// Installation folder shared across projects.
+    // MZOON_TOOLS_CACHE_DIR
+    pub cache_dir: Option<PathBuf>,
+    /// Expected checksum of the downloaded archive; the published checksum file is used otherwise.
+    pub wasm_bindgen_sha256: Option<String>,
+    pub wasm_opt_sha256: Option<String>,
+}
+
+impl Tools {
+    fn apply_env_overrides(&mut self) {
+        if let Some(path) = env::var_os("MZOON_WASM_BINDGEN") {
+            self.wasm_bindgen = Some(path.into());
+        }
+        if let Some(path) = env::var_os("MZOON_WASM_OPT") {
+            self.wasm_opt = Some(path.into());
+        }
+        if let Ok(offline) = env::var("MZOON_OFFLINE") {
+            self.offline = matches!(offline.as_str(), "true" | "1");
+        }
+        if let Some(path) = env::var_os("MZOON_TOOLS_CACHE_DIR") {
+            self.cache_dir = Some(path.into());
+        }
+    }
+}
+
 impl Config {
+    /// Loads `MoonZoon.toml` and `MoonZoonCustom.toml`.
+    /// Tables `[profile.<name>]` of the selected `profile` are merged into the root tables.
     #[throws]
-    pub async fn load_from_moonzoon_tomls() -> Config {
-        let mut config = read_moonzoon_toml().await?;
-        if let Some(custom_env_vars) = read_moonzoon_custom_toml().await? {
+    pub async fn load_from_moonzoon_tomls(profile: Option<&str>) -> Config {
+        let mut config = read_moonzoon_toml(profile).await?;
+        if let Some(custom_env_vars) = read_moonzoon_custom_toml(profile).await? {
             config.custom_env_vars = custom_env_vars;
         }
+        config.profile = profile.map(ToOwned::to_owned);
+        config.tools.apply_env_overrides();
+
+        let mut errors = Vec::new();
+        for problem in config.check_combinations() {
+            match problem.severity {
+                // `mzoon` generates missing PEM files before the backend is built.
+                _ if problem.code == ProblemCode::MissingPemFiles => (),
+                Severity::Error => errors.push(problem.describe(SOURCE)),
+                Severity::Warning => eprintln!("Warning: {}", problem.describe(SOURCE)),
+            }
+        }
+        if !errors.is_empty() {
+            bail!("Invalid {MOONZOON_TOML}:\n{}", errors.join("\n"));
+        }
         config
     }
+
+    pub fn check_combinations(&self) -> Vec<Problem> {
+        Settings {
+            port: self.port,
+            https: self.https,
+            redirect_port: self.redirect.port,
+            redirect_enabled: self.redirect.enabled,
+            cors_origins_empty: self.cors.origins.is_empty(),
+        }
+        .check_combinations()
+    }
 }
 
 #[throws]
-async fn read_moonzoon_toml() -> Config {
-    let config_toml = fs::read_to_string("MoonZoon.toml")
+async fn read_moonzoon_toml(profile: Option<&str>) -> Config {
+    let config_toml = fs::read_to_string(MOONZOON_TOML)
         .await
         .context("Failed to read MoonZoon.toml")?;
 
-    toml::from_str(&config_toml).context("Failed to parse MoonZoon.toml")?
+    // Errors contain line numbers only when deserialized directly from the file content.
+    let config: Config = toml::from_str(&config_toml).context("Failed to parse MoonZoon.toml")?;
+    let Some(profile) = profile else {
+        return config;
+    };
+    with_profile(&config_toml, profile)?
+}
+
+/// Merges `[profile.<profile>]` into the root tables of `MoonZoon.toml`'s content.
+#[throws]
+pub fn with_profile(config_toml: &str, profile: &str) -> Config {
+    let config = toml::from_str(config_toml).context("Failed to parse MoonZoon.toml")?;
+    apply_profile(config, Some(profile), true)
+        .context("Failed to apply the profile in MoonZoon.toml")?
+        .try_into()
+        .with_context(|| format!("Failed to parse MoonZoon.toml with the profile `{profile}`"))?
 }
 
 #[throws]
-async fn read_moonzoon_custom_toml() -> Option<Vec<(String, String)>> {
+async fn read_moonzoon_custom_toml(profile: Option<&str>) -> Option<Vec<(String, String)>> {
     if fs::metadata("MoonZoonCustom.toml").await.is_err() {
         return None;
     }
@@ -71,6 +187,8 @@ async fn read_moonzoon_custom_toml() -> Option<Vec<(String, String)>> {
 
     let custom_config =
         toml::from_str(&custom_config_toml).context("Failed to parse MoonZoonCustom.toml")?;
+    let custom_config = apply_profile(custom_config, profile, false)
+        .context("Failed to apply the profile in MoonZoonCustom.toml")?;
     let pairs =
         toml_to_env_vars(custom_config).context("Failed to parse MoonZoonCustom.toml's content")?;
     Some(pairs)
@@ -92,3 +210,37 @@ fn toml_to_env_vars(toml: toml::Value) -> Vec<(String, String)> {
         },
     )?
 }
+
+/// Removes the `profile` table and merges the selected profile's table into the root table.
+/// Profile tables are merged recursively; other values (incl. arrays) are replaced.
+#[throws]
+fn apply_profile(mut toml: toml::Table, profile: Option<&str>, required: bool) -> toml::Value {
+    let profiles = toml.remove("profile");
+    if let Some(profile) = profile {
+        let profile_table = match profiles {
+            Some(toml::Value::Table(mut profiles)) => profiles.remove(profile),
+            Some(_) => Err(anyhow!("`profile` has to be a table"))?,
+            None => None,
+        };
+        match profile_table {
+            Some(toml::Value::Table(profile_table)) => merge_tables(&mut toml, profile_table),
+            Some(_) => Err(anyhow!("`profile.{profile}` has to be a table"))?,
+            None if required => Err(anyhow!("Profile `{profile}` not found"))?,
+            None => (),
+        }
+    }
+    toml::Value::Table(toml)
+}
+
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, overlay_value) in overlay {
+        let value = match (base.remove(&key), overlay_value) {
+            (Some(toml::Value::Table(mut base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(&mut base_table, overlay_table);
+                toml::Value::Table(base_table)
+            }
+            (_, overlay_value) => overlay_value,
+        };
+        base.insert(key, value);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::helper::workspace_member::{web_worker_workspace_members, WorkspaceMember};
+use anyhow::{Context, Error};
+use apply::Also;
+use cargo_metadata::MetadataCommand;
+use fehler::throws;
+use std::{fs::File, path::PathBuf};
+use tokio::task;
+
+// -- public --
+
+/// Packs `frontend/pkg` (incl. precompressed `.br` and `.gz` files), Web Worker pkgs and `public`
+/// into an uncompressed tar archive for Moon's `embedded_assets` feature.
+/// Returns the absolute path to the archive.
+#[throws]
+pub async fn create_embedded_assets_archive() -> PathBuf {
+    println!("Creating embedded assets archive...");
+    let archive_path = task::spawn_blocking(create_archive_sync).await??;
+    println!("Embedded assets archive created");
+    archive_path
+}
+
+// -- private --
+
+#[throws]
+fn create_archive_sync() -> PathBuf {
+    let archive_path: PathBuf = MetadataCommand::new()
+        .no_deps()
+        .exec()?
+        .target_directory
+        .also(|directory| directory.push("moon_embedded_assets.tar"))
+        .into();
+
+    let file = File::create(&archive_path).context("Failed to create embedded assets archive")?;
+    let mut tar_builder = tar::Builder::new(file);
+
+    tar_builder
+        .append_dir_all("frontend/pkg", "frontend/pkg")
+        .context("Failed to embed frontend/pkg")?;
+
+    if PathBuf::from("public").is_dir() {
+        tar_builder
+            .append_dir_all("public", "public")
+            .context("Failed to embed public")?;
+    }
+
+    for WorkspaceMember { name, path } in web_worker_workspace_members()? {
+        tar_builder
+            .append_dir_all(format!("web_workers/{name}/pkg"), path.join("pkg"))
+            .with_context(|| format!("Failed to embed Web Worker '{name}' pkg"))?;
+    }
+
+    tar_builder.finish()?;
+    archive_path
+}
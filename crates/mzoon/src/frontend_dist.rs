@@ -5,32 +5,46 @@ use crate::helper::{
 };
 use crate::run_backend::run_backend;
 use crate::{BuildMode, Hosting};
-use anyhow::Error;
+use anyhow::{Context, Error};
 use const_format::concatcp;
 use fehler::throws;
 use fs_extra::dir;
-use std::path::Path;
+use std::{env, path::Path};
 use tokio::{fs, task};
 
+mod hosting;
+
+use hosting::create_hosting_files;
+
 const FRONTEND_DIST_DIR: &str = "frontend_dist";
 const API_DIR: &str = concatcp!(FRONTEND_DIST_DIR, "/_api");
 
 // -- public --
 
+/// Sets `BASE_PATH` read by Zoon during the frontend compilation to prefix its URLs
+/// (`/_api/up_msg_handler`, `public_url`, router paths, etc.).
+pub fn set_base_path_env_var(base_path: Option<&str>) {
+    env::set_var(
+        "BASE_PATH",
+        normalize_base_path(base_path.unwrap_or_default()),
+    );
+}
+
 #[throws]
 pub async fn create_frontend_dist(
     build_mode: BuildMode,
     config: &Config,
     hosting: Option<Hosting>,
+    base_path: Option<&str>,
 ) {
     println!("Creating frontend_dist...");
 
     recreate_api_dir_with_frontend_dist().await?;
-    recreate_index_html(build_mode, config).await?;
+    recreate_index_html(build_mode, config, base_path).await?;
     task::spawn_blocking(copy_pkg_public_sync).await??;
     task::spawn_blocking(copy_web_workers_sync).await??;
     if let Some(hosting) = hosting {
-        create_hosting_files(hosting).await?;
+        create_hosting_files(hosting, config).await?;
     }
 
     println!("frontend_dist created");
@@ -47,11 +61,16 @@ async fn recreate_api_dir_with_frontend_dist() {
 }
 
 #[throws]
-async fn recreate_index_html(build_mode: BuildMode, config: &Config) {
+async fn recreate_index_html(build_mode: BuildMode, config: &Config, base_path: Option<&str>) {
     let server = run_backend(build_mode)?;
     let html = download(localhost_url(config)).await?;
     drop(server);
 
+    let mut html = String::from_utf8(html).context("index.html is not a valid UTF-8 file")?;
+    if let Some(base_path) = base_path {
+        html = prefix_api_urls(&html, base_path);
+    }
+
     fs::write(concatcp!(FRONTEND_DIST_DIR, "/index.html"), html).await?;
 }
 
@@ -83,19 +102,49 @@ fn copy_web_workers_sync() {
     }
 }
 
-#[throws]
-async fn create_hosting_files(hosting: Hosting) {
-    match hosting {
-        Hosting::Netlify => {
-            const NETLIFY_ROUTER: &str = concatcp!(FRONTEND_DIST_DIR, "/netlify.toml");
-            if fs::metadata(NETLIFY_ROUTER).await.is_err() {
-                fs::write(
-                    NETLIFY_ROUTER,
-                    include_str!("../hosting_configs/netlify.toml"),
-                )
-                .await?;
-                println!("netlify.toml added to frontend_dist");
-            }
-        }
+// E.g. `my_repo/` => `/my_repo`, empty for the root.
+fn normalize_base_path(base_path: &str) -> String {
+    let base_path = base_path.trim_matches('/');
+    if base_path.is_empty() {
+        return String::new();
+    }
+    format!("/{base_path}")
+}
+
+// E.g. `/_api/pkg/frontend.js` => `/my_repo/_api/pkg/frontend.js`
+// for sites served from a subpath like GitHub Pages project sites.
+fn prefix_api_urls(html: &str, base_path: &str) -> String {
+    let base_path = normalize_base_path(base_path);
+    if base_path.is_empty() {
+        return html.to_owned();
+    }
+    html.replace("'/_api/", &format!("'{base_path}/_api/"))
+        .replace("\"/_api/", &format!("\"{base_path}/_api/"))
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_base_path() {
+        assert_eq!(normalize_base_path("my_repo"), "/my_repo");
+        assert_eq!(normalize_base_path("/my_repo/"), "/my_repo");
+        assert_eq!(normalize_base_path("/"), "");
+        assert_eq!(normalize_base_path(""), "");
+    }
+
+    #[test]
+    fn test_prefix_api_urls() {
+        let html = r#"<script type="module">import init from '/_api/pkg/frontend.js';</script>
+<link rel="preload" href="/_api/pkg/frontend_bg.wasm">
+<a href="/about">About</a>"#;
+        let prefixed = prefix_api_urls(html, "my_repo/");
+        assert!(prefixed.contains("from '/my_repo/_api/pkg/frontend.js'"));
+        assert!(prefixed.contains(r#"href="/my_repo/_api/pkg/frontend_bg.wasm""#));
+        assert!(prefixed.contains(r#"href="/about""#));
+        assert_eq!(prefix_api_urls(html, "/"), html);
     }
 }
@@ -0,0 +1,353 @@
+use super::FRONTEND_DIST_DIR;
+use crate::config::Config;
+use crate::Hosting;
+use anyhow::{Context, Error};
+use fehler::throws;
+use std::path::Path;
+use tokio::fs;
+
+const INDEX_HTML_PATH: &str = "/index.html";
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+const NO_CACHE_CACHE_CONTROL: &str = "no-cache";
+
+// -- public --
+
+#[throws]
+pub async fn create_hosting_files(hosting: Hosting, config: &Config) {
+    let headers = Headers::new(config);
+    match hosting {
+        Hosting::Netlify => {
+            write_if_not_present("netlify.toml", netlify_toml(&headers)).await?;
+        }
+        Hosting::CloudflarePages => {
+            write_if_not_present("_redirects", cloudflare_redirects()).await?;
+            write_if_not_present("_headers", cloudflare_headers(&headers)).await?;
+        }
+        Hosting::Vercel => {
+            write_if_not_present("vercel.json", vercel_json(&headers)).await?;
+        }
+        Hosting::GithubPages => {
+            create_github_pages_files(&headers).await?;
+        }
+        Hosting::Nginx => {
+            write_if_not_present("nginx.conf", nginx_conf(&headers)).await?;
+        }
+        Hosting::Caddy => {
+            write_if_not_present("Caddyfile", caddyfile(&headers)).await?;
+        }
+    }
+}
+
+// -- private --
+
+// ------ Headers ------
+
+struct Headers {
+    pkg_cache_control: &'static str,
+    cross_origin_isolation: bool,
+}
+
+impl Headers {
+    fn new(config: &Config) -> Self {
+        Self {
+            // Pkg files are renamed with the build id on every build when `cache_busting` is enabled.
+            pkg_cache_control: if config.cache_busting {
+                IMMUTABLE_CACHE_CONTROL
+            } else {
+                NO_CACHE_CACHE_CONTROL
+            },
+            // https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/SharedArrayBuffer#security_requirements
+            cross_origin_isolation: config.frontend_multithreading == Some(true),
+        }
+    }
+
+    fn cross_origin_isolation_headers(&self) -> &'static [(&'static str, &'static str)] {
+        if self.cross_origin_isolation {
+            &[
+                ("Cross-Origin-Opener-Policy", "same-origin"),
+                ("Cross-Origin-Embedder-Policy", "require-corp"),
+            ]
+        } else {
+            &[]
+        }
+    }
+}
+
+#[throws]
+async fn write_if_not_present(file_name: &str, content: String) {
+    let path = Path::new(FRONTEND_DIST_DIR).join(file_name);
+    if fs::metadata(&path).await.is_ok() {
+        println!("{file_name} already exists in frontend_dist, skipping");
+        return;
+    }
+    fs::write(&path, content)
+        .await
+        .with_context(|| format!("Failed to write {path:?}"))?;
+    println!("{file_name} added to frontend_dist");
+}
+
+// ------ Netlify ------
+
+// https://docs.netlify.com/routing/headers/
+fn netlify_toml(headers: &Headers) -> String {
+    let mut toml = format!(
+        r#"[[redirects]]
+  from = "/*"
+  to = "{INDEX_HTML_PATH}"
+  status = 200
+
+[[headers]]
+  for = "/_api/pkg/*"
+  [headers.values]
+    Cache-Control = "{pkg_cache_control}"
+
+[[headers]]
+  for = "/_api/web_workers/*"
+  [headers.values]
+    Cache-Control = "{pkg_cache_control}"
+"#,
+        pkg_cache_control = headers.pkg_cache_control,
+    );
+    let cross_origin_isolation_headers = headers.cross_origin_isolation_headers();
+    if !cross_origin_isolation_headers.is_empty() {
+        toml.push_str("\n[[headers]]\n  for = \"/*\"\n  [headers.values]\n");
+        for (name, value) in cross_origin_isolation_headers {
+            toml.push_str(&format!("    {name} = \"{value}\"\n"));
+        }
+    }
+    toml
+}
+
+// ------ Cloudflare Pages ------
+
+// https://developers.cloudflare.com/pages/configuration/redirects/
+fn cloudflare_redirects() -> String {
+    format!("/* {INDEX_HTML_PATH} 200\n")
+}
+
+// https://developers.cloudflare.com/pages/configuration/headers/
+fn cloudflare_headers(headers: &Headers) -> String {
+    let mut content = String::new();
+    for path in ["/_api/pkg/*", "/_api/web_workers/*"] {
+        content.push_str(&format!(
+            "{path}\n  Cache-Control: {}\n",
+            headers.pkg_cache_control
+        ));
+    }
+    let cross_origin_isolation_headers = headers.cross_origin_isolation_headers();
+    if !cross_origin_isolation_headers.is_empty() {
+        content.push_str("/*\n");
+        for (name, value) in cross_origin_isolation_headers {
+            content.push_str(&format!("  {name}: {value}\n"));
+        }
+    }
+    content
+}
+
+// ------ Vercel ------
+
+// https://vercel.com/docs/projects/project-configuration
+fn vercel_json(headers: &Headers) -> String {
+    let header_json =
+        |name: &str, value: &str| format!(r#"{{ "key": "{name}", "value": "{value}" }}"#);
+
+    let pkg_cache_control = header_json("Cache-Control", headers.pkg_cache_control);
+    let mut header_rules = vec![
+        format!(r#"{{ "source": "/_api/pkg/(.*)", "headers": [{pkg_cache_control}] }}"#),
+        format!(r#"{{ "source": "/_api/web_workers/(.*)", "headers": [{pkg_cache_control}] }}"#),
+    ];
+    let cross_origin_isolation_headers = headers.cross_origin_isolation_headers();
+    if !cross_origin_isolation_headers.is_empty() {
+        let headers = cross_origin_isolation_headers
+            .iter()
+            .map(|(name, value)| header_json(name, value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        header_rules.push(format!(
+            r#"{{ "source": "/(.*)", "headers": [{headers}] }}"#
+        ));
+    }
+    let header_rules = header_rules.join(",\n    ");
+
+    format!(
+        r#"{{
+  "rewrites": [
+    {{ "source": "/(.*)", "destination": "{INDEX_HTML_PATH}" }}
+  ],
+  "headers": [
+    {header_rules}
+  ]
+}}
+"#
+    )
+}
+
+// ------ GitHub Pages ------
+
+// https://docs.github.com/en/pages/getting-started-with-github-pages/creating-a-custom-404-page-for-your-github-pages-site
+#[throws]
+async fn create_github_pages_files(headers: &Headers) {
+    // Jekyll would ignore the `_api` folder because of the underscore prefix.
+    write_if_not_present(".nojekyll", String::new()).await?;
+
+    // GitHub Pages serves `404.html` for unknown paths so it works as a SPA fallback.
+    let index_html = fs::read_to_string(format!("{FRONTEND_DIST_DIR}{INDEX_HTML_PATH}"))
+        .await
+        .context("Failed to read frontend_dist/index.html")?;
+    write_if_not_present("404.html", index_html).await?;
+
+    if !headers.cross_origin_isolation_headers().is_empty() {
+        eprintln!(
+            "GitHub Pages doesn't allow to set COOP/COEP headers required by `frontend_multithreading`"
+        );
+    }
+}
+
+// ------ Nginx ------
+
+// https://nginx.org/en/docs/http/ngx_http_headers_module.html
+fn nginx_conf(headers: &Headers) -> String {
+    // NOTE: `add_header` directives are inherited only when the current level doesn't define any,
+    // so the cross-origin isolation headers have to be repeated in every `location`.
+    let cross_origin_isolation_headers = headers
+        .cross_origin_isolation_headers()
+        .iter()
+        .map(|(name, value)| format!("\n        add_header {name} \"{value}\" always;"))
+        .collect::<String>();
+
+    format!(
+        r#"server {{
+    listen 80;
+    # Replace with the absolute path to the `frontend_dist` folder.
+    root /usr/share/nginx/html;
+    index index.html;
+
+    types {{
+        application/wasm wasm;
+    }}
+
+    location ~ ^/_api/(pkg|web_workers)/ {{
+        add_header Cache-Control "{pkg_cache_control}" always;{cross_origin_isolation_headers}
+        try_files $uri =404;
+    }}
+
+    location / {{
+        add_header Cache-Control "{NO_CACHE_CACHE_CONTROL}" always;{cross_origin_isolation_headers}
+        try_files $uri $uri/ {INDEX_HTML_PATH};
+    }}
+}}
+"#,
+        pkg_cache_control = headers.pkg_cache_control,
+    )
+}
+
+// ------ Caddy ------
+
+// https://caddyserver.com/docs/caddyfile/directives/header
+fn caddyfile(headers: &Headers) -> String {
+    let cross_origin_isolation_headers = headers
+        .cross_origin_isolation_headers()
+        .iter()
+        .map(|(name, value)| format!("\n    header {name} \"{value}\""))
+        .collect::<String>();
+
+    format!(
+        r#":80 {{
+    # Replace with the absolute path to the `frontend_dist` folder.
+    root * .
+    encode zstd gzip{cross_origin_isolation_headers}
+    header /_api/pkg/* Cache-Control "{pkg_cache_control}"
+    header /_api/web_workers/* Cache-Control "{pkg_cache_control}"
+
+    try_files {{path}} {INDEX_HTML_PATH}
+    file_server
+}}
+"#,
+        pkg_cache_control = headers.pkg_cache_control,
+    )
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(cross_origin_isolation: bool) -> Headers {
+        Headers {
+            pkg_cache_control: IMMUTABLE_CACHE_CONTROL,
+            cross_origin_isolation,
+        }
+    }
+
+    #[test]
+    fn test_netlify_toml() {
+        let toml = netlify_toml(&headers(true));
+        let toml = toml::from_str::<toml::Table>(&toml).unwrap();
+        assert_eq!(toml["redirects"][0]["to"].as_str(), Some(INDEX_HTML_PATH));
+        assert_eq!(toml["headers"][0]["for"].as_str(), Some("/_api/pkg/*"));
+        assert_eq!(
+            toml["headers"][0]["values"]["Cache-Control"].as_str(),
+            Some(IMMUTABLE_CACHE_CONTROL)
+        );
+        assert_eq!(
+            toml["headers"][2]["values"]["Cross-Origin-Embedder-Policy"].as_str(),
+            Some("require-corp")
+        );
+        assert!(!netlify_toml(&headers(false)).contains("Cross-Origin"));
+    }
+
+    #[test]
+    fn test_cloudflare_files() {
+        assert_eq!(cloudflare_redirects(), "/* /index.html 200\n");
+        let content = cloudflare_headers(&headers(true));
+        assert!(content.starts_with(&format!(
+            "/_api/pkg/*\n  Cache-Control: {IMMUTABLE_CACHE_CONTROL}\n"
+        )));
+        assert!(content.ends_with(
+            "/*\n  Cross-Origin-Opener-Policy: same-origin\n  Cross-Origin-Embedder-Policy: require-corp\n"
+        ));
+    }
+
+    #[test]
+    fn test_vercel_json() {
+        let json = vercel_json(&headers(true));
+        let json = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        assert_eq!(json["rewrites"][0]["destination"], INDEX_HTML_PATH);
+        assert_eq!(json["headers"][1]["source"], "/_api/web_workers/(.*)");
+        assert_eq!(
+            json["headers"][1]["headers"][0]["value"],
+            IMMUTABLE_CACHE_CONTROL
+        );
+        assert_eq!(json["headers"][2]["headers"][0]["value"], "same-origin");
+
+        let json = vercel_json(&headers(false));
+        let json = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        assert_eq!(json["headers"].as_array().map(Vec::len), Some(2));
+    }
+
+    #[test]
+    fn test_nginx_conf() {
+        let conf = nginx_conf(&headers(true));
+        // Repeated in every `location`, see `nginx_conf`.
+        assert_eq!(conf.matches("Cross-Origin-Opener-Policy").count(), 2);
+        assert!(conf.contains(&format!(
+            "add_header Cache-Control \"{IMMUTABLE_CACHE_CONTROL}\" always;"
+        )));
+        assert!(conf.contains("try_files $uri $uri/ /index.html;"));
+        assert_eq!(conf.matches('{').count(), conf.matches('}').count());
+    }
+
+    #[test]
+    fn test_caddyfile() {
+        let caddyfile = caddyfile(&headers(false));
+        assert!(caddyfile.contains(&format!(
+            "header /_api/web_workers/* Cache-Control \"{IMMUTABLE_CACHE_CONTROL}\""
+        )));
+        assert!(!caddyfile.contains("Cross-Origin"));
+        assert_eq!(
+            caddyfile.matches('{').count(),
+            caddyfile.matches('}').count()
+        );
+    }
+}
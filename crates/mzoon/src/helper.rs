@@ -1,3 +1,4 @@
+mod backend_binary_path;
 mod download;
 mod file_compressor;
 mod localhost_url;
@@ -7,8 +8,11 @@ mod try_into_string;
 mod visit_files;
 pub mod workspace_member;
 
-pub use download::download;
-pub use file_compressor::{BrotliFileCompressor, FileCompressor, GzipFileCompressor};
+pub use backend_binary_path::backend_binary_path;
+pub use download::{download, download_verified};
+pub use file_compressor::{
+    create_compressed_files, BrotliFileCompressor, FileCompressor, GzipFileCompressor,
+};
 pub use localhost_url::localhost_url;
 pub use read_to_vec::{AsyncReadToVec, ReadToVec};
 pub use try_into_string::TryIntoString;
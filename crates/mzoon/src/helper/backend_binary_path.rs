@@ -0,0 +1,17 @@
+use crate::BuildMode;
+use anyhow::Error;
+use apply::Also;
+use cargo_metadata::MetadataCommand;
+use fehler::throws;
+use std::{env::consts::EXE_SUFFIX, path::PathBuf};
+
+#[throws]
+pub fn backend_binary_path(build_mode: BuildMode) -> PathBuf {
+    MetadataCommand::new()
+        .no_deps()
+        .exec()?
+        .target_directory
+        .also(|directory| directory.push(build_mode.target_profile_folder()))
+        .also(|directory| directory.push(format!("backend{EXE_SUFFIX}")))
+        .into()
+}
@@ -1,5 +1,7 @@
-use anyhow::Error;
+use anyhow::{anyhow, Context, Error};
 use fehler::throws;
+use ring::digest::{digest, SHA256};
+use std::fmt::Write;
 
 #[throws]
 pub async fn download(url: impl AsRef<str>) -> Vec<u8> {
@@ -7,6 +9,51 @@ pub async fn download(url: impl AsRef<str>) -> Vec<u8> {
     again::retry(|| get(url)).await?
 }
 
+/// Downloads the file and verifies its SHA-256 checksum.
+/// The checksum file from `checksum_url` is used when `expected_sha256` is `None`.
+#[throws]
+pub async fn download_verified(
+    url: impl AsRef<str>,
+    checksum_url: impl AsRef<str>,
+    expected_sha256: Option<&str>,
+) -> Vec<u8> {
+    let url = url.as_ref();
+    let expected_sha256 = match expected_sha256 {
+        Some(expected_sha256) => expected_sha256.to_owned(),
+        None => {
+            let checksum_url = checksum_url.as_ref();
+            let checksum_file = download(checksum_url)
+                .await
+                .with_context(|| format!("Failed to download the checksum '{checksum_url}'"))?;
+            // Format: `<hex checksum>  <file name>`
+            String::from_utf8_lossy(&checksum_file)
+                .split_whitespace()
+                .next()
+                .ok_or_else(|| anyhow!("Empty checksum file '{checksum_url}'"))?
+                .to_owned()
+        }
+    };
+
+    let content = download(url).await?;
+    let actual_sha256 = sha256_hex(&content);
+    if !actual_sha256.eq_ignore_ascii_case(expected_sha256.trim()) {
+        Err(anyhow!(
+            "Checksum mismatch for '{url}': expected {expected_sha256}, found {actual_sha256}"
+        ))?;
+    }
+    content
+}
+
+fn sha256_hex(content: &[u8]) -> String {
+    digest(&SHA256, content)
+        .as_ref()
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
 #[throws]
 async fn get(url: &str) -> Vec<u8> {
     reqwest::get(url)
@@ -1,4 +1,4 @@
-use crate::helper::ReadToVec;
+use crate::helper::{AsyncReadToVec, ReadToVec};
 use anyhow::{Context, Result};
 use apply::Also;
 use async_trait::async_trait;
@@ -8,7 +8,20 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
-use tokio::{fs, io::AsyncWriteExt, task::spawn_blocking};
+use tokio::{fs, io::AsyncWriteExt, task::spawn_blocking, try_join};
+
+/// Creates `<file>.br` and `<file>.gz` next to the file.
+pub async fn create_compressed_files(file_path: impl AsRef<Path>) -> Result<()> {
+    let file_path = file_path.as_ref();
+    let content = Arc::new(fs::File::open(&file_path).await?.read_to_vec().await?);
+
+    try_join!(
+        BrotliFileCompressor::compress_file(Arc::clone(&content), file_path, "br"),
+        GzipFileCompressor::compress_file(content, file_path, "gz"),
+    )
+    .with_context(|| format!("Failed to create compressed files for {file_path:?}"))?;
+    Ok(())
+}
 
 #[async_trait]
 pub trait FileCompressor {
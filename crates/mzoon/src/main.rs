@@ -1,16 +1,23 @@
 use anyhow::Error;
 use clap::Parser;
 use fehler::throws;
+use size_report::SizeReportArgs;
 use std::path::PathBuf;
 
 mod build_backend;
 mod build_frontend;
+mod bundle;
 mod command;
 mod config;
+mod embedded_assets;
 mod frontend_dist;
 mod helper;
+mod public_assets;
 mod run_backend;
 mod set_env_vars;
+mod size_report;
+mod symbol_map;
+mod tool;
 mod wasm_bindgen;
 mod wasm_opt;
 mod watcher;
@@ -33,6 +40,9 @@ enum Args {
         profiling: bool,
         #[clap(short, long)]
         open: bool,
+        /// Profile from MoonZoon.toml - e.g. `production` for `[profile.production]`
+        #[clap(long)]
+        profile: Option<String>,
     },
     Build {
         #[clap(short, long)]
@@ -44,12 +54,43 @@ enum Args {
         frontend_dist: bool,
         #[clap(value_enum)]
         hosting: Option<Hosting>,
+        /// Subpath the frontend_dist will be served from - e.g. my_repo for GitHub Pages project sites
+        #[clap(long, requires = "frontend_dist")]
+        base_path: Option<String>,
+        /// Create a self-contained folder and tarball with the backend binary, assets and env vars
+        #[clap(short, long, conflicts_with = "frontend_dist")]
+        bundle: bool,
+        /// Add a generated Dockerfile to the bundle
+        #[clap(long, requires = "bundle")]
+        dockerfile: bool,
+        /// Embed frontend files and public assets into the backend binary
+        #[clap(short, long, conflicts_with = "frontend_dist")]
+        embed_assets: bool,
+        /// Profile from MoonZoon.toml - e.g. `production` for `[profile.production]`
+        #[clap(long)]
+        profile: Option<String>,
+        /// Write frontend and Web Worker sizes to size_report.json
+        #[clap(long)]
+        size_report: bool,
+        /// Previous size_report.json to compare with; fails on size regressions
+        #[clap(long, requires = "size_report")]
+        size_baseline: Option<PathBuf>,
+        /// Allowed brotli size increase in percent when comparing with --size-baseline
+        #[clap(long, requires = "size_baseline", default_value_t = 0.)]
+        size_tolerance: f64,
     },
+    /// Validate MoonZoon.toml without building
+    Check,
 }
 
 #[derive(clap::ValueEnum, Clone, Copy, Debug)]
 pub enum Hosting {
     Netlify,
+    CloudflarePages,
+    Vercel,
+    GithubPages,
+    Nginx,
+    Caddy,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -113,12 +154,38 @@ async fn main() {
             release,
             profiling,
             open,
-        } => command::start(BuildMode::new(release, profiling), open).await?,
+            profile,
+        } => command::start(BuildMode::new(release, profiling), open, profile).await?,
         Args::Build {
             release,
             profiling,
             frontend_dist,
             hosting,
-        } => command::build(BuildMode::new(release, profiling), frontend_dist, hosting).await?,
+            base_path,
+            bundle,
+            dockerfile,
+            embed_assets,
+            profile,
+            size_report,
+            size_baseline,
+            size_tolerance,
+        } => {
+            command::build(command::BuildOptions {
+                build_mode: BuildMode::new(release, profiling),
+                frontend_dist,
+                hosting,
+                base_path,
+                bundle,
+                dockerfile,
+                embed_assets,
+                profile,
+                size_report_args: size_report.then_some(SizeReportArgs {
+                    baseline: size_baseline,
+                    tolerance: size_tolerance,
+                }),
+            })
+            .await?
+        }
+        Args::Check => command::check().await?,
     }
 }
@@ -0,0 +1,108 @@
+use crate::helper::{create_compressed_files, visit_files};
+use anyhow::{Context, Error};
+use fehler::throws;
+use futures::TryStreamExt;
+use ring::digest::{digest, SHA256};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Component, Path, PathBuf},
+};
+use tokio::fs;
+
+const PUBLIC_DIR: &str = "public";
+const OUTPUT_DIR: &str = "frontend/pkg/public";
+// NOTE: Sync with Moon's `Frontend::public_assets_script`.
+const MANIFEST_PATH: &str = "frontend/pkg/public_manifest.json";
+const HASH_LENGTH: usize = 16;
+
+// -- public --
+
+/// Copies files from `public` to `frontend/pkg/public` with content hashes in their names
+/// and writes a manifest with logical names (e.g. `images/logo.png`)
+/// mapped to hashed paths (e.g. `public/images/logo.0123456789abcdef.png`) relative to `_api/pkg`.
+#[throws]
+pub async fn process_public_assets(compress: bool) {
+    if fs::metadata(PUBLIC_DIR).await.is_err() {
+        return;
+    }
+    println!("Processing public assets...");
+
+    let files = visit_files(PUBLIC_DIR).try_collect::<Vec<_>>().await?;
+    let mut manifest = BTreeMap::new();
+
+    for file in files {
+        let path = file.path();
+        let relative_path = path.strip_prefix(PUBLIC_DIR)?;
+        let Some(logical_name) = logical_name(relative_path) else {
+            continue;
+        };
+
+        let content = fs::read(&path)
+            .await
+            .with_context(|| format!("Failed to read the public asset {path:?}"))?;
+        let hashed_path = hashed_path(relative_path, &content_hash(&content));
+
+        let output_path = Path::new(OUTPUT_DIR).join(&hashed_path);
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(&output_path, &content)
+            .await
+            .with_context(|| format!("Failed to write the public asset {output_path:?}"))?;
+        if compress {
+            create_compressed_files(&output_path).await?;
+        }
+
+        let hashed_name = logical_name_of(&hashed_path);
+        manifest.insert(logical_name, format!("public/{hashed_name}"));
+    }
+
+    fs::write(MANIFEST_PATH, serde_json::to_string_pretty(&manifest)?)
+        .await
+        .context("Failed to write the public assets manifest")?;
+    println!("Public assets processed");
+}
+
+// -- private --
+
+/// `images/logo.png` with `/` separators on all platforms, `None` for hidden files like `.gitkeep`.
+fn logical_name(relative_path: &Path) -> Option<String> {
+    let hidden = relative_path.components().any(|component| {
+        matches!(component, Component::Normal(name) if name.to_string_lossy().starts_with('.'))
+    });
+    if hidden {
+        return None;
+    }
+    Some(logical_name_of(relative_path))
+}
+
+fn logical_name_of(relative_path: &Path) -> String {
+    relative_path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn content_hash(content: &[u8]) -> String {
+    let mut hash = String::new();
+    for byte in digest(&SHA256, content).as_ref() {
+        let _ = write!(hash, "{byte:02x}");
+    }
+    hash.truncate(HASH_LENGTH);
+    hash
+}
+
+/// `images/logo.png` => `images/logo.<hash>.png`
+fn hashed_path(relative_path: &Path, hash: &str) -> PathBuf {
+    let stem = relative_path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy();
+    let file_name = match relative_path.extension() {
+        Some(extension) => format!("{stem}.{hash}.{}", extension.to_string_lossy()),
+        None => format!("{stem}.{hash}"),
+    };
+    relative_path.with_file_name(file_name)
+}
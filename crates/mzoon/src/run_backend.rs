@@ -1,19 +1,14 @@
+use crate::helper::backend_binary_path;
 use crate::BuildMode;
 use anyhow::{Context, Error};
-use apply::{Also, Apply};
-use cargo_metadata::MetadataCommand;
+use apply::Apply;
 use fehler::throws;
 use tokio::process::{Child, Command};
 
 #[throws]
 pub fn run_backend(build_mode: BuildMode) -> Child {
     println!("Run backend");
-    MetadataCommand::new()
-        .no_deps()
-        .exec()?
-        .target_directory
-        .also(|directory| directory.push(build_mode.target_profile_folder()))
-        .also(|directory| directory.push("backend"))
+    backend_binary_path(build_mode)?
         .apply(Command::new)
         .kill_on_drop(true)
         .spawn()
@@ -2,46 +2,88 @@ use crate::{config::Config, BuildMode};
 use std::env;
 
 pub fn set_env_vars(config: &Config, build_mode: BuildMode, frontend_dist: bool) {
+    for (key, value) in env_vars(config, build_mode, frontend_dist) {
+        env::set_var(key, value);
+    }
+}
+
+/// Env vars read by Moon's `Config` during the app start.
+pub fn env_vars(
+    config: &Config,
+    build_mode: BuildMode,
+    frontend_dist: bool,
+) -> Vec<(String, String)> {
+    let mut env_vars = Vec::new();
+    let mut add = |key: &str, value: String| env_vars.push((key.to_owned(), value));
+
     // port = 8443
-    env::set_var("PORT", config.port.to_string());
+    add("PORT", config.port.to_string());
     // https = true
-    env::set_var("HTTPS", config.https.to_string());
+    add("HTTPS", config.https.to_string());
     // cache_busting = true
-    env::set_var("CACHE_BUSTING", config.cache_busting.to_string());
+    add("CACHE_BUSTING", config.cache_busting.to_string());
     // backend_log_level = "warn"
-    env::set_var("BACKEND_LOG_LEVEL", config.backend_log_level.as_str());
+    add(
+        "BACKEND_LOG_LEVEL",
+        config.backend_log_level.as_str().to_owned(),
+    );
     // frontend_multithreading = true
-    env::set_var(
+    add(
         "FRONTEND_MULTITHREADING",
         (config.frontend_multithreading == Some(true)).to_string(),
     );
 
     // [redirect]
     // port = 8080
-    env::set_var("REDIRECT_PORT", config.redirect.port.to_string());
+    add("REDIRECT_PORT", config.redirect.port.to_string());
     // enabled = true
-    env::set_var("REDIRECT_ENABLED", config.redirect.enabled.to_string());
+    add("REDIRECT_ENABLED", config.redirect.enabled.to_string());
 
     // [cors]
     // origins = ["*", "https://example.com"]
-    env::set_var("CORS_ORIGINS", config.cors.origins.join(","));
+    add("CORS_ORIGINS", config.cors.origins.join(","));
+
+    // [otlp]
+    // endpoint = "http://localhost:4318"
+    if let Some(endpoint) = &config.otlp.endpoint {
+        add("OTLP_ENDPOINT", endpoint.clone());
+    }
+    // service_name = "my_app"
+    if let Some(service_name) = &config.otlp.service_name {
+        add("OTLP_SERVICE_NAME", service_name.clone());
+    }
 
-    env::set_var(
+    // [backplane]
+    // address = "tcp://127.0.0.1:9100"
+    if let Some(address) = &config.backplane.address {
+        add("BACKPLANE_ADDRESS", address.clone());
+    }
+    // instance_id = "moon_1"
+    if let Some(instance_id) = &config.backplane.instance_id {
+        add("BACKPLANE_INSTANCE_ID", instance_id.clone());
+    }
+
+    add(
         "COMPRESSED_PKG",
         (build_mode.is_not_dev() && !frontend_dist).to_string(),
     );
 
     // frontend_dist = false
-    env::set_var("FRONTEND_DIST", frontend_dist.to_string());
+    add("FRONTEND_DIST", frontend_dist.to_string());
 
     // frontend_auto_reload = false
-    env::set_var(
+    add(
         "FRONTEND_AUTO_RELOAD",
         (build_mode.is_not_release() && !frontend_dist).to_string(),
     );
 
-    // custom configs from MoonZoonCustom.toml
-    for (key, value) in &config.custom_env_vars {
-        env::set_var(key, value);
+    // mzoon --profile production
+    if let Some(profile) = &config.profile {
+        add("PROFILE", profile.clone());
     }
+
+    // custom configs from MoonZoonCustom.toml
+    env_vars.extend(config.custom_env_vars.iter().cloned());
+
+    env_vars
 }
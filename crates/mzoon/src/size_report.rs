@@ -0,0 +1,438 @@
+use crate::helper::{visit_files, BrotliFileCompressor, FileCompressor, GzipFileCompressor};
+use crate::BuildMode;
+use anyhow::{bail, Context, Error};
+use fehler::throws;
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+use tokio::{fs, task::spawn_blocking};
+use wasmparser::{Name, NameSectionReader, Parser, Payload, TypeRef};
+
+pub const SIZE_REPORT_PATH: &str = "size_report.json";
+const TOP_FUNCTIONS: usize = 30;
+const UNKNOWN_NAME: &str = "[unknown]";
+
+// ------ SizeReportArgs ------
+
+pub struct SizeReportArgs {
+    pub baseline: Option<PathBuf>,
+    /// Allowed brotli size increase in percent.
+    pub tolerance: f64,
+}
+
+// ------ SizeReport ------
+
+/// File sizes of the frontend and Web Worker pkgs
+/// with wasm code size attributed to crates and functions.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SizeReport {
+    pub build_mode: String,
+    pub files: Vec<FileSizes>,
+    /// Empty when the wasm file doesn't contain the name section (use `--profiling`).
+    pub crates: Vec<CodeSize>,
+    pub functions: Vec<CodeSize>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct FileSizes {
+    pub crate_name: String,
+    /// Path relative to the crate's `pkg` folder, e.g. `frontend_bg.wasm`.
+    pub file: String,
+    /// Size before `wasm-opt`.
+    pub raw: u64,
+    pub optimized: u64,
+    pub brotli: u64,
+    pub gzip: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CodeSize {
+    /// The crate compiled to the analyzed wasm file, e.g. `frontend`.
+    pub pkg: String,
+    /// A crate or a demangled function name.
+    pub name: String,
+    pub size: u64,
+}
+
+impl SizeReport {
+    pub fn new(build_mode: BuildMode) -> Self {
+        Self {
+            build_mode: build_mode.target_profile_folder().to_owned(),
+            ..Self::default()
+        }
+    }
+
+    /// Call after `wasm-bindgen`.
+    #[throws]
+    pub async fn record_raw_sizes(&mut self, crate_name: &str, crate_path: &Path) {
+        for (file, path) in pkg_files(crate_name, crate_path).await? {
+            let raw = fs::metadata(&path).await?.len();
+            self.files.push(FileSizes {
+                crate_name: crate_name.to_owned(),
+                file,
+                raw,
+                ..FileSizes::default()
+            });
+        }
+    }
+
+    /// Call after `wasm-opt` and before the pkg files are renamed.
+    #[throws]
+    pub async fn record_optimized_sizes(&mut self, crate_name: &str, crate_path: &Path) {
+        for (file, path) in pkg_files(crate_name, crate_path).await? {
+            let content = fs::read(&path)
+                .await
+                .with_context(|| format!("Failed to read {path:?} for the size report"))?;
+            let is_wasm = file.ends_with(".wasm");
+            let optimized = content.len() as u64;
+
+            let (brotli, gzip, code_sizes) = spawn_blocking(move || -> anyhow::Result<_> {
+                let brotli = BrotliFileCompressor::compress(&content)?.len() as u64;
+                let gzip = GzipFileCompressor::compress(&content)?.len() as u64;
+                let code_sizes = if is_wasm {
+                    function_sizes(&content)?
+                } else {
+                    Vec::new()
+                };
+                Ok((brotli, gzip, code_sizes))
+            })
+            .await??;
+
+            let sizes = match self
+                .files
+                .iter_mut()
+                .find(|sizes| sizes.crate_name == crate_name && sizes.file == file)
+            {
+                Some(sizes) => sizes,
+                None => {
+                    self.files.push(FileSizes {
+                        crate_name: crate_name.to_owned(),
+                        file,
+                        ..FileSizes::default()
+                    });
+                    self.files.last_mut().unwrap()
+                }
+            };
+            sizes.optimized = optimized;
+            sizes.brotli = brotli;
+            sizes.gzip = gzip;
+
+            self.add_code_sizes(crate_name, code_sizes);
+        }
+    }
+
+    #[throws]
+    pub async fn load(path: &Path) -> Self {
+        let report = fs::read(path)
+            .await
+            .with_context(|| format!("Failed to read the size report {path:?}"))?;
+        serde_json::from_slice(&report)
+            .with_context(|| format!("Failed to parse the size report {path:?}"))?
+    }
+
+    #[throws]
+    pub async fn write(&self, path: &Path) {
+        fs::write(path, serde_json::to_vec_pretty(self)?)
+            .await
+            .with_context(|| format!("Failed to write the size report {path:?}"))?;
+    }
+
+    pub fn print(&self) {
+        println!("Size report ({}):", self.build_mode);
+        println!(
+            "  {:<40} {:>10} {:>10} {:>10} {:>10}",
+            "file", "raw", "optimized", "brotli", "gzip"
+        );
+        for sizes in &self.files {
+            println!(
+                "  {:<40} {:>10} {:>10} {:>10} {:>10}",
+                format!("{}/{}", sizes.crate_name, sizes.file),
+                sizes.raw,
+                sizes.optimized,
+                sizes.brotli,
+                sizes.gzip
+            );
+        }
+        if self.crates.is_empty() {
+            println!("  [wasm code isn't attributed to crates - the name section is missing, try `--profiling`]");
+            return;
+        }
+        println!("  Largest crates:");
+        for code_size in self.crates.iter().take(10) {
+            println!(
+                "  {:>10}  {} ({})",
+                code_size.size, code_size.name, code_size.pkg
+            );
+        }
+        println!("  Largest functions:");
+        for code_size in self.functions.iter().take(10) {
+            println!(
+                "  {:>10}  {} ({})",
+                code_size.size, code_size.name, code_size.pkg
+            );
+        }
+    }
+
+    /// Fails when a brotli-compressed file is larger than in the baseline by more than `tolerance` percent.
+    #[throws]
+    pub fn check_against(&self, baseline: &SizeReport, tolerance: f64) {
+        let mut regressions = Vec::new();
+        for sizes in &self.files {
+            let file = format!("{}/{}", sizes.crate_name, sizes.file);
+            let Some(baseline_sizes) = baseline.files.iter().find(|baseline| {
+                baseline.crate_name == sizes.crate_name && baseline.file == sizes.file
+            }) else {
+                println!("  {file}: new file, {} B brotli", sizes.brotli);
+                continue;
+            };
+            let diff = sizes.brotli as i64 - baseline_sizes.brotli as i64;
+            let diff_percent = if baseline_sizes.brotli == 0 {
+                0.
+            } else {
+                diff as f64 / baseline_sizes.brotli as f64 * 100.
+            };
+            println!("  {file}: {diff:+} B brotli ({diff_percent:+.2} %)");
+            if diff > 0 && diff_percent > tolerance {
+                regressions.push(file);
+            }
+        }
+        if !regressions.is_empty() {
+            bail!(
+                "Size regression over {tolerance} % in: {}",
+                regressions.join(", ")
+            );
+        }
+    }
+
+    fn add_code_sizes(&mut self, pkg: &str, code_sizes: Vec<(String, u64)>) {
+        let mut crates = BTreeMap::<String, u64>::new();
+        for (name, size) in code_sizes {
+            *crates.entry(owner_crate(&name).to_owned()).or_default() += size;
+            self.functions.push(CodeSize {
+                pkg: pkg.to_owned(),
+                name,
+                size,
+            });
+        }
+        self.crates
+            .extend(crates.into_iter().map(|(name, size)| CodeSize {
+                pkg: pkg.to_owned(),
+                name,
+                size,
+            }));
+
+        self.crates.sort_by(|a, b| b.size.cmp(&a.size));
+        self.functions.sort_by(|a, b| b.size.cmp(&a.size));
+        self.functions.truncate(TOP_FUNCTIONS);
+    }
+}
+
+// -- private --
+
+/// The wasm file, the JS file and snippets with paths relative to the `pkg` folder.
+#[throws]
+async fn pkg_files(crate_name: &str, crate_path: &Path) -> Vec<(String, PathBuf)> {
+    let pkg_path = crate_path.join("pkg");
+    let mut files = vec![
+        (
+            format!("{crate_name}_bg.wasm"),
+            pkg_path.join(format!("{crate_name}_bg.wasm")),
+        ),
+        (
+            format!("{crate_name}.js"),
+            pkg_path.join(format!("{crate_name}.js")),
+        ),
+    ];
+    let snippets_path = pkg_path.join("snippets");
+    if fs::metadata(&snippets_path).await.is_ok() {
+        let snippets = visit_files(&snippets_path).try_collect::<Vec<_>>().await?;
+        for snippet in snippets {
+            let path = snippet.path();
+            let file = path
+                .strip_prefix(&pkg_path)?
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((file, path));
+        }
+    }
+    files
+}
+
+/// Demangled function names with their code sizes, `[unknown]` for functions without a name.
+/// Empty when the name section is missing.
+#[throws]
+fn function_sizes(wasm: &[u8]) -> Vec<(String, u64)> {
+    let mut imported_functions = 0;
+    let mut body_sizes = Vec::new();
+    let mut names = BTreeMap::new();
+
+    for payload in Parser::new(0).parse_all(wasm) {
+        match payload? {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    if let TypeRef::Func(_) = import?.ty {
+                        imported_functions += 1;
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) => {
+                body_sizes.push(body.range().len() as u64);
+            }
+            Payload::CustomSection(reader) if reader.name() == "name" => {
+                for name in NameSectionReader::new(reader.data(), reader.data_offset()) {
+                    // Ignore a malformed name section, sizes are still valid.
+                    let Ok(Name::Function(function_names)) = name else {
+                        continue;
+                    };
+                    for naming in function_names.into_iter().flatten() {
+                        names.insert(naming.index, naming.name.to_owned());
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    if names.is_empty() {
+        return Vec::new();
+    }
+
+    body_sizes
+        .into_iter()
+        .enumerate()
+        .map(|(index, size)| {
+            let name = names
+                .get(&(imported_functions + index as u32))
+                .map(|name| format!("{:#}", rustc_demangle::demangle(name)))
+                .unwrap_or_else(|| UNKNOWN_NAME.to_owned());
+            (name, size)
+        })
+        .collect()
+}
+
+/// `zoon::element::Button::new` => `zoon`,
+/// `<zoon::Text as core::fmt::Debug>::fmt` => `zoon`,
+/// `<T as core::fmt::Debug>::fmt` => `core`.
+fn owner_crate(function_name: &str) -> &str {
+    let Some(qualified) = function_name.strip_prefix('<') else {
+        return path_crate(function_name).unwrap_or(UNKNOWN_NAME);
+    };
+    match qualified.split_once(" as ") {
+        Some((self_type, trait_path)) => path_crate(self_type)
+            .or_else(|| path_crate(trait_path))
+            .unwrap_or(UNKNOWN_NAME),
+        None => path_crate(qualified).unwrap_or(UNKNOWN_NAME),
+    }
+}
+
+fn path_crate(path: &str) -> Option<&str> {
+    let path = path.trim_start_matches(['&', '*', '<', '[', '(']);
+    let path = path.strip_prefix("mut ").unwrap_or(path);
+    let (crate_name, _) = path.split_once("::")?;
+    let valid = !crate_name.is_empty()
+        && crate_name
+            .chars()
+            .all(|char| char.is_alphanumeric() || char == '_');
+    valid.then_some(crate_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(files: &[(&str, u64)]) -> SizeReport {
+        SizeReport {
+            files: files
+                .iter()
+                .map(|(file, brotli)| FileSizes {
+                    crate_name: "frontend".to_owned(),
+                    file: (*file).to_owned(),
+                    brotli: *brotli,
+                    ..FileSizes::default()
+                })
+                .collect(),
+            ..SizeReport::default()
+        }
+    }
+
+    #[test]
+    fn test_owner_crate() {
+        assert_eq!(owner_crate("zoon::element::Button::new"), "zoon");
+        assert_eq!(owner_crate("<zoon::Text as core::fmt::Debug>::fmt"), "zoon");
+        assert_eq!(owner_crate("<T as core::fmt::Debug>::fmt"), "core");
+        assert_eq!(owner_crate("<[T] as core::fmt::Debug>::fmt"), "core");
+        assert_eq!(
+            owner_crate("<&mut alloc::vec::Vec<u8> as core::ops::drop::Drop>::drop"),
+            "alloc"
+        );
+        assert_eq!(owner_crate("<alloc::string::String>::new"), "alloc");
+        assert_eq!(owner_crate("__wbindgen_malloc"), UNKNOWN_NAME);
+        assert_eq!(owner_crate("<T as U>::f"), UNKNOWN_NAME);
+    }
+
+    #[test]
+    fn test_path_crate() {
+        assert_eq!(path_crate("std::io::stdio::_print"), Some("std"));
+        assert_eq!(path_crate("&mut core::fmt::Formatter"), Some("core"));
+        assert_eq!(path_crate("*const u8"), None);
+        assert_eq!(path_crate("{closure}::call"), None);
+        assert_eq!(path_crate("::leading"), None);
+    }
+
+    #[test]
+    fn test_check_against_tolerance() {
+        let baseline = report(&[("frontend_bg.wasm", 1000)]);
+        // Exactly at the tolerance.
+        assert!(report(&[("frontend_bg.wasm", 1010)])
+            .check_against(&baseline, 1.)
+            .is_ok());
+        assert!(report(&[("frontend_bg.wasm", 900)])
+            .check_against(&baseline, 0.)
+            .is_ok());
+
+        let error = report(&[("frontend_bg.wasm", 1011)])
+            .check_against(&baseline, 1.)
+            .unwrap_err();
+        assert!(error.to_string().contains("frontend/frontend_bg.wasm"));
+    }
+
+    #[test]
+    fn test_check_against_missing_files() {
+        let baseline = report(&[("frontend_bg.wasm", 1000), ("frontend.js", 0)]);
+        let current = report(&[
+            ("frontend_bg.wasm", 1000),
+            ("frontend.js", 100),
+            ("snippets/new.js", 5000),
+        ]);
+        // New files and files with an empty baseline aren't regressions.
+        assert!(current.check_against(&baseline, 0.).is_ok());
+        // Files removed since the baseline are ignored.
+        assert!(report(&[]).check_against(&baseline, 0.).is_ok());
+    }
+
+    #[test]
+    fn test_function_sizes() {
+        let module = [
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+            0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section: `() -> ()`
+            0x03, 0x03, 0x02, 0x00, 0x00, // function section: 2 functions
+            0x0a, 0x07, 0x02, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b, // code section
+        ];
+        assert!(function_sizes(&module).unwrap().is_empty());
+
+        let name_section = [
+            0x00, 0x0b, 0x04, b'n', b'a', b'm', b'e', // custom section `name`
+            0x01, 0x04, 0x01, 0x00, 0x01, b'f', // function 0 is `f`
+        ];
+        let module = [&module[..], &name_section].concat();
+        assert_eq!(
+            function_sizes(&module).unwrap(),
+            [("f".to_owned(), 2), (UNKNOWN_NAME.to_owned(), 2)]
+        );
+    }
+}
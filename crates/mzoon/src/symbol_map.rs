@@ -0,0 +1,192 @@
+use anyhow::{Context, Error};
+use fehler::throws;
+use gimli::{Dwarf, EndianSlice, LittleEndian, SectionId};
+use serde::Serialize;
+use std::{borrow::Cow, collections::BTreeMap, path::Path};
+use tokio::{fs, task::spawn_blocking};
+use wasmparser::{Name, NameSectionReader, Parser, Payload};
+
+// NOTE: Sync with Moon's `Frontend::symbol_map_script` and Zoon's `panic_hook`.
+const SYMBOL_MAP_FILE_EXTENSION: &str = "symbols.json";
+
+// ------ SymbolMap ------
+
+/// Sidecar for the `wasm` file used by Zoon's panic hook to symbolicate stack traces.
+#[derive(Debug, Default, Serialize)]
+struct SymbolMap {
+    /// Offset of the code section content in the wasm module.
+    /// DWARF addresses are relative to it while browsers report module offsets.
+    code_offset: u32,
+    /// Demangled function names by function index.
+    functions: BTreeMap<u32, String>,
+    files: Vec<String>,
+    /// `(address, index to files, line)` sorted by address.
+    lines: Vec<(u32, u32, u32)>,
+}
+
+// -- public --
+
+/// Writes `pkg/<crate_name>.symbols.json` with function names from the name section
+/// and source lines from DWARF (kept by `wasm-bindgen --keep-debug` in dev and profiling builds).
+#[throws]
+pub async fn create_symbol_map(crate_name: &str, crate_path: &Path) {
+    let pkg_path = crate_path.join("pkg");
+    let wasm = fs::read(pkg_path.join(format!("{crate_name}_bg.wasm")))
+        .await
+        .with_context(|| format!("Failed to read the wasm file of the crate '{crate_name}'"))?;
+
+    let symbol_map = spawn_blocking(move || symbol_map(&wasm)).await??;
+    if symbol_map.lines.is_empty() {
+        println!(
+            "No DWARF line info found in '{crate_name}', panics will include only function names"
+        );
+    }
+
+    fs::write(
+        pkg_path.join(format!("{crate_name}.{SYMBOL_MAP_FILE_EXTENSION}")),
+        serde_json::to_vec(&symbol_map)?,
+    )
+    .await
+    .with_context(|| format!("Failed to write the symbol map of the crate '{crate_name}'"))?;
+}
+
+// -- private --
+
+#[throws]
+fn symbol_map(wasm: &[u8]) -> SymbolMap {
+    let mut symbol_map = SymbolMap::default();
+    let mut debug_sections = BTreeMap::<&str, &[u8]>::new();
+
+    for payload in Parser::new(0).parse_all(wasm) {
+        match payload? {
+            Payload::CodeSectionStart { range, .. } => {
+                symbol_map.code_offset = range.start as u32;
+            }
+            Payload::CustomSection(reader) if reader.name() == "name" => {
+                for name in NameSectionReader::new(reader.data(), reader.data_offset()) {
+                    let Ok(Name::Function(function_names)) = name else {
+                        continue;
+                    };
+                    for naming in function_names.into_iter().flatten() {
+                        let name = format!("{:#}", rustc_demangle::demangle(naming.name));
+                        symbol_map.functions.insert(naming.index, name);
+                    }
+                }
+            }
+            Payload::CustomSection(reader) if reader.name().starts_with(".debug_") => {
+                debug_sections.insert(reader.name(), reader.data());
+            }
+            _ => (),
+        }
+    }
+
+    if !debug_sections.is_empty() {
+        add_lines(&mut symbol_map, &debug_sections).context("Failed to read DWARF")?;
+    }
+    symbol_map
+}
+
+#[throws]
+fn add_lines(symbol_map: &mut SymbolMap, debug_sections: &BTreeMap<&str, &[u8]>) {
+    let dwarf = Dwarf::load(|section: SectionId| -> Result<_, gimli::Error> {
+        let data = debug_sections
+            .get(section.name())
+            .copied()
+            .unwrap_or_default();
+        Ok(EndianSlice::new(data, LittleEndian))
+    })?;
+
+    let mut file_indices = BTreeMap::<String, u32>::new();
+    let mut units = dwarf.units();
+    while let Some(header) = units.next()? {
+        let unit = dwarf.unit(header)?;
+        let Some(program) = unit.line_program.clone() else {
+            continue;
+        };
+        let mut rows = program.rows();
+        while let Some((header, row)) = rows.next_row()? {
+            if row.end_sequence() {
+                continue;
+            }
+            let (Some(file), Some(line)) = (row.file(header), row.line()) else {
+                continue;
+            };
+            let mut path = String::new();
+            if let Some(directory) = file.directory(header) {
+                path.push_str(&dwarf.attr_string(&unit, directory)?.to_string_lossy());
+                path.push('/');
+            }
+            path.push_str(
+                &dwarf
+                    .attr_string(&unit, file.path_name())?
+                    .to_string_lossy(),
+            );
+            let path = shorten_path(&path);
+
+            let file_index = match file_indices.get(path.as_ref()) {
+                Some(file_index) => *file_index,
+                None => {
+                    let file_index = symbol_map.files.len() as u32;
+                    file_indices.insert(path.to_string(), file_index);
+                    symbol_map.files.push(path.into_owned());
+                    file_index
+                }
+            };
+            symbol_map
+                .lines
+                .push((row.address() as u32, file_index, line.get() as u32));
+        }
+    }
+
+    symbol_map.lines.sort_unstable();
+    // Keep only rows where the location changes to make the sidecar smaller.
+    symbol_map
+        .lines
+        .dedup_by(|next, previous| (next.1, next.2) == (previous.1, previous.2));
+}
+
+/// Removes the Cargo registry prefix and normalizes separators,
+/// e.g. `/home/me/.cargo/registry/src/index.crates.io-6f17d22bba15001f/dominator-0.5.34/src/dom.rs`
+/// => `dominator-0.5.34/src/dom.rs`.
+fn shorten_path(path: &str) -> Cow<str> {
+    let path = if path.contains('\\') {
+        Cow::from(path.replace('\\', "/"))
+    } else {
+        Cow::from(path)
+    };
+    match path.find("/registry/src/") {
+        Some(index) => {
+            let crate_path = &path[index + "/registry/src/".len()..];
+            let crate_path = crate_path
+                .split_once('/')
+                .map(|(_, crate_path)| crate_path)
+                .unwrap_or(crate_path);
+            Cow::from(crate_path.to_owned())
+        }
+        None => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shorten_path() {
+        assert_eq!(
+            shorten_path("/home/me/.cargo/registry/src/index.crates.io-6f17d22bba15001f/dominator-0.5.34/src/dom.rs"),
+            "dominator-0.5.34/src/dom.rs"
+        );
+        assert_eq!(
+            shorten_path(
+                r"C:\Users\me\.cargo\registry\src\index.crates.io-6f17d22bba15001f\dominator-0.5.34\src\dom.rs"
+            ),
+            "dominator-0.5.34/src/dom.rs"
+        );
+        assert_eq!(shorten_path("frontend/src/main.rs"), "frontend/src/main.rs");
+        assert_eq!(
+            shorten_path(r"frontend\src\main.rs"),
+            "frontend/src/main.rs"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::config::Tools;
+use anyhow::{anyhow, Context, Error};
+use bool_ext::BoolExt;
+use fehler::throws;
+use std::{
+    env,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+use tokio::process::Command;
+
+// ------ Tool ------
+
+/// An external binary with a pinned version used by mzoon to build the frontend.
+pub struct Tool {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub version_args: &'static [&'static str],
+    pub expected_version_output_start: &'static str,
+    /// Executable path relative to the installation folder, e.g. `bin/wasm-opt`.
+    pub executable: &'static str,
+    /// Installation folder used when `tools.cache_dir` isn't set.
+    pub project_install_dir: &'static str,
+    /// Installation folder relative to `tools.cache_dir`, e.g. `binaryen-123`.
+    pub cache_install_dir: &'static str,
+}
+
+impl Tool {
+    /// Finds the tool with the expected version in this order:
+    /// 1. The path configured in `MoonZoon.toml` or by an env variable.
+    /// 2. The project installation folder.
+    /// 3. The shared cache folder.
+    /// 4. Folders in `PATH`.
+    ///
+    /// Returns `None` if the tool has to be installed.
+    #[throws]
+    pub async fn find(&self, configured_path: Option<&Path>, tools: &Tools) -> Option<PathBuf> {
+        if let Some(path) = configured_path {
+            self.check(path)
+                .await
+                .with_context(|| format!("Invalid configured {} {path:?}", self.name))?;
+            return Some(path.to_owned());
+        }
+
+        let mut candidates = vec![Path::new(self.project_install_dir).join(self.executable)];
+        if let Some(cache_dir) = &tools.cache_dir {
+            candidates.push(cache_dir.join(self.cache_install_dir).join(self.executable));
+        }
+        for candidate in candidates {
+            if self.check(&candidate).await.is_ok() {
+                return Some(candidate);
+            }
+        }
+
+        if let Some(path) = self.find_in_path().await {
+            println!("{} {} found in PATH: {path:?}", self.name, self.version);
+            return Some(path);
+        }
+
+        if tools.offline {
+            Err(anyhow!(
+                "{name} {version} not found and downloads are disabled by the offline mode. \
+                Set `tools.{key}` in MoonZoon.toml, install it to '{project_install_dir}' \
+                or add it to PATH",
+                name = self.name,
+                version = self.version,
+                key = self.name.replace('-', "_"),
+                project_install_dir = self.project_install_dir,
+            ))?;
+        }
+        None
+    }
+
+    /// The folder where the tool should be installed.
+    pub fn install_dir(&self, tools: &Tools) -> PathBuf {
+        match &tools.cache_dir {
+            Some(cache_dir) => cache_dir.join(self.cache_install_dir),
+            None => PathBuf::from(self.project_install_dir),
+        }
+    }
+
+    /// Checks the installed tool and returns its executable path.
+    #[throws]
+    pub async fn check_installed(&self, install_dir: &Path) -> PathBuf {
+        let path = install_dir.join(self.executable);
+        self.check(&path)
+            .await
+            .with_context(|| format!("{} installation failed", self.name))?;
+        path
+    }
+
+    #[throws]
+    async fn check(&self, path: &Path) {
+        let version_output = Command::new(path)
+            .args(self.version_args)
+            .output()
+            .await?
+            .stdout;
+
+        version_output
+            .starts_with(self.expected_version_output_start.as_bytes())
+            .err(anyhow!(
+                "{}'s expected version is {}, found '{}'",
+                self.name,
+                self.version,
+                String::from_utf8_lossy(&version_output).trim()
+            ))?;
+    }
+
+    async fn find_in_path(&self) -> Option<PathBuf> {
+        let file_name = Path::new(self.executable)
+            .file_name()
+            .unwrap_or(OsStr::new(self.name));
+        let mut file_name = file_name.to_owned();
+        file_name.push(env::consts::EXE_SUFFIX);
+
+        for dir in env::split_paths(&env::var_os("PATH")?) {
+            let candidate = dir.join(&file_name);
+            if candidate.is_file() && self.check(&candidate).await.is_ok() {
+                return Some(candidate);
+            }
+        }
+        None
+    }
+}
@@ -1,6 +1,7 @@
-use crate::{helper::download, BuildMode};
+use crate::config::Tools;
+use crate::tool::Tool;
+use crate::{helper::download_verified, BuildMode};
 use anyhow::{anyhow, Context, Error};
-use apply::Apply;
 use bool_ext::BoolExt;
 use cargo_metadata::MetadataCommand;
 use cfg_if::cfg_if;
@@ -8,19 +9,34 @@ use const_format::{concatcp, formatcp};
 use fehler::throws;
 use flate2::read::GzDecoder;
 use std::ffi::OsStr;
+use std::fs::create_dir_all;
 use std::path::{Path, PathBuf, MAIN_SEPARATOR as SEP};
 use tar::Archive;
-use tokio::process::Command;
+use tokio::{process::Command, task};
 
 // NOTE: Sync with zoon's wasm-bindgen version.
 const VERSION: &str = "0.2.100";
 
+static WASM_BINDGEN: Tool = Tool {
+    name: "wasm-bindgen",
+    version: VERSION,
+    version_args: &["-V"],
+    expected_version_output_start: concatcp!("wasm-bindgen ", VERSION),
+    executable: "wasm-bindgen",
+    project_install_dir: "frontend",
+    cache_install_dir: concatcp!("wasm-bindgen-", VERSION),
+};
+
 // -- public --
 
+/// Returns the path to the wasm-bindgen executable.
 #[throws]
-pub async fn check_or_install_wasm_bindgen() {
-    if check_wasm_bindgen().await.is_ok() {
-        return;
+pub async fn check_or_install_wasm_bindgen(tools: &Tools) -> PathBuf {
+    if let Some(path) = WASM_BINDGEN
+        .find(tools.wasm_bindgen.as_deref(), tools)
+        .await?
+    {
+        return path;
     }
 
     const TARGET: &str = env!("TARGET");
@@ -57,20 +73,34 @@ pub async fn check_or_install_wasm_bindgen() {
             "Pre-compiled wasm-bindgen binary '{NEAREST_TARGET}' will be used for the target platform '{TARGET}'"
         );
     }
-    download(DOWNLOAD_URL)
-        .await
-        .context(formatcp!(
-            "Failed to download wasm-bindgen from the url '{DOWNLOAD_URL}'"
-        ))?
-        .apply(unpack_wasm_bindgen)
-        .context("Failed to unpack wasm-bindgen")?;
+    let tar_gz = download_verified(
+        DOWNLOAD_URL,
+        concatcp!(DOWNLOAD_URL, ".sha256sum"),
+        tools.wasm_bindgen_sha256.as_deref(),
+    )
+    .await
+    .context(formatcp!(
+        "Failed to download wasm-bindgen from the url '{DOWNLOAD_URL}'"
+    ))?;
+
+    let install_dir = WASM_BINDGEN.install_dir(tools);
+    task::spawn_blocking({
+        let install_dir = install_dir.clone();
+        move || unpack_wasm_bindgen(tar_gz, install_dir)
+    })
+    .await?
+    .context("Failed to unpack wasm-bindgen")?;
+
+    let path = WASM_BINDGEN.check_installed(&install_dir).await?;
     println!("wasm-bindgen installed");
+    path
 }
 
 // https://rustwasm.github.io/wasm-bindgen/reference/cli.html
 // https://webassembly.org/roadmap/
 #[throws]
 pub async fn build_with_wasm_bindgen(
+    wasm_bindgen_path: &Path,
     build_mode: BuildMode,
     crate_name: &str,
     crate_path: &Path,
@@ -91,6 +121,10 @@ pub async fn build_with_wasm_bindgen(
     if build_mode.is_dev() {
         args.push("--debug".as_ref());
     }
+    if build_mode.is_not_release() {
+        // DWARF for the symbol map.
+        args.push("--keep-debug".as_ref());
+    }
 
     let target_path = MetadataCommand::new().no_deps().exec()?.target_directory;
     let target_profile_folder = build_mode.target_profile_folder();
@@ -98,7 +132,7 @@ pub async fn build_with_wasm_bindgen(
         format!("{target_path}{SEP}wasm32-unknown-unknown{SEP}{target_profile_folder}{SEP}{crate_name}.wasm");
     args.push(wasm_path.as_ref());
 
-    Command::new("frontend/wasm-bindgen")
+    Command::new(wasm_bindgen_path)
         .args(&args)
         .status()
         .await
@@ -110,25 +144,7 @@ pub async fn build_with_wasm_bindgen(
 // -- private --
 
 #[throws]
-async fn check_wasm_bindgen() {
-    const EXPECTED_VERSION_OUTPUT_START: &[u8] = concatcp!("wasm-bindgen ", VERSION).as_bytes();
-
-    let version_output = Command::new("frontend/wasm-bindgen")
-        .args(&["-V"])
-        .output()
-        .await?
-        .stdout;
-
-    if !version_output.starts_with(EXPECTED_VERSION_OUTPUT_START) {
-        Err(anyhow!(concatcp!(
-            "wasm-bindgen's expected version is ",
-            VERSION
-        )))?;
-    }
-}
-
-#[throws]
-fn unpack_wasm_bindgen(tar_gz: Vec<u8>) {
+fn unpack_wasm_bindgen(tar_gz: Vec<u8>, install_dir: PathBuf) {
     let tar = GzDecoder::new(tar_gz.as_slice());
     let mut archive = Archive::new(tar);
 
@@ -141,8 +157,8 @@ fn unpack_wasm_bindgen(tar_gz: Vec<u8>) {
         if file_stem != "wasm-bindgen" {
             continue;
         }
-        let mut destination = PathBuf::from("frontend");
-        destination.push(path.file_name().unwrap());
+        create_dir_all(&install_dir)?;
+        let destination = install_dir.join(path.file_name().unwrap());
         entry.unpack(destination)?;
         return;
     }
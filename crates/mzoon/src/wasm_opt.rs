@@ -1,25 +1,36 @@
-use crate::{helper::download, BuildMode};
+use crate::config::Tools;
+use crate::tool::Tool;
+use crate::{helper::download_verified, BuildMode};
 use anyhow::{anyhow, Context, Error};
-use apply::Apply;
 use bool_ext::BoolExt;
 use cfg_if::cfg_if;
 use const_format::{concatcp, formatcp};
 use fehler::throws;
 use flate2::read::GzDecoder;
 use std::fs::create_dir_all;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tar::Archive;
-use tokio::process::Command;
+use tokio::{process::Command, task};
 
 const VERSION: &str = "123";
-static WASM_OPT_PATH: &str = "frontend/binaryen/bin/wasm-opt";
+
+static WASM_OPT: Tool = Tool {
+    name: "wasm-opt",
+    version: VERSION,
+    version_args: &["--version"],
+    expected_version_output_start: concatcp!("wasm-opt version ", VERSION),
+    executable: "bin/wasm-opt",
+    project_install_dir: "frontend/binaryen",
+    cache_install_dir: concatcp!("binaryen-", VERSION),
+};
 
 // -- public --
 
+/// Returns the path to the wasm-opt executable.
 #[throws]
-pub async fn check_or_install_wasm_opt() {
-    if check_wasm_opt().await.is_ok() {
-        return;
+pub async fn check_or_install_wasm_opt(tools: &Tools) -> PathBuf {
+    if let Some(path) = WASM_OPT.find(tools.wasm_opt.as_deref(), tools).await? {
+        return path;
     }
 
     const TARGET: &str = env!("TARGET");
@@ -49,19 +60,36 @@ pub async fn check_or_install_wasm_opt() {
         "Pre-compiled wasm-opt binary '{ARCHIVE_PLATFORM}' will be used for the target platform '{TARGET}'"
     );
 
-    download(DOWNLOAD_URL)
-        .await
-        .context(formatcp!(
-            "Failed to download wasm-opt from the url '{DOWNLOAD_URL}'"
-        ))?
-        .apply(unpack_wasm_opt)
-        .await
-        .context("Failed to unpack wasm-opt")?;
+    let tar_gz = download_verified(
+        DOWNLOAD_URL,
+        concatcp!(DOWNLOAD_URL, ".sha256"),
+        tools.wasm_opt_sha256.as_deref(),
+    )
+    .await
+    .context(formatcp!(
+        "Failed to download wasm-opt from the url '{DOWNLOAD_URL}'"
+    ))?;
+
+    let install_dir = WASM_OPT.install_dir(tools);
+    task::spawn_blocking({
+        let install_dir = install_dir.clone();
+        move || unpack_wasm_opt(tar_gz, install_dir)
+    })
+    .await?
+    .context("Failed to unpack wasm-opt")?;
+
+    let path = WASM_OPT.check_installed(&install_dir).await?;
     println!("wasm-opt installed");
+    path
 }
 
 #[throws]
-pub async fn optimize_with_wasm_opt(build_mode: BuildMode, crate_name: &str, crate_path: &Path) {
+pub async fn optimize_with_wasm_opt(
+    wasm_opt_path: &Path,
+    build_mode: BuildMode,
+    crate_name: &str,
+    crate_path: &Path,
+) {
     let wasm_path = crate_path.join("pkg").join(format!("{crate_name}_bg.wasm"));
     let mut args = vec![
         wasm_path.as_os_str(),
@@ -75,7 +103,7 @@ pub async fn optimize_with_wasm_opt(build_mode: BuildMode, crate_name: &str, cra
     if let BuildMode::Profiling = build_mode {
         args.push("--debuginfo".as_ref());
     }
-    Command::new(WASM_OPT_PATH)
+    Command::new(wasm_opt_path)
         .args(&args)
         .status()
         .await
@@ -87,25 +115,7 @@ pub async fn optimize_with_wasm_opt(build_mode: BuildMode, crate_name: &str, cra
 // -- private --
 
 #[throws]
-async fn check_wasm_opt() {
-    const EXPECTED_VERSION_OUTPUT_START: &[u8] = concatcp!("wasm-opt version ", VERSION).as_bytes();
-
-    let version_output = Command::new(WASM_OPT_PATH)
-        .args(&["--version"])
-        .output()
-        .await?
-        .stdout;
-
-    if !version_output.starts_with(EXPECTED_VERSION_OUTPUT_START) {
-        Err(anyhow!(concatcp!(
-            "wasm-opt's expected version is ",
-            VERSION
-        )))?;
-    }
-}
-
-#[throws]
-async fn unpack_wasm_opt(tar_gz: Vec<u8>) {
+fn unpack_wasm_opt(tar_gz: Vec<u8>, install_dir: PathBuf) {
     let tar = GzDecoder::new(tar_gz.as_slice());
     let mut archive = Archive::new(tar);
 
@@ -117,21 +127,18 @@ async fn unpack_wasm_opt(tar_gz: Vec<u8>) {
             .file_name()
             .ok_or_else(|| anyhow!("Entry without a file name"))?
             .to_str()
-            .ok_or_else(|| anyhow!("Entry with a non-Unicode file name"))?;
+            .ok_or_else(|| anyhow!("Entry with a non-Unicode file name"))?
+            .to_owned();
 
-        let output_dir = match file_name {
+        let output_dir = match file_name.as_str() {
             // Windows | Linux + Mac
-            "wasm-opt.exe" | "wasm-opt" => "frontend/binaryen/bin",
+            "wasm-opt.exe" | "wasm-opt" => install_dir.join("bin"),
             // The lib is required on Mac.
             // Note: It's called `binaryen.lib` on Windows and `libbinaryen.a` on Linux.
-            "libbinaryen.dylib" => "frontend/binaryen/lib",
+            "libbinaryen.dylib" => install_dir.join("lib"),
             _ => continue,
         };
-        create_dir_all(output_dir)?;
-        entry.unpack(format!("{output_dir}/{file_name}"))?;
-    }
-
-    if let Err(error) = check_wasm_opt().await {
-        eprintln!("wasm-opt installation failed: {error:#}");
+        create_dir_all(&output_dir)?;
+        entry.unpack(output_dir.join(file_name))?;
     }
 }
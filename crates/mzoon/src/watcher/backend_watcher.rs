@@ -74,7 +74,7 @@ async fn on_change(
 }
 
 async fn build_and_run(server: Arc<Mutex<Option<Child>>>, build_mode: BuildMode, https: bool) {
-    if let Err(error) = build_backend(build_mode, https).await {
+    if let Err(error) = build_backend(build_mode, https, false).await {
         return eprintln!("{}", error);
     }
     match run_backend(build_mode) {
@@ -1,6 +1,6 @@
 use super::project_watcher::ProjectWatcher;
 use crate::build_frontend::build_frontend;
-use crate::config::Config;
+use crate::config::{Config, Tools};
 use crate::BuildMode;
 use anyhow::{Context, Error, Result};
 use fehler::throws;
@@ -39,6 +39,7 @@ impl FrontendWatcher {
                 build_mode,
                 config.cache_busting,
                 config.frontend_multithreading == Some(true),
+                Arc::new(config.tools.clone()),
             )),
         }
     }
@@ -57,6 +58,7 @@ async fn on_change(
     build_mode: BuildMode,
     cache_busting: bool,
     frontend_multithreading: bool,
+    tools: Arc<Tools>,
 ) {
     let mut build_task = None::<JoinHandle<()>>;
     let mut compilation_killer_sender = None::<watch::Sender<()>>;
@@ -79,6 +81,7 @@ async fn on_change(
             build_mode,
             cache_busting,
             frontend_multithreading,
+            Arc::clone(&tools),
             Some(new_compilation_killer_sender.subscribe()),
         )));
         compilation_killer_sender = Some(new_compilation_killer_sender);
@@ -98,6 +101,7 @@ async fn build_and_reload(
     build_mode: BuildMode,
     cache_busting: bool,
     frontend_multithreading: bool,
+    tools: Arc<Tools>,
     compilation_killer: Option<watch::Receiver<()>>,
 ) {
     if let Err(error) = build_frontend(
@@ -105,6 +109,8 @@ async fn build_and_reload(
         cache_busting,
         false,
         frontend_multithreading,
+        &tools,
+        None,
         compilation_killer,
     )
     .await
@@ -0,0 +1,231 @@
+//! Screen reader announcements, focus helpers and the accessibility audit.
+//! ARIA attributes are set by the `Accessible` ability.
+
+use crate::*;
+use std::{cell::RefCell, collections::BTreeSet};
+
+const FOCUSABLE_SELECTOR: &str = "a[href], area[href], button:not([disabled]), input:not([disabled]):not([type=hidden]), select:not([disabled]), textarea:not([disabled]), iframe, [contenteditable], [tabindex]:not([tabindex='-1'])";
+const AUDIT_DELAY_MS: u32 = 1000;
+const MAX_ELEMENT_PREVIEW_LENGTH: usize = 120;
+
+thread_local! {
+    static LIVE_REGIONS: RefCell<Option<(web_sys::HtmlElement, web_sys::HtmlElement)>> = RefCell::new(None);
+    static REPORTED_ISSUES: RefCell<BTreeSet<String>> = RefCell::new(BTreeSet::new());
+}
+
+// ------ announcements ------
+
+/// Reads the message by screen readers through a visually hidden live region.
+pub fn announce(message: &str, politeness: Politeness) {
+    let live_region = LIVE_REGIONS.with(|live_regions| {
+        let mut live_regions = live_regions.borrow_mut();
+        let (polite, assertive) = live_regions.get_or_insert_with(|| {
+            (
+                live_region(Politeness::Polite),
+                live_region(Politeness::Assertive),
+            )
+        });
+        match politeness {
+            Politeness::Polite => polite.clone(),
+            Politeness::Assertive => assertive.clone(),
+        }
+    });
+    // Clear and set the text in the next task so the same message is announced again.
+    live_region.set_text_content(None);
+    let message = message.to_owned();
+    Task::start(async move {
+        Timer::sleep(50).await;
+        live_region.set_text_content(Some(&message));
+    });
+}
+
+fn live_region(politeness: Politeness) -> web_sys::HtmlElement {
+    let element = document()
+        .create_element("div")
+        .unwrap_throw()
+        .unchecked_into::<web_sys::HtmlElement>();
+    let politeness: &str = politeness.into();
+    element
+        .set_attribute("aria-live", politeness)
+        .unwrap_throw();
+    element.set_attribute("aria-atomic", "true").unwrap_throw();
+    element
+        .set_attribute(
+            "style",
+            "position: absolute; width: 1px; height: 1px; margin: -1px; padding: 0; overflow: hidden; clip: rect(0, 0, 0, 0); white-space: nowrap; border: 0;",
+        )
+        .unwrap_throw();
+    dominator::body().append_child(&element).unwrap_throw();
+    element
+}
+
+// ------ focus ------
+
+/// Focusable descendants in the DOM order.
+pub fn focusable_elements(container: &web_sys::Element) -> Vec<web_sys::HtmlElement> {
+    let Ok(node_list) = container.query_selector_all(FOCUSABLE_SELECTOR) else {
+        return Vec::new();
+    };
+    (0..node_list.length())
+        .filter_map(|index| {
+            node_list
+                .item(index)?
+                .dyn_into::<web_sys::HtmlElement>()
+                .ok()
+        })
+        .collect()
+}
+
+/// Focuses the first focusable descendant or the container itself.
+pub fn focus_first(container: &web_sys::Element) {
+    match focusable_elements(container).first() {
+        Some(element) => {
+            let _ = element.focus();
+        }
+        None => {
+            if let Some(container) = container.dyn_ref::<web_sys::HtmlElement>() {
+                let _ = container.focus();
+            }
+        }
+    }
+}
+
+/// Moves the focus from the last to the first focusable descendant (or back with `backwards`).
+/// Returns `true` when the focus has been moved.
+pub(crate) fn cycle_focus(container: &web_sys::Element, backwards: bool) -> bool {
+    let elements = focusable_elements(container);
+    let (Some(first), Some(last)) = (elements.first(), elements.last()) else {
+        return false;
+    };
+    let active_element = document().active_element();
+    let is_active = |element: &web_sys::HtmlElement| {
+        active_element.as_ref() == Some(element.unchecked_ref::<web_sys::Element>())
+    };
+    let outside = active_element
+        .as_ref()
+        .map(|active_element| not(container.contains(Some(active_element))))
+        .unwrap_or(true);
+    let target = match backwards {
+        true if outside || is_active(first) => last,
+        false if outside || is_active(last) => first,
+        _ => return false,
+    };
+    let _ = target.focus();
+    true
+}
+
+// ------ audit ------
+
+/// Checks the document for common accessibility issues and writes them as warnings to the console.
+/// It runs automatically in debug builds after the app has been started.
+///
+/// Checks: images without `alt`, buttons and links without an accessible name,
+/// form controls without a label and `aria-*` references to missing ids.
+pub fn audit() -> Vec<String> {
+    let mut issues = Vec::new();
+    let document = document();
+    let mut check = |selector: &str, issue: &str, has_issue: &dyn Fn(&web_sys::Element) -> bool| {
+        for element in query_all(&document, selector) {
+            if has_issue(&element) {
+                issues.push(format!("{issue}: {}", element_preview(&element)));
+            }
+        }
+    };
+
+    check(
+        "img:not([alt])",
+        "Image without description (`alt`)",
+        &|element| not(is_hidden(element)),
+    );
+    check(
+        "button, [role=button], a[href], [role=link]",
+        "Button or link without an accessible name",
+        &|element| not(is_hidden(element)) && not(has_accessible_name(element, true)),
+    );
+    check(
+        "input:not([type=hidden]):not([type=button]):not([type=submit]), textarea, select, [role=checkbox], [role=slider], [role=listbox], [role=radiogroup]",
+        "Form control without a label",
+        &|element| not(is_hidden(element)) && not(has_accessible_name(element, false)),
+    );
+    for attribute in ["aria-labelledby", "aria-describedby", "aria-controls"] {
+        let issue = format!("`{attribute}` references a missing id");
+        check(&format!("[{attribute}]"), &issue, &|element| {
+            element
+                .get_attribute(attribute)
+                .unwrap_or_default()
+                .split_whitespace()
+                .any(|id| document.get_element_by_id(id).is_none())
+        });
+    }
+
+    REPORTED_ISSUES.with(|reported_issues| {
+        let mut reported_issues = reported_issues.borrow_mut();
+        for issue in &issues {
+            if reported_issues.insert(issue.clone()) {
+                console::warn(&format!("Accessibility: {issue}"));
+            }
+        }
+    });
+    issues
+}
+
+pub(crate) fn audit_after_start() {
+    Task::start(async {
+        Timer::sleep(AUDIT_DELAY_MS).await;
+        audit();
+    });
+}
+
+fn query_all(document: &web_sys::Document, selector: &str) -> Vec<web_sys::Element> {
+    let Ok(node_list) = document.query_selector_all(selector) else {
+        return Vec::new();
+    };
+    (0..node_list.length())
+        .filter_map(|index| node_list.item(index)?.dyn_into::<web_sys::Element>().ok())
+        .collect()
+}
+
+fn has_accessible_name(element: &web_sys::Element, text_content_is_name: bool) -> bool {
+    let has_attribute = |name| {
+        element
+            .get_attribute(name)
+            .is_some_and(|value| not(value.trim().is_empty()))
+    };
+    if has_attribute("aria-label") || has_attribute("aria-labelledby") || has_attribute("title") {
+        return true;
+    }
+    if text_content_is_name {
+        let has_text = element
+            .text_content()
+            .is_some_and(|text| not(text.trim().is_empty()));
+        let has_image_with_alt = element
+            .query_selector("img[alt]:not([alt=''])")
+            .ok()
+            .flatten()
+            .is_some();
+        return has_text || has_image_with_alt;
+    }
+    let has_label_for_id = not(element.id().is_empty())
+        && document()
+            .query_selector(&format!("label[for='{}']", element.id()))
+            .ok()
+            .flatten()
+            .is_some();
+    has_label_for_id || element.closest("label").ok().flatten().is_some()
+}
+
+fn is_hidden(element: &web_sys::Element) -> bool {
+    element
+        .closest("[aria-hidden=true]")
+        .ok()
+        .flatten()
+        .is_some()
+}
+
+fn element_preview(element: &web_sys::Element) -> String {
+    let html = element.outer_html();
+    match html.char_indices().nth(MAX_ELEMENT_PREVIEW_LENGTH) {
+        Some((index, _)) => format!("{}…", &html[..index]),
+        None => html,
+    }
+}
@@ -0,0 +1,124 @@
+use crate::*;
+use moonlight::{serde_json, ClientLogKind, ClientLogRecord, SessionId};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
+use web_sys::{Request, RequestInit};
+
+// NOTE: Sync with Moon's `client_log_responder`.
+const CLIENT_LOG_URL: &str = "/_api/client_log";
+const FLUSH_INTERVAL_MS: u32 = 5_000;
+const MAX_BATCH_LEN: usize = 20;
+// The oldest records are dropped when Moon is unreachable.
+const MAX_QUEUE_LEN: usize = 200;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static QUEUE: Lazy<Mutex<Vec<ClientLogRecord>>> = Lazy::new(<_>::default);
+static SESSION_ID: Lazy<Mutex<Option<SessionId>>> = Lazy::new(<_>::default);
+
+// ------ public ------
+
+/// Starts shipping panics, `console::error` / `eprintln!` messages and failed `Connection`
+/// requests to Moon in batches. Moon passes them to the sink set by `moon::set_client_log_sink`.
+/// Records carry the session id of the first `Connection` created in the app.
+pub fn enable() {
+    if ENABLED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    Task::start(async {
+        loop {
+            Timer::sleep(FLUSH_INTERVAL_MS).await;
+            flush().await;
+        }
+    });
+}
+
+// ------ crate ------
+
+pub(crate) fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// Records are attributed to the session of the first `Connection` created in the app,
+/// later connections don't change it.
+pub(crate) fn set_session_id_if_unset(session_id: SessionId) {
+    SESSION_ID.lock().unwrap_throw().get_or_insert(session_id);
+}
+
+pub(crate) fn push(kind: ClientLogKind, message: &str) {
+    if !is_enabled() {
+        return;
+    }
+    let record = ClientLogRecord {
+        kind,
+        message: message.to_owned(),
+        url: page_url(),
+        timestamp: js_sys::Date::now(),
+    };
+    let batch_full = {
+        let mut queue = QUEUE.lock().unwrap_throw();
+        if queue.len() >= MAX_QUEUE_LEN {
+            queue.remove(0);
+        }
+        queue.push(record);
+        queue.len() >= MAX_BATCH_LEN
+    };
+    if batch_full {
+        Task::start(flush());
+    }
+}
+
+/// Sends the panic with other queued records by `sendBeacon`
+/// because the app doesn't run async tasks reliably after a panic.
+pub(crate) fn push_panic_and_flush(message: &str) {
+    push(ClientLogKind::Panic, message);
+    let Some(body) = take_batch_body() else {
+        return;
+    };
+    if let Ok(window) = js_sys::global().dyn_into::<web_sys::Window>() {
+        let _ = window
+            .navigator()
+            .send_beacon_with_opt_str(&client_log_url(), Some(&body));
+    }
+}
+
+// ------ private ------
+
+async fn flush() {
+    let Some(body) = take_batch_body() else {
+        return;
+    };
+    let request_init = RequestInit::new();
+    request_init.set_method("POST");
+    request_init.set_body(&JsValue::from(body));
+    let Ok(request) = Request::new_with_str_and_init(&client_log_url(), &request_init) else {
+        return;
+    };
+    // Failures aren't reported to prevent a feedback loop through `console::error`.
+    let _ = JsFuture::from(window().fetch_with_request(&request)).await;
+}
+
+fn take_batch_body() -> Option<String> {
+    let records = std::mem::take(&mut *QUEUE.lock().ok()?);
+    if records.is_empty() {
+        return None;
+    }
+    serde_json::to_string(&records).ok()
+}
+
+fn client_log_url() -> String {
+    // `sendBeacon` can't set headers so the session id is passed in the query.
+    match *SESSION_ID.lock().unwrap_throw() {
+        Some(session_id) => with_base_path(&format!("{CLIENT_LOG_URL}?session_id={session_id}")),
+        None => with_base_path(CLIENT_LOG_URL),
+    }
+}
+
+fn page_url() -> String {
+    Reflect::get(&js_sys::global(), &JsValue::from_str("location"))
+        .and_then(|location| Reflect::get(&location, &JsValue::from_str("href")))
+        .ok()
+        .and_then(|href| href.as_string())
+        .unwrap_or_default()
+}
@@ -1,5 +1,5 @@
 use crate::*;
-use moonlight::SessionId;
+use moonlight::{ClientLogKind, SessionId, UploadProgress};
 use std::{
     collections::BTreeMap,
     error::Error,
@@ -8,7 +8,12 @@ use std::{
     pin::Pin,
     sync::{Arc, Mutex},
 };
-use web_sys::{Request, RequestInit, Response};
+use web_sys::{File, Headers, Request, RequestInit, Response};
+
+// NOTE: Sync with Moon's `INSTANCE_HEADER`.
+const INSTANCE_HEADER: &str = "X-Moon-Instance";
+// NOTE: Sync with Moon's `FILE_NAME_HEADER`.
+const FILE_NAME_HEADER: &str = "X-Upload-File-Name";
 
 mod sse;
 use sse::SSE;
@@ -37,15 +42,42 @@ impl<DMsg> Clone for DMsgSenders<DMsg> {
     }
 }
 
+// ------ UploadProgresses ------
+
+#[derive(Clone, Default)]
+struct UploadProgresses(Arc<Mutex<BTreeMap<CorId, Mutable<Option<UploadProgress>>>>>);
+
+impl UploadProgresses {
+    fn insert(&self, cor_id: CorId) -> Mutable<Option<UploadProgress>> {
+        let progress = Mutable::new(None);
+        self.0
+            .lock()
+            .unwrap_throw()
+            .insert(cor_id, progress.clone());
+        progress
+    }
+
+    fn remove(&self, cor_id: &CorId) {
+        self.0.lock().unwrap_throw().remove(cor_id);
+    }
+
+    fn update(&self, upload_progress: UploadProgress) {
+        if let Some(progress) = self.0.lock().unwrap_throw().get(&upload_progress.cor_id) {
+            progress.set(Some(upload_progress));
+        }
+    }
+}
+
 // ------ Connection ------
 
 pub struct Connection<UMsg, DMsg> {
     session_id: SessionId,
-    _sse: SSE,
+    sse: SSE,
     auth_token_getter:
         Option<Box<dyn Fn() -> Pin<Box<dyn Future<Output = Option<AuthToken>>>> + Send + Sync>>,
     msg_types: PhantomData<(UMsg, DMsg)>,
     d_msg_senders: DMsgSenders<DMsg>,
+    upload_progresses: UploadProgresses,
 }
 
 impl<UMsg: Serialize, DMsg: DeserializeOwned + 'static> Connection<UMsg, DMsg> {
@@ -70,13 +102,21 @@ impl<UMsg: Serialize, DMsg: DeserializeOwned + 'static> Connection<UMsg, DMsg> {
             }
         };
 
+        let upload_progresses = UploadProgresses::default();
+        let upload_progress_handler = {
+            let upload_progresses = upload_progresses.clone();
+            move |upload_progress| upload_progresses.update(upload_progress)
+        };
+
         let session_id = SessionId::new();
+        client_log::set_session_id_if_unset(session_id);
         Self {
             session_id,
-            _sse: SSE::new(session_id, down_msg_handler),
+            sse: SSE::new(session_id, down_msg_handler, upload_progress_handler),
             auth_token_getter: None,
             msg_types: PhantomData,
             d_msg_senders,
+            upload_progresses,
         }
     }
 
@@ -126,40 +166,31 @@ impl<UMsg: Serialize, DMsg: DeserializeOwned + 'static> Connection<UMsg, DMsg> {
 
         // ---- Request ----
         let request =
-            Request::new_with_str_and_init("/_api/up_msg_handler", &request_init).unwrap_throw();
+            Request::new_with_str_and_init(&with_base_path("/_api/up_msg_handler"), &request_init)
+                .unwrap_throw();
 
         // ---- Headers ----
-        let headers = request.headers();
-        headers
-            .set("X-Correlation-ID", &cor_id.to_string())
-            .unwrap_throw();
-        headers
-            .set("X-Session-ID", &self.session_id.to_string())
-            .unwrap_throw();
-
-        if msg_options.auth_token {
-            let auth_token = if let Some(auth_token_getter) = &self.auth_token_getter {
-                auth_token_getter().await
-            } else {
-                None
-            };
-            if let Some(auth_token) = auth_token {
-                headers
-                    .set("X-Auth-Token", auth_token.as_str())
-                    .unwrap_throw();
-            }
-        }
+        self.set_headers(&request.headers(), cor_id, msg_options)
+            .await;
 
         // ---- Response ----
+        let report_failure = |error: SendUpMsgError| {
+            client_log::push(
+                ClientLogKind::RequestFailed,
+                &format!("UpMsg request {cor_id} failed: {error}"),
+            );
+            error
+        };
+
         let response = JsFuture::from(window().fetch_with_request(&request))
             .await
-            .map_err(|error| SendUpMsgError::RequestFailed(error))?
+            .map_err(|error| report_failure(SendUpMsgError::RequestFailed(error)))?
             .unchecked_into::<Response>();
 
         if response.ok() {
             return Ok(cor_id);
         }
-        Err(SendUpMsgError::ResponseIsNot2xx)
+        Err(report_failure(SendUpMsgError::ResponseIsNot2xx))
     }
 
     pub async fn exchange_msgs(&self, up_msg: UMsg) -> Result<(DMsg, CorId), ExchangeMsgsError> {
@@ -185,6 +216,141 @@ impl<UMsg: Serialize, DMsg: DeserializeOwned + 'static> Connection<UMsg, DMsg> {
             .map_err(|_| ExchangeMsgsError::ReceiveError(ReceiveDownMsgError::ConnectionClosed))?;
         Ok((d_msg, cor_id))
     }
+
+    /// Streams the file to Moon's upload handler (see `moon::set_upload_handler`).
+    /// Await `FileUpload::finished` to send the request.
+    pub fn upload_file<'a>(&'a self, file: &File) -> FileUpload<'a> {
+        self.upload_file_with_options(file, MsgOptions::default())
+    }
+
+    pub fn upload_file_with_options<'a>(
+        &'a self,
+        file: &File,
+        msg_options: MsgOptions,
+    ) -> FileUpload<'a> {
+        let cor_id = CorId::new();
+        let progress = self.upload_progresses.insert(cor_id);
+        let file = file.clone();
+        let request = Box::pin({
+            let progress = progress.clone();
+            async move {
+                let result = self.send_file(&file, cor_id, msg_options).await;
+                if result.is_ok() {
+                    let size = file.size() as u64;
+                    progress.set(Some(UploadProgress {
+                        cor_id,
+                        received: size,
+                        total: Some(size),
+                        done: true,
+                    }));
+                }
+                self.upload_progresses.remove(&cor_id);
+                result
+            }
+        });
+        FileUpload {
+            cor_id,
+            progress,
+            request,
+        }
+    }
+
+    async fn send_file(
+        &self,
+        file: &File,
+        cor_id: CorId,
+        msg_options: MsgOptions,
+    ) -> Result<CorId, SendUpMsgError> {
+        // ---- RequestInit ----
+        let request_init = RequestInit::new();
+        request_init.set_method("POST");
+        request_init.set_body(file);
+
+        // ---- Request ----
+        let request =
+            Request::new_with_str_and_init(&with_base_path("/_api/upload"), &request_init)
+                .unwrap_throw();
+
+        // ---- Headers ----
+        let headers = request.headers();
+        self.set_headers(&headers, cor_id, msg_options).await;
+        let file_name = js_sys::encode_uri_component(&file.name());
+        headers
+            .set(FILE_NAME_HEADER, &String::from(file_name))
+            .unwrap_throw();
+        let content_type = file.type_();
+        if !content_type.is_empty() {
+            headers.set("Content-Type", &content_type).unwrap_throw();
+        }
+
+        // ---- Response ----
+        let report_failure = |error: SendUpMsgError| {
+            client_log::push(
+                ClientLogKind::RequestFailed,
+                &format!("Upload request {cor_id} failed: {error}"),
+            );
+            error
+        };
+
+        let response = JsFuture::from(window().fetch_with_request(&request))
+            .await
+            .map_err(|error| report_failure(SendUpMsgError::RequestFailed(error)))?
+            .unchecked_into::<Response>();
+
+        if response.ok() {
+            return Ok(cor_id);
+        }
+        Err(report_failure(SendUpMsgError::ResponseIsNot2xx))
+    }
+
+    async fn set_headers(&self, headers: &Headers, cor_id: CorId, msg_options: MsgOptions) {
+        headers
+            .set("X-Correlation-ID", &cor_id.to_string())
+            .unwrap_throw();
+        headers
+            .set("X-Session-ID", &self.session_id.to_string())
+            .unwrap_throw();
+        // Sticky-session hint to route the request to the Moon instance holding the SSE connection.
+        if let Some(instance_id) = self.sse.instance_id() {
+            headers.set(INSTANCE_HEADER, &instance_id).unwrap_throw();
+        }
+
+        if msg_options.auth_token {
+            let auth_token = if let Some(auth_token_getter) = &self.auth_token_getter {
+                auth_token_getter().await
+            } else {
+                None
+            };
+            if let Some(auth_token) = auth_token {
+                headers
+                    .set("X-Auth-Token", auth_token.as_str())
+                    .unwrap_throw();
+            }
+        }
+    }
+}
+
+// ------ FileUpload ------
+
+pub struct FileUpload<'a> {
+    cor_id: CorId,
+    progress: Mutable<Option<UploadProgress>>,
+    request: Pin<Box<dyn Future<Output = Result<CorId, SendUpMsgError>> + 'a>>,
+}
+
+impl<'a> FileUpload<'a> {
+    pub fn cor_id(&self) -> CorId {
+        self.cor_id
+    }
+
+    /// `None` until Moon reports the first received bytes.
+    pub fn progress_signal(&self) -> impl Signal<Item = Option<UploadProgress>> {
+        self.progress.signal()
+    }
+
+    pub async fn finished(self) -> Result<CorId, SendUpMsgError> {
+        self.request.await
+    }
 }
 
 // ------ MsgOptions ------
@@ -1,15 +1,22 @@
 // @TODO remove / fix?
 #![allow(unexpected_cfgs)]
 
-use crate::moonlight::{DownMsgTransporterForDe, SessionId};
+use crate::moonlight::{DownMsgTransporterForDe, SessionId, UploadProgress};
 use crate::{format, *};
-use std::{error::Error, fmt};
+use std::{
+    error::Error,
+    fmt,
+    sync::{Arc, Mutex},
+};
 
 // ------ SSE ------
 
 pub struct SSE {
     reconnecting_event_source: SendWrapper<ReconnectingEventSource>,
     _down_msg_handler: SendWrapper<Closure<dyn FnMut(JsValue)>>,
+    _upload_progress_handler: SendWrapper<Closure<dyn FnMut(JsValue)>>,
+    _instance_handler: SendWrapper<Closure<dyn FnMut(JsValue)>>,
+    instance_id: Arc<Mutex<Option<String>>>,
 }
 
 impl Drop for SSE {
@@ -23,18 +30,48 @@ impl SSE {
     pub fn new<DMsg: DeserializeOwned>(
         session_id: SessionId,
         down_msg_handler: impl FnMut(DMsg, CorId) + 'static,
+        upload_progress_handler: impl FnMut(UploadProgress) + 'static,
     ) -> Self {
         let down_msg_handler = down_msg_handler_closure(down_msg_handler);
+        let upload_progress_handler = upload_progress_handler_closure(upload_progress_handler);
+
+        let instance_id = Arc::new(Mutex::new(None));
+        let instance_handler = instance_handler_closure(Arc::clone(&instance_id));
 
         let reconnecting_event_source = connect(session_id);
         reconnecting_event_source
             .add_event_listener("down_msg", down_msg_handler.as_ref().unchecked_ref());
+        reconnecting_event_source.add_event_listener(
+            "upload_progress",
+            upload_progress_handler.as_ref().unchecked_ref(),
+        );
+        reconnecting_event_source
+            .add_event_listener("instance", instance_handler.as_ref().unchecked_ref());
 
         Self {
             reconnecting_event_source: SendWrapper::new(reconnecting_event_source),
             _down_msg_handler: SendWrapper::new(down_msg_handler),
+            _upload_progress_handler: SendWrapper::new(upload_progress_handler),
+            _instance_handler: SendWrapper::new(instance_handler),
+            instance_id,
         }
     }
+
+    /// Id of the Moon instance holding the SSE connection, sent by Moon after each (re)connect.
+    pub fn instance_id(&self) -> Option<String> {
+        self.instance_id.lock().unwrap_throw().clone()
+    }
+}
+
+fn instance_handler_closure(
+    instance_id: Arc<Mutex<Option<String>>>,
+) -> Closure<dyn FnMut(JsValue)> {
+    Closure::new(move |event: JsValue| {
+        let data = Reflect::get(&event, &JsValue::from("data"))
+            .ok()
+            .and_then(|data| data.as_string());
+        *instance_id.lock().unwrap_throw() = data;
+    })
 }
 
 #[cfg(feature = "serde")]
@@ -49,6 +86,22 @@ fn down_msg_handler_closure<DMsg: DeserializeOwned>(
     )
 }
 
+#[cfg(feature = "serde")]
+fn upload_progress_handler_closure(
+    mut upload_progress_handler: impl FnMut(UploadProgress) + 'static,
+) -> Closure<dyn FnMut(JsValue)> {
+    Closure::new(move |event: JsValue| {
+        let upload_progress = Reflect::get(&event, &JsValue::from("data"))
+            .ok()
+            .and_then(|data| data.as_string())
+            .and_then(|data| serde_json::from_str(&data).ok());
+        match upload_progress {
+            Some(upload_progress) => upload_progress_handler(upload_progress),
+            None => crate::eprintln!("invalid upload progress event"),
+        }
+    })
+}
+
 #[cfg(feature = "serde")]
 fn down_msg_transporter_from_event<DMsg: DeserializeOwned>(
     event: JsValue,
@@ -63,7 +116,7 @@ fn down_msg_transporter_from_event<DMsg: DeserializeOwned>(
 
 fn connect(session_id: SessionId) -> ReconnectingEventSource {
     ReconnectingEventSource::new(
-        &format!("/_api/message_sse/{}", session_id),
+        &with_base_path(&format!("/_api/message_sse/{}", session_id)),
         Some(ReconnectingEventSourceOptions {
             withCredentials: false,
             max_retry_time: 5000,
@@ -13,6 +13,14 @@ extern "C" {
     pub fn log(input: &str);
 }
 
+// ------ warn ------
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console)]
+    pub fn warn(input: &str);
+}
+
 // ------ eprintln ------
 
 #[macro_export]
@@ -22,6 +30,19 @@ macro_rules! eprintln {
 
 #[wasm_bindgen]
 extern "C" {
-    #[wasm_bindgen(js_namespace = console)]
-    pub fn error(input: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = error)]
+    fn console_error(input: &str);
+}
+
+/// Writes to the browser console and ships the message to Moon when `client_log` is enabled.
+pub fn error(input: &str) {
+    console_error(input);
+    #[cfg(feature = "connection")]
+    client_log::push(moonlight::ClientLogKind::ConsoleError, input);
+}
+
+/// Writes to the browser console only.
+#[cfg(feature = "panic_hook")]
+pub(crate) fn error_local(input: &str) {
+    console_error(input);
 }
@@ -12,9 +12,16 @@ pub use canvas::Canvas;
 pub mod checkbox;
 pub use checkbox::Checkbox;
 
+mod choice;
+pub use choice::ChoiceOption;
+pub(crate) use choice::{custom_choices_el, into_option_renderer, Choices};
+
 pub mod column;
 pub use column::Column;
 
+pub mod dialog;
+pub use dialog::Dialog;
+
 pub mod el;
 pub use el::El;
 
@@ -30,12 +37,28 @@ pub use label::Label;
 pub mod link;
 pub use link::{Link, NewTab};
 
+mod overlay;
+pub use overlay::Placement;
+pub(crate) use overlay::{AnchoredContent, ScrollLock};
+
 pub mod paragraph;
 pub use paragraph::Paragraph;
 
+pub mod popover;
+pub use popover::Popover;
+
+pub mod radio_group;
+pub use radio_group::RadioGroup;
+
 pub mod row;
 pub use row::Row;
 
+pub mod select;
+pub use select::Select;
+
+pub mod slider;
+pub use slider::{Slider, SliderValue};
+
 pub mod spacer;
 pub use spacer::Spacer;
 
@@ -54,6 +77,12 @@ pub use text_area::TextArea;
 pub mod text_input;
 pub use text_input::{InputType, Placeholder, TextInput};
 
+pub mod tooltip;
+pub use tooltip::Tooltip;
+
+pub mod virtual_list;
+pub use virtual_list::VirtualList;
+
 // --
 
 pub mod raw_el;
@@ -18,6 +18,9 @@ pub use pointer_event_aware::{
 mod touch_event_aware;
 pub use touch_event_aware::{TouchEventAware, TouchHandling};
 
+mod drag_and_drop;
+pub use drag_and_drop::{Draggable, DropTarget, SortableList};
+
 mod hookable_lifecycle;
 pub use hookable_lifecycle::HookableLifecycle;
 
@@ -41,3 +44,6 @@ pub use has_ids::HasIds;
 
 mod has_lang;
 pub use has_lang::HasLang;
+
+mod accessible;
+pub use accessible::{Accessible, Politeness, Role};
@@ -0,0 +1,262 @@
+use crate::*;
+use std::{cell::RefCell, rc::Rc};
+use strum::IntoStaticStr;
+
+// ------ Accessible ------
+
+/// ARIA roles, states and relations. See also `zoon::accessibility` for announcements and the audit.
+pub trait Accessible: RawElWrapper + Sized {
+    fn role(self, role: Role) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr("role", role.into()))
+    }
+
+    /// Accessible name when there is no visible label.
+    fn aria_label<'a>(self, label: impl IntoCowStr<'a>) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr("aria-label", &label.into_cow_str()))
+    }
+
+    fn aria_label_signal<'a>(
+        self,
+        label: impl Signal<Item = impl IntoOptionCowStr<'a>> + Unpin + 'static,
+    ) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr_signal("aria-label", label))
+    }
+
+    /// Ids of elements (see `HasIds::id`) that label this element.
+    fn labelled_by<'a>(self, ids: impl IntoIterator<Item = impl IntoCowStr<'a>>) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr("aria-labelledby", &join_ids(ids)))
+    }
+
+    /// Ids of elements (see `HasIds::id`) that describe this element, e.g. error messages.
+    fn described_by<'a>(self, ids: impl IntoIterator<Item = impl IntoCowStr<'a>>) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr("aria-describedby", &join_ids(ids)))
+    }
+
+    fn described_by_signal<'a>(
+        self,
+        ids: impl Signal<Item = impl IntoOptionCowStr<'a>> + Unpin + 'static,
+    ) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr_signal("aria-describedby", ids))
+    }
+
+    /// Ids of elements (see `HasIds::id`) whose content or presence is controlled by this element.
+    fn controls<'a>(self, ids: impl IntoIterator<Item = impl IntoCowStr<'a>>) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr("aria-controls", &join_ids(ids)))
+    }
+
+    fn expanded(self, expanded: bool) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr("aria-expanded", bool_str(expanded)))
+    }
+
+    fn expanded_signal(self, expanded: impl Signal<Item = bool> + Unpin + 'static) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr_signal("aria-expanded", expanded.map(bool_str)))
+    }
+
+    fn pressed(self, pressed: bool) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr("aria-pressed", bool_str(pressed)))
+    }
+
+    fn pressed_signal(self, pressed: impl Signal<Item = bool> + Unpin + 'static) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr_signal("aria-pressed", pressed.map(bool_str)))
+    }
+
+    fn selected(self, selected: bool) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr("aria-selected", bool_str(selected)))
+    }
+
+    fn selected_signal(self, selected: impl Signal<Item = bool> + Unpin + 'static) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr_signal("aria-selected", selected.map(bool_str)))
+    }
+
+    fn disabled(self, disabled: bool) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr("aria-disabled", bool_str(disabled)))
+    }
+
+    fn disabled_signal(self, disabled: impl Signal<Item = bool> + Unpin + 'static) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr_signal("aria-disabled", disabled.map(bool_str)))
+    }
+
+    fn invalid(self, invalid: bool) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr("aria-invalid", bool_str(invalid)))
+    }
+
+    fn invalid_signal(self, invalid: impl Signal<Item = bool> + Unpin + 'static) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr_signal("aria-invalid", invalid.map(bool_str)))
+    }
+
+    fn busy_signal(self, busy: impl Signal<Item = bool> + Unpin + 'static) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr_signal("aria-busy", busy.map(bool_str)))
+    }
+
+    /// Hides the element from assistive technologies, e.g. decorative icons.
+    fn aria_hidden(self, hidden: bool) -> Self {
+        if hidden {
+            return self.update_raw_el(|raw_el| raw_el.attr("aria-hidden", "true"));
+        }
+        self
+    }
+
+    fn aria_hidden_signal(self, hidden: impl Signal<Item = bool> + Unpin + 'static) -> Self {
+        self.update_raw_el(|raw_el| raw_el.attr_signal("aria-hidden", hidden.map_true(|| "true")))
+    }
+
+    /// Changes of the element content are announced by screen readers.
+    fn live_region(self, politeness: Politeness) -> Self {
+        self.update_raw_el(|raw_el| {
+            raw_el
+                .attr("aria-live", politeness.into())
+                .attr("aria-atomic", "true")
+        })
+    }
+
+    /// `Tab` and `Shift + Tab` cycle through the focusable descendants while `trap` is `true`.
+    fn focus_trap_signal(self, trap: impl Signal<Item = bool> + Unpin + 'static) -> Self
+    where
+        <Self::RawEl as RawEl>::DomElement: AsRef<web_sys::Element>,
+    {
+        let trapped = Mutable::new(false);
+        let task_handle = Task::start_droppable(
+            trap.for_each_sync(clone!((trapped) move |trap| trapped.set_neq(trap))),
+        );
+        self.update_raw_el(|raw_el| {
+            let container = raw_el.dom_element();
+            raw_el
+                .event_handler(move |event: events::KeyDown| {
+                    if trapped.get() && event.key() == "Tab" {
+                        let container: &web_sys::Element = container.as_ref();
+                        if accessibility::cycle_focus(container, event.shift_key()) {
+                            event.prevent_default();
+                        }
+                    }
+                })
+                .after_remove(move |_| drop(task_handle))
+        })
+    }
+
+    /// Focuses the first focusable descendant on insert and the previously focused element on remove.
+    fn restore_focus(self) -> Self
+    where
+        <Self::RawEl as RawEl>::DomElement: AsRef<web_sys::Element>,
+    {
+        let previously_focused = Rc::new(RefCell::new(None));
+        let previously_focused_on_remove = Rc::clone(&previously_focused);
+        self.update_raw_el(|raw_el| {
+            raw_el
+                .after_insert(move |container| {
+                    *previously_focused.borrow_mut() = document()
+                        .active_element()
+                        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+                    accessibility::focus_first(container.as_ref());
+                })
+                .after_remove(move |_| {
+                    if let Some(element) = previously_focused_on_remove.borrow_mut().take() {
+                        let _ = element.focus();
+                    }
+                })
+        })
+    }
+}
+
+// ------ Role ------
+
+/// [WAI-ARIA roles](https://www.w3.org/TR/wai-aria-1.2/#role_definitions).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoStaticStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum Role {
+    Alert,
+    AlertDialog,
+    Application,
+    Article,
+    Banner,
+    Button,
+    Cell,
+    Checkbox,
+    ColumnHeader,
+    ComboBox,
+    Complementary,
+    ContentInfo,
+    Definition,
+    Dialog,
+    Document,
+    Feed,
+    Figure,
+    Form,
+    Grid,
+    GridCell,
+    Group,
+    Heading,
+    Img,
+    Link,
+    List,
+    ListBox,
+    ListItem,
+    Log,
+    Main,
+    Marquee,
+    Math,
+    Menu,
+    MenuBar,
+    MenuItem,
+    MenuItemCheckbox,
+    MenuItemRadio,
+    Navigation,
+    None,
+    Note,
+    Option,
+    Presentation,
+    ProgressBar,
+    Radio,
+    RadioGroup,
+    Region,
+    Row,
+    RowGroup,
+    RowHeader,
+    ScrollBar,
+    Search,
+    SearchBox,
+    Separator,
+    Slider,
+    SpinButton,
+    Status,
+    Switch,
+    Tab,
+    Table,
+    TabList,
+    TabPanel,
+    Term,
+    TextBox,
+    Timer,
+    Toolbar,
+    Tooltip,
+    Tree,
+    TreeGrid,
+    TreeItem,
+}
+
+// ------ Politeness ------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoStaticStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum Politeness {
+    /// Announced when the user is idle.
+    Polite,
+    /// Interrupts the current announcement.
+    Assertive,
+}
+
+// ------ helpers ------
+
+fn join_ids<'a>(ids: impl IntoIterator<Item = impl IntoCowStr<'a>>) -> String {
+    ids.into_iter()
+        .map(|id| id.into_cow_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn bool_str(value: bool) -> &'static str {
+    if value {
+        "true"
+    } else {
+        "false"
+    }
+}
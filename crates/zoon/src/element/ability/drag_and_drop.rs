@@ -0,0 +1,286 @@
+use crate::*;
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+// Marks drags started by `Draggable`, other drags (files, text or links) don't have it.
+const PAYLOAD_FORMAT: &str = "application/x-moonzoon-payload";
+
+thread_local! {
+    static DRAG_PAYLOAD: RefCell<Option<Box<dyn Any>>> = RefCell::new(None);
+}
+
+// ------ Draggable ------
+
+/// HTML drag and drop source. See `DropTarget` for the other side.
+pub trait Draggable: RawElWrapper + Sized {
+    /// Makes the element draggable. `payload` is called when the dragging starts
+    /// and its value is passed to `DropTarget::on_drop` of the element where it's dropped.
+    fn drag_payload<T: 'static>(self, payload: impl Fn() -> T + 'static) -> Self {
+        self.update_raw_el(|raw_el| {
+            raw_el
+                .attr("draggable", "true")
+                .event_handler(move |event: events::DragStart| {
+                    // Parent `Draggable`s would replace the payload.
+                    event.stop_propagation();
+                    if let Some(data_transfer) = event.data_transfer() {
+                        let _ = data_transfer.set_data(PAYLOAD_FORMAT, "");
+                        data_transfer.set_effect_allowed("move");
+                    }
+                    let payload: Box<dyn Any> = Box::new(payload());
+                    DRAG_PAYLOAD.with(|drag_payload| *drag_payload.borrow_mut() = Some(payload));
+                })
+                .event_handler(|_: events::DragEnd| {
+                    DRAG_PAYLOAD.with(|drag_payload| drag_payload.borrow_mut().take());
+                })
+        })
+    }
+
+    /// Element displayed under the cursor instead of the browser's snapshot of the dragged element.
+    fn drag_preview<'a, IE: IntoElement<'a> + 'a>(
+        self,
+        preview: impl Fn() -> IE + 'static,
+    ) -> Self {
+        self.update_raw_el(|raw_el| {
+            raw_el.event_handler(move |event: events::DragStart| {
+                let Some(data_transfer) = event.data_transfer() else {
+                    return;
+                };
+                // `setDragImage` needs a rendered element, the browser takes its snapshot immediately.
+                let container = document().create_element("div").unwrap_throw();
+                container
+                    .set_attribute(
+                        "style",
+                        "position: fixed; top: -10000px; left: -10000px; pointer-events: none;",
+                    )
+                    .unwrap_throw();
+                dominator::body().append_child(&container).unwrap_throw();
+                let dom_handle = dominator::append_dom(
+                    &container,
+                    preview().into_element().into_raw().into_dom(),
+                );
+                data_transfer.set_drag_image(&container, 0, 0);
+                Task::start(async move {
+                    Timer::sleep(0).await;
+                    dom_handle.discard();
+                    container.remove();
+                });
+            })
+        })
+    }
+
+    fn on_dragging_change(self, handler: impl FnMut(bool) + 'static) -> Self {
+        let handler = Rc::new(RefCell::new(handler));
+        self.update_raw_el(|raw_el| {
+            raw_el
+                .event_handler({
+                    let handler = Rc::clone(&handler);
+                    move |_: events::DragStart| handler.borrow_mut()(true)
+                })
+                .event_handler(move |_: events::DragEnd| handler.borrow_mut()(false))
+        })
+    }
+
+    /// Item of `list` that can be moved to the position of another item by dragging.
+    /// `index` is usually taken from `SignalVecExt::enumerate`.
+    fn sortable_item<T: 'static>(
+        self,
+        list: &SortableList<T>,
+        index: ReadOnlyMutable<Option<usize>>,
+    ) -> Self {
+        let list_id = Rc::clone(&list.id);
+        let drag_index = index.clone();
+        let items = list.items.clone();
+        self.drag_payload(move || SortablePayload {
+            list_id: Rc::clone(&list_id),
+            index: drag_index.get(),
+        })
+        .update_raw_el(|raw_el| {
+            let list_id = Rc::clone(&list.id);
+            on_payload_drop(
+                raw_el,
+                move |payload: &SortablePayload| Rc::ptr_eq(&payload.list_id, &list_id),
+                move |payload: SortablePayload| {
+                    if let (Some(old_index), Some(new_index)) = (payload.index, index.get()) {
+                        if old_index != new_index {
+                            items.lock_mut().move_from_to(old_index, new_index);
+                        }
+                    }
+                },
+            )
+        })
+    }
+}
+
+// ------ DropTarget ------
+
+/// HTML drag and drop target for `Draggable` payloads and files.
+pub trait DropTarget: RawElWrapper + Sized {
+    /// `handler` is called when a `Draggable` with a payload of the type `T` is dropped on the element.
+    /// Other payloads are not accepted.
+    fn on_drop<T: 'static>(self, handler: impl FnMut(T) + 'static) -> Self {
+        self.update_raw_el(|raw_el| on_payload_drop(raw_el, |_: &T| true, handler))
+    }
+
+    /// `handler` is called when files are dropped on the element, e.g. from the file manager.
+    fn on_files_drop(self, mut handler: impl FnMut(Vec<web_sys::File>) + 'static) -> Self {
+        self.update_raw_el(|raw_el| {
+            raw_el
+                .event_handler_with_options(
+                    EventOptions::new().preventable(),
+                    |event: events::DragOver| {
+                        let Some(data_transfer) = event.data_transfer() else {
+                            return;
+                        };
+                        if data_transfer.types().includes(&"Files".into(), 0) {
+                            event.prevent_default();
+                            data_transfer.set_drop_effect("copy");
+                        }
+                    },
+                )
+                .event_handler_with_options(
+                    EventOptions::new().preventable(),
+                    move |event: events::Drop| {
+                        let Some(file_list) = event.data_transfer().and_then(|data| data.files())
+                        else {
+                            return;
+                        };
+                        let files = (0..file_list.length())
+                            .filter_map(|index| file_list.get(index))
+                            .collect::<Vec<_>>();
+                        if not(files.is_empty()) {
+                            event.prevent_default();
+                            handler(files);
+                        }
+                    },
+                )
+        })
+    }
+
+    /// `true` while something is dragged over the element or its descendants.
+    fn on_drag_hovered_change(self, handler: impl FnMut(bool) + 'static) -> Self {
+        // `dragenter` and `dragleave` are fired also when the cursor moves between descendants.
+        let entered_elements = Rc::new(RefCell::new(0_u32));
+        let handler = Rc::new(RefCell::new(handler));
+        self.update_raw_el(|raw_el| {
+            raw_el
+                .event_handler(
+                    clone!((entered_elements, handler) move |_: events::DragEnter| {
+                        let mut entered_elements = entered_elements.borrow_mut();
+                        *entered_elements += 1;
+                        if *entered_elements == 1 {
+                            handler.borrow_mut()(true);
+                        }
+                    }),
+                )
+                .event_handler(
+                    clone!((entered_elements, handler) move |_: events::DragLeave| {
+                        let mut entered_elements = entered_elements.borrow_mut();
+                        *entered_elements = entered_elements.saturating_sub(1);
+                        if *entered_elements == 0 {
+                            handler.borrow_mut()(false);
+                        }
+                    }),
+                )
+                .event_handler(move |_: events::Drop| {
+                    *entered_elements.borrow_mut() = 0;
+                    handler.borrow_mut()(false);
+                })
+        })
+    }
+}
+
+// ------ SortableList ------
+
+/// Items reordered by dragging their elements, see `Draggable::sortable_item`.
+pub struct SortableList<T> {
+    items: MutableVec<T>,
+    // Items can't be moved between lists.
+    id: Rc<()>,
+}
+
+impl<T> SortableList<T> {
+    pub fn new(items: MutableVec<T>) -> Self {
+        Self {
+            items,
+            id: Rc::new(()),
+        }
+    }
+}
+
+impl<T> Clone for SortableList<T> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            id: Rc::clone(&self.id),
+        }
+    }
+}
+
+struct SortablePayload {
+    list_id: Rc<()>,
+    index: Option<usize>,
+}
+
+// ------ helpers ------
+
+fn on_payload_drop<RE: RawEl, T: 'static>(
+    raw_el: RE,
+    accepts: impl Fn(&T) -> bool + 'static,
+    mut handler: impl FnMut(T) + 'static,
+) -> RE {
+    let accepts = Rc::new(accepts);
+    raw_el
+        .event_handler_with_options(
+            EventOptions::new().preventable(),
+            clone!((accepts) move |event: events::DragOver| {
+                let data_transfer = event.data_transfer();
+                if has_payload(data_transfer.as_ref(), &*accepts) {
+                    event.prevent_default();
+                    if let Some(data_transfer) = data_transfer {
+                        data_transfer.set_drop_effect("move");
+                    }
+                }
+            }),
+        )
+        .event_handler_with_options(
+            EventOptions::new().preventable(),
+            move |event: events::Drop| {
+                if let Some(payload) = take_payload(event.data_transfer().as_ref(), &*accepts) {
+                    event.prevent_default();
+                    handler(payload);
+                }
+            },
+        )
+}
+
+/// A leftover payload (e.g. of a drag canceled outside of the window) is ignored
+/// when the dragged data come from another document or application.
+fn has_payload<T: 'static>(
+    data_transfer: Option<&web_sys::DataTransfer>,
+    accepts: &dyn Fn(&T) -> bool,
+) -> bool {
+    let from_drag_payload = data_transfer
+        .is_some_and(|data_transfer| data_transfer.types().includes(&PAYLOAD_FORMAT.into(), 0));
+    if not(from_drag_payload) {
+        return false;
+    }
+    DRAG_PAYLOAD.with(|drag_payload| {
+        drag_payload
+            .borrow()
+            .as_ref()
+            .and_then(|payload| payload.downcast_ref::<T>())
+            .is_some_and(accepts)
+    })
+}
+
+fn take_payload<T: 'static>(
+    data_transfer: Option<&web_sys::DataTransfer>,
+    accepts: &dyn Fn(&T) -> bool,
+) -> Option<T> {
+    if not(has_payload(data_transfer, accepts)) {
+        return None;
+    }
+    DRAG_PAYLOAD.with(|drag_payload| {
+        let payload = drag_payload.borrow_mut().take()?;
+        payload.downcast::<T>().ok().map(|payload| *payload)
+    })
+}
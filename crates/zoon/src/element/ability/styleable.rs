@@ -64,4 +64,56 @@ pub trait Styleable<'a>: RawElWrapper + Sized {
         }
         self
     }
+
+    /// Apply the style only while the media query matches.
+    /// # Example
+    /// ```no_run
+    /// use zoon::*;
+    ///
+    /// let sidebar = Column::new()
+    ///     .s(Width::exact(80))
+    ///     .s_media(Breakpoint::Large.up(), Width::exact(240))
+    ///     .s_media(MediaQuery::portrait(), Padding::all(4))
+    ///     .item("Sidebar");
+    /// ```
+    fn s_media<S: Style<'a>>(self, query: MediaQuery, style: impl Into<Option<S>>) -> Self {
+        self.s_conditional(style, |group| group.media(query.clone()))
+    }
+
+    /// Apply the style only while the nearest ancestor with the [Container] style matches the query.
+    /// # Example
+    /// ```no_run
+    /// use zoon::*;
+    ///
+    /// let card = El::new()
+    ///     .s(Container::new())
+    ///     .child(
+    ///         Row::new()
+    ///             .s(Gap::both(8))
+    ///             .s_container(ContainerQuery::max_width(400), Font::new().size(12))
+    ///             .items(["Title", "Description"]),
+    ///     );
+    /// ```
+    fn s_container<S: Style<'a>>(self, query: ContainerQuery, style: impl Into<Option<S>>) -> Self {
+        self.s_conditional(style, |group| group.container(query.clone()))
+    }
+
+    #[doc(hidden)]
+    fn s_conditional<S: Style<'a>>(
+        self,
+        style: impl Into<Option<S>>,
+        condition: impl Fn(StyleGroup<'a>) -> StyleGroup<'a>,
+    ) -> Self {
+        if let Some(style) = style.into() {
+            return self.update_raw_el(|mut raw_el| {
+                let mut groups = StyleGroups::default();
+                style.move_to_groups(&mut groups);
+                for group in groups.into_groups() {
+                    raw_el = raw_el.style_group(condition(group));
+                }
+                raw_el
+            });
+        }
+        self
+    }
 }
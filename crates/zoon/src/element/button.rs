@@ -180,11 +180,13 @@ impl<LabelFlag, OnPressFlag, RE: RawEl> Focusable for Button<LabelFlag, OnPressF
 impl<LabelFlag, OnPressFlag, RE: RawEl> MouseEventAware for Button<LabelFlag, OnPressFlag, RE> {}
 impl<LabelFlag, OnPressFlag, RE: RawEl> PointerEventAware for Button<LabelFlag, OnPressFlag, RE> {}
 impl<LabelFlag, OnPressFlag, RE: RawEl> TouchEventAware for Button<LabelFlag, OnPressFlag, RE> {}
+impl<LabelFlag, OnPressFlag, RE: RawEl> Draggable for Button<LabelFlag, OnPressFlag, RE> {}
 impl<LabelFlag, OnPressFlag, RE: RawEl> AddNearbyElement<'_>
     for Button<LabelFlag, OnPressFlag, RE>
 {
 }
 impl<LabelFlag, OnPressFlag, RE: RawEl> HasIds for Button<LabelFlag, OnPressFlag, RE> {}
+impl<LabelFlag, OnPressFlag, RE: RawEl> Accessible for Button<LabelFlag, OnPressFlag, RE> {}
 
 // ------ ------
 //  Attributes
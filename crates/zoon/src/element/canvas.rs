@@ -43,6 +43,7 @@ impl<WidthFlag, HeightFlag, RE: RawEl> PointerEventAware for Canvas<WidthFlag, H
 impl<WidthFlag, HeightFlag, RE: RawEl> TouchEventAware for Canvas<WidthFlag, HeightFlag, RE> {}
 impl<WidthFlag, HeightFlag, RE: RawEl> AddNearbyElement<'_> for Canvas<WidthFlag, HeightFlag, RE> {}
 impl<WidthFlag, HeightFlag, RE: RawEl> HasIds for Canvas<WidthFlag, HeightFlag, RE> {}
+impl<WidthFlag, HeightFlag, RE: RawEl> Accessible for Canvas<WidthFlag, HeightFlag, RE> {}
 
 // ------ ------
 //  Attributes
@@ -128,6 +128,10 @@ impl<IdFlag, OnChangeFlag, LabelFlag, IconFlag, CheckedFlag, RE: RawEl> HasIds
     for Checkbox<IdFlag, OnChangeFlag, LabelFlag, IconFlag, CheckedFlag, RE>
 {
 }
+impl<IdFlag, OnChangeFlag, LabelFlag, IconFlag, CheckedFlag, RE: RawEl> Accessible
+    for Checkbox<IdFlag, OnChangeFlag, LabelFlag, IconFlag, CheckedFlag, RE>
+{
+}
 
 // ------ ------
 //  Attributes
@@ -198,6 +202,26 @@ impl<'a, IdFlag, OnChangeFlag, LabelFlag, IconFlag, CheckedFlag, RE: RawEl>
         self.into_type()
     }
 
+    /// Binds the checked state and changes to the field, touches it on blur and sets `aria-invalid`.
+    pub fn field(
+        self,
+        field: &Field<bool>,
+    ) -> Checkbox<IdFlag, OnChangeFlagSet, LabelFlag, IconFlag, CheckedFlagSet, RE>
+    where
+        OnChangeFlag: FlagNotSet,
+        CheckedFlag: FlagNotSet,
+    {
+        let aria_invalid = field.aria_invalid_signal();
+        let touched_field = field.clone();
+        self.checked_signal(field.value_signal())
+            .on_change(clone!((field) move |checked| field.set(checked)))
+            .update_raw_el(|raw_el| {
+                raw_el
+                    .attr_signal("aria-invalid", aria_invalid)
+                    .event_handler(move |_: events::Blur| touched_field.touch())
+            })
+    }
+
     pub fn label_hidden(
         mut self,
         label: impl IntoCowStr<'a>,
@@ -0,0 +1,266 @@
+use crate::*;
+use std::rc::Rc;
+
+// ------ ChoiceOption ------
+
+/// An option of `Select` or `RadioGroup`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChoiceOption<T> {
+    pub value: T,
+    pub label: String,
+    pub disabled: bool,
+}
+
+impl<T> ChoiceOption<T> {
+    pub fn new<'a>(value: T, label: impl IntoCowStr<'a>) -> Self {
+        Self {
+            value,
+            label: label.into_cow_str().into_owned(),
+            disabled: false,
+        }
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+// ------ ChoiceState ------
+
+#[derive(Clone, PartialEq)]
+enum ChoiceState<T> {
+    NotSet,
+    Value(Option<T>),
+    FirstValue(Option<T>),
+}
+
+impl<T> ChoiceState<T> {
+    fn selected(&self) -> Option<&T> {
+        match self {
+            Self::NotSet => None,
+            Self::FirstValue(selected) | Self::Value(selected) => selected.as_ref(),
+        }
+    }
+}
+
+// ------ Choices ------
+
+/// Options and the selected value shared by `Select` and `RadioGroup`.
+pub(crate) struct Choices<T> {
+    state: Mutable<ChoiceState<T>>,
+    pub(crate) options: MutableVec<ChoiceOption<T>>,
+}
+
+impl<T> Clone for Choices<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            options: self.options.clone(),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Choices<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Mutable::new(ChoiceState::NotSet),
+            options: MutableVec::new(),
+        }
+    }
+
+    pub(crate) fn set_selected(&self, selected: Option<T>) {
+        let mut state = self.state.lock_mut();
+        let new_state = match &*state {
+            ChoiceState::NotSet => ChoiceState::FirstValue(selected),
+            ChoiceState::FirstValue(_) | ChoiceState::Value(_) => ChoiceState::Value(selected),
+        };
+        if *state != new_state {
+            *state = new_state;
+        }
+    }
+
+    pub(crate) fn selected_index(&self) -> Option<usize> {
+        let state = self.state.lock_ref();
+        let selected = state.selected()?;
+        self.options
+            .lock_ref()
+            .iter()
+            .position(|option| &option.value == selected)
+    }
+
+    pub(crate) fn select_index(&self, index: usize) {
+        let option = self.options.lock_ref().get(index).cloned();
+        if let Some(option) = option.filter(|option| not(option.disabled)) {
+            self.set_selected(Some(option.value));
+        }
+    }
+
+    pub(crate) fn is_selected_signal(&self, value: T) -> impl Signal<Item = bool> {
+        self.state
+            .signal_ref(move |state| state.selected() == Some(&value))
+            .dedupe()
+    }
+
+    /// `true` also when the selected value isn't among the options.
+    pub(crate) fn is_nothing_selected_signal(&self) -> impl Signal<Item = bool> {
+        let options = self.options.signal_vec_cloned().to_signal_cloned();
+        let is_nothing_selected = map_ref! {
+            let state = self.state.signal_cloned(),
+            let options = options =>
+            not(state
+                .selected()
+                .is_some_and(|selected| options.iter().any(|option| &option.value == selected)))
+        };
+        is_nothing_selected.dedupe()
+    }
+
+    pub(crate) fn set_options(&self, options: impl IntoIterator<Item = ChoiceOption<T>>) {
+        self.options
+            .lock_mut()
+            .replace_cloned(options.into_iter().collect());
+    }
+
+    pub(crate) fn options_task(
+        &self,
+        options: impl SignalVec<Item = ChoiceOption<T>> + 'static,
+    ) -> TaskHandle {
+        let choice_options = self.options.clone();
+        Task::start_droppable(options.for_each(move |diff| {
+            MutableVecLockMut::apply_vec_diff(&mut choice_options.lock_mut(), diff);
+            async {}
+        }))
+    }
+
+    pub(crate) fn selected_task(
+        &self,
+        selected: impl Signal<Item = Option<T>> + Unpin + 'static,
+    ) -> TaskHandle {
+        let choices = self.clone();
+        Task::start_droppable(
+            selected.for_each_sync(move |selected| choices.set_selected(selected)),
+        )
+    }
+
+    pub(crate) fn on_change_task(
+        &self,
+        mut on_change: impl FnMut(Option<T>) + 'static,
+    ) -> TaskHandle {
+        Task::start_droppable(self.state.signal_cloned().for_each_sync(move |state| {
+            if let ChoiceState::Value(selected) = state {
+                on_change(selected);
+            }
+        }))
+    }
+
+    /// Moves the selection with arrow keys, `Home` and `End`, skipping disabled options.
+    /// Returns `false` when the key isn't handled.
+    pub(crate) fn navigate(&self, key: &str) -> bool {
+        let enabled_indices = self
+            .options
+            .lock_ref()
+            .iter()
+            .enumerate()
+            .filter(|(_, option)| not(option.disabled))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let selected_index = self.selected_index();
+        let position = selected_index.and_then(|selected_index| {
+            enabled_indices
+                .iter()
+                .position(|index| *index == selected_index)
+        });
+
+        let new_position = match key {
+            "ArrowDown" | "ArrowRight" => match position {
+                Some(position) => (position + 1) % enabled_indices.len(),
+                None => 0,
+            },
+            "ArrowUp" | "ArrowLeft" => match position {
+                Some(0) | None => enabled_indices.len().saturating_sub(1),
+                Some(position) => position - 1,
+            },
+            "Home" => 0,
+            "End" => enabled_indices.len().saturating_sub(1),
+            _ => return false,
+        };
+        if let Some(index) = enabled_indices.get(new_position) {
+            self.select_index(*index);
+        }
+        true
+    }
+}
+
+// ------ custom options ------
+
+pub(crate) type OptionRenderer<T> = Rc<dyn Fn(&ChoiceOption<T>, Mutable<bool>) -> RawElOrText>;
+
+pub(crate) fn into_option_renderer<'a, T, IE: IntoElement<'a> + 'a>(
+    renderer: impl Fn(&ChoiceOption<T>, Mutable<bool>) -> IE + 'static,
+) -> OptionRenderer<T> {
+    Rc::new(move |option, selected| renderer(option, selected).into_element().into_raw())
+}
+
+/// Keyboard-navigable container (`role` is e.g. `listbox` or `radiogroup`) with rendered options.
+pub(crate) fn custom_choices_el<T: Clone + PartialEq + 'static>(
+    choices: &Choices<T>,
+    role: &'static str,
+    option_role: &'static str,
+    option_renderer: OptionRenderer<T>,
+) -> RawHtmlEl<web_sys::HtmlDivElement> {
+    let navigator = choices.clone();
+    let option_choices = choices.clone();
+    RawHtmlEl::<web_sys::HtmlDivElement>::new("div")
+        .attr("role", role)
+        .attr("tabindex", "0")
+        .style("display", "inline-flex")
+        .style("flex-direction", "column")
+        .event_handler(move |event: events::KeyDown| {
+            if navigator.navigate(&event.key()) {
+                event.prevent_default();
+            }
+        })
+        .children_signal_vec(choices.options.signal_vec_cloned().map(move |option| {
+            custom_option_el(&option_choices, option, option_role, &option_renderer)
+        }))
+}
+
+fn custom_option_el<T: Clone + PartialEq + 'static>(
+    choices: &Choices<T>,
+    option: ChoiceOption<T>,
+    role: &'static str,
+    option_renderer: &OptionRenderer<T>,
+) -> RawHtmlEl<web_sys::HtmlDivElement> {
+    let selected = Mutable::new(false);
+    let content = option_renderer(&option, selected.clone());
+    let selected_updater = Task::start_droppable(
+        choices
+            .is_selected_signal(option.value.clone())
+            .for_each_sync(clone!((selected) move |is_selected| selected.set_neq(is_selected))),
+    );
+    // `aria-checked` for `radio`, `aria-selected` for `option`.
+    let selected_attr = if role == "radio" {
+        "aria-checked"
+    } else {
+        "aria-selected"
+    };
+    let choices = choices.clone();
+    let ChoiceOption {
+        value, disabled, ..
+    } = option;
+    RawHtmlEl::<web_sys::HtmlDivElement>::new("div")
+        .attr("role", role)
+        .attr_signal(
+            selected_attr,
+            selected.signal().map(|selected| selected.to_string()),
+        )
+        .attr("aria-disabled", &disabled.to_string())
+        .style("cursor", if disabled { "not-allowed" } else { "pointer" })
+        .event_handler(move |_: events::Click| {
+            if not(disabled) {
+                choices.set_selected(Some(value.clone()));
+            }
+        })
+        .child(content)
+        .after_remove(move |_| drop(selected_updater))
+}
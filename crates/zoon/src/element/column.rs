@@ -86,10 +86,13 @@ impl<EmptyFlag, RE: RawEl> KeyboardEventAware for Column<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> MouseEventAware for Column<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> PointerEventAware for Column<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> TouchEventAware for Column<EmptyFlag, RE> {}
+impl<EmptyFlag, RE: RawEl> Draggable for Column<EmptyFlag, RE> {}
+impl<EmptyFlag, RE: RawEl> DropTarget for Column<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> MutableViewport for Column<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> ResizableViewport for Column<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> AddNearbyElement<'_> for Column<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> HasIds for Column<EmptyFlag, RE> {}
+impl<EmptyFlag, RE: RawEl> Accessible for Column<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> SelectableTextContent for Column<EmptyFlag, RE> {}
 
 // ------ ------
@@ -0,0 +1,251 @@
+use crate::*;
+use std::{cell::RefCell, marker::PhantomData, rc::Rc};
+
+// ------ ------
+//    Element
+// ------ ------
+
+make_flags!(Child, Open, OnClose);
+
+pub struct Dialog<ChildFlag, OpenFlag, OnCloseFlag, RE: RawEl> {
+    raw_el: RE,
+    flags: PhantomData<(ChildFlag, OpenFlag, OnCloseFlag)>,
+}
+
+impl<OnCloseFlag, RE: RawEl> Element for Dialog<ChildFlagSet, OpenFlagSet, OnCloseFlag, RE> {}
+
+impl
+    Dialog<
+        ChildFlagNotSet,
+        OpenFlagNotSet,
+        OnCloseFlagNotSet,
+        RawHtmlEl<web_sys::HtmlDialogElement>,
+    >
+{
+    /// Modal native `<dialog>` displayed in the browser's top layer above all other elements.
+    /// The page behind it is inert and can't be scrolled, `Tab` cycles through the dialog content
+    /// and the previously focused element is focused again when the dialog is closed.
+    #[track_caller]
+    pub fn new() -> Self {
+        let raw_el = RawHtmlEl::<web_sys::HtmlDialogElement>::new("dialog")
+            .class("dialog")
+            .attr("aria-modal", "true")
+            .style("padding", "0")
+            .style("border", "none")
+            .style("flex-direction", "column");
+        let dom_element = raw_el.dom_element();
+        let raw_el = raw_el
+            .event_handler_with_options(
+                EventOptions::new().preventable(),
+                move |event: events::KeyDown| {
+                    if event.key() == "Tab"
+                        && accessibility::cycle_focus(dom_element.as_ref(), event.shift_key())
+                    {
+                        event.prevent_default();
+                    }
+                },
+            )
+            // `Escape` fires `cancel`, the dialog is closed by `open_signal`, see `on_close`.
+            .event_handler_with_options(
+                EventOptions::new().preventable(),
+                |event: events_extra::DialogCancel| event.prevent_default(),
+            );
+        Self {
+            raw_el,
+            flags: PhantomData,
+        }
+    }
+}
+
+impl<ChildFlag, OpenFlag, OnCloseFlag, RE: RawEl> RawElWrapper
+    for Dialog<ChildFlag, OpenFlag, OnCloseFlag, RE>
+{
+    type RawEl = RE;
+
+    fn raw_el_mut(&mut self) -> &mut Self::RawEl {
+        &mut self.raw_el
+    }
+}
+
+// ------ ------
+//   Abilities
+// ------ ------
+
+impl<ChildFlag, OpenFlag, OnCloseFlag, RE: RawEl> Styleable<'_>
+    for Dialog<ChildFlag, OpenFlag, OnCloseFlag, RE>
+{
+}
+impl<ChildFlag, OpenFlag, OnCloseFlag, RE: RawEl> KeyboardEventAware
+    for Dialog<ChildFlag, OpenFlag, OnCloseFlag, RE>
+{
+}
+impl<ChildFlag, OpenFlag, OnCloseFlag, RE: RawEl> MouseEventAware
+    for Dialog<ChildFlag, OpenFlag, OnCloseFlag, RE>
+{
+}
+impl<ChildFlag, OpenFlag, OnCloseFlag, RE: RawEl> PointerEventAware
+    for Dialog<ChildFlag, OpenFlag, OnCloseFlag, RE>
+{
+}
+impl<ChildFlag, OpenFlag, OnCloseFlag, RE: RawEl> TouchEventAware
+    for Dialog<ChildFlag, OpenFlag, OnCloseFlag, RE>
+{
+}
+impl<ChildFlag, OpenFlag, OnCloseFlag, RE: RawEl> MutableViewport
+    for Dialog<ChildFlag, OpenFlag, OnCloseFlag, RE>
+{
+}
+impl<ChildFlag, OpenFlag, OnCloseFlag, RE: RawEl> ResizableViewport
+    for Dialog<ChildFlag, OpenFlag, OnCloseFlag, RE>
+{
+}
+impl<ChildFlag, OpenFlag, OnCloseFlag, RE: RawEl> HasIds
+    for Dialog<ChildFlag, OpenFlag, OnCloseFlag, RE>
+{
+}
+impl<ChildFlag, OpenFlag, OnCloseFlag, RE: RawEl> Accessible
+    for Dialog<ChildFlag, OpenFlag, OnCloseFlag, RE>
+{
+}
+
+// ------ ------
+//  Attributes
+// ------ ------
+
+impl<'a, ChildFlag, OpenFlag, OnCloseFlag, RE: RawEl> Dialog<ChildFlag, OpenFlag, OnCloseFlag, RE> {
+    pub fn child(
+        mut self,
+        child: impl IntoOptionElement<'a> + 'a,
+    ) -> Dialog<ChildFlagSet, OpenFlag, OnCloseFlag, RE>
+    where
+        ChildFlag: FlagNotSet,
+    {
+        self.raw_el = self.raw_el.child(child);
+        self.into_type()
+    }
+
+    pub fn child_signal(
+        mut self,
+        child: impl Signal<Item = impl IntoOptionElement<'a>> + Unpin + 'static,
+    ) -> Dialog<ChildFlagSet, OpenFlag, OnCloseFlag, RE>
+    where
+        ChildFlag: FlagNotSet,
+    {
+        self.raw_el = self.raw_el.child_signal(child);
+        self.into_type()
+    }
+
+    pub fn open_signal(
+        mut self,
+        open: impl Signal<Item = bool> + Unpin + 'static,
+    ) -> Dialog<ChildFlag, OpenFlagSet, OnCloseFlag, RE>
+    where
+        OpenFlag: FlagNotSet,
+        RE::DomElement: AsRef<web_sys::HtmlDialogElement>,
+    {
+        let modal = Rc::new(RefCell::new(None::<Modal>));
+        let opener = Rc::new(RefCell::new(None));
+        self.raw_el = self
+            .raw_el
+            .after_insert(clone!((modal, opener) move |dialog| {
+                // `showModal` works only on dialogs in the document.
+                let dialog: web_sys::HtmlDialogElement = dialog.as_ref().clone();
+                *opener.borrow_mut() = Some(Task::start_droppable(open.for_each_sync(
+                    move |open| {
+                        if open && not(dialog.open()) {
+                            let previously_focused = document()
+                                .active_element()
+                                .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+                            if dialog.show_modal().is_ok() {
+                                dialog.style().set_property("display", "flex").unwrap_throw();
+                                *modal.borrow_mut() = Some(Modal {
+                                    previously_focused,
+                                    _scroll_lock: ScrollLock::new(),
+                                });
+                            }
+                        } else if not(open) && dialog.open() {
+                            dialog.close();
+                            dialog.style().remove_property("display").unwrap_throw();
+                            modal.borrow_mut().take();
+                        }
+                    },
+                )));
+            }))
+            // Browsers may close the dialog without `open_signal`,
+            // e.g. by `<form method="dialog">` or by repeated `Escape`.
+            .event_handler(clone!((modal) move |event: events_extra::DialogClose| {
+                if let Some(dialog) = event.dyn_target::<web_sys::HtmlDialogElement>() {
+                    dialog.style().remove_property("display").unwrap_throw();
+                }
+                modal.borrow_mut().take();
+            }))
+            .after_remove(move |_| {
+                opener.borrow_mut().take();
+                modal.borrow_mut().take();
+            });
+        self.into_type()
+    }
+
+    /// Called on `Escape`, on click outside the dialog or when the browser closes the dialog
+    /// (e.g. by `<form method="dialog">`). Set `open_signal` to `false` to close it.
+    /// It may be called again when the dialog has been closed by `open_signal`.
+    pub fn on_close(
+        mut self,
+        on_close: impl FnMut() + 'static,
+    ) -> Dialog<ChildFlag, OpenFlag, OnCloseFlagSet, RE>
+    where
+        OnCloseFlag: FlagNotSet,
+        RE::DomElement: AsRef<web_sys::HtmlDialogElement>,
+    {
+        let on_close = Rc::new(RefCell::new(on_close));
+        let dom_element = self.raw_el.dom_element();
+        self.raw_el = self
+            .raw_el
+            .event_handler(clone!((on_close) move |_: events_extra::DialogCancel| {
+                on_close.borrow_mut()();
+            }))
+            .event_handler(clone!((on_close) move |_: events_extra::DialogClose| {
+                on_close.borrow_mut()();
+            }))
+            .event_handler(move |event: events::Click| {
+                // Clicks on the backdrop target the dialog but they are outside of its rectangle.
+                let dialog: &web_sys::HtmlDialogElement = dom_element.as_ref();
+                let dialog_target: &web_sys::EventTarget = dialog.as_ref();
+                let target_is_dialog = event.target().as_ref() == Some(dialog_target);
+                let rect = dialog.get_bounding_client_rect();
+                let (x, y) = (f64::from(event.x()), f64::from(event.y()));
+                let outside =
+                    x < rect.left() || x > rect.right() || y < rect.top() || y > rect.bottom();
+                if target_is_dialog && outside {
+                    on_close.borrow_mut()();
+                }
+            });
+        self.into_type()
+    }
+
+    fn into_type<NewChildFlag, NewOpenFlag, NewOnCloseFlag>(
+        self,
+    ) -> Dialog<NewChildFlag, NewOpenFlag, NewOnCloseFlag, RE> {
+        Dialog {
+            raw_el: self.raw_el,
+            flags: PhantomData,
+        }
+    }
+}
+
+// ------ ------
+//     Extra
+// ------ ------
+
+struct Modal {
+    previously_focused: Option<web_sys::HtmlElement>,
+    _scroll_lock: ScrollLock,
+}
+
+impl Drop for Modal {
+    fn drop(&mut self) {
+        if let Some(element) = self.previously_focused.take() {
+            let _ = element.focus();
+        }
+    }
+}
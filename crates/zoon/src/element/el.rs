@@ -82,10 +82,13 @@ impl<ChildFlag, RE: RawEl> KeyboardEventAware for El<ChildFlag, RE> {}
 impl<ChildFlag, RE: RawEl> MouseEventAware for El<ChildFlag, RE> {}
 impl<ChildFlag, RE: RawEl> PointerEventAware for El<ChildFlag, RE> {}
 impl<ChildFlag, RE: RawEl> TouchEventAware for El<ChildFlag, RE> {}
+impl<ChildFlag, RE: RawEl> Draggable for El<ChildFlag, RE> {}
+impl<ChildFlag, RE: RawEl> DropTarget for El<ChildFlag, RE> {}
 impl<ChildFlag, RE: RawEl> MutableViewport for El<ChildFlag, RE> {}
 impl<ChildFlag, RE: RawEl> ResizableViewport for El<ChildFlag, RE> {}
 impl<ChildFlag, RE: RawEl> AddNearbyElement<'_> for El<ChildFlag, RE> {}
 impl<ChildFlag, RE: RawEl> HasIds for El<ChildFlag, RE> {}
+impl<ChildFlag, RE: RawEl> Accessible for El<ChildFlag, RE> {}
 impl<ChildFlag, RE: RawEl> SelectableTextContent for El<ChildFlag, RE> {}
 
 // ------ ------
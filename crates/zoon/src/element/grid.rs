@@ -81,10 +81,13 @@ impl<EmptyFlag, RE: RawEl> KeyboardEventAware for Grid<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> MouseEventAware for Grid<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> PointerEventAware for Grid<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> TouchEventAware for Grid<EmptyFlag, RE> {}
+impl<EmptyFlag, RE: RawEl> Draggable for Grid<EmptyFlag, RE> {}
+impl<EmptyFlag, RE: RawEl> DropTarget for Grid<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> MutableViewport for Grid<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> ResizableViewport for Grid<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> AddNearbyElement<'_> for Grid<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> HasIds for Grid<EmptyFlag, RE> {}
+impl<EmptyFlag, RE: RawEl> Accessible for Grid<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> SelectableTextContent for Grid<EmptyFlag, RE> {}
 
 // ------ ------
@@ -47,11 +47,13 @@ impl<UrlFlag, DescriptionFlag, RE: RawEl> PointerEventAware
 {
 }
 impl<UrlFlag, DescriptionFlag, RE: RawEl> TouchEventAware for Image<UrlFlag, DescriptionFlag, RE> {}
+impl<UrlFlag, DescriptionFlag, RE: RawEl> Draggable for Image<UrlFlag, DescriptionFlag, RE> {}
 impl<UrlFlag, DescriptionFlag, RE: RawEl> AddNearbyElement<'_>
     for Image<UrlFlag, DescriptionFlag, RE>
 {
 }
 impl<UrlFlag, DescriptionFlag, RE: RawEl> HasIds for Image<UrlFlag, DescriptionFlag, RE> {}
+impl<UrlFlag, DescriptionFlag, RE: RawEl> Accessible for Image<UrlFlag, DescriptionFlag, RE> {}
 
 // ------ ------
 //  Attributes
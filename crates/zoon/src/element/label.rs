@@ -46,6 +46,7 @@ impl<LabelFlag, ForInputFlag, RE: RawEl> AddNearbyElement<'_>
 {
 }
 impl<LabelFlag, ForInputFlag, RE: RawEl> HasIds for Label<LabelFlag, ForInputFlag, RE> {}
+impl<LabelFlag, ForInputFlag, RE: RawEl> Accessible for Label<LabelFlag, ForInputFlag, RE> {}
 impl<LabelFlag, ForInputFlag, RE: RawEl> SelectableTextContent
     for Label<LabelFlag, ForInputFlag, RE>
 {
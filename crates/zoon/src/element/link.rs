@@ -1,5 +1,5 @@
 use crate::*;
-use std::marker::PhantomData;
+use std::{borrow::Cow, marker::PhantomData};
 
 // ------ ------
 //    Element
@@ -120,8 +120,10 @@ impl<LabelFlag, ToFlag, RE: RawEl> Focusable for Link<LabelFlag, ToFlag, RE> whe
 impl<LabelFlag, ToFlag, RE: RawEl> MouseEventAware for Link<LabelFlag, ToFlag, RE> {}
 impl<LabelFlag, ToFlag, RE: RawEl> PointerEventAware for Link<LabelFlag, ToFlag, RE> {}
 impl<LabelFlag, ToFlag, RE: RawEl> TouchEventAware for Link<LabelFlag, ToFlag, RE> {}
+impl<LabelFlag, ToFlag, RE: RawEl> Draggable for Link<LabelFlag, ToFlag, RE> {}
 impl<LabelFlag, ToFlag, RE: RawEl> AddNearbyElement<'_> for Link<LabelFlag, ToFlag, RE> {}
 impl<LabelFlag, ToFlag, RE: RawEl> HasIds for Link<LabelFlag, ToFlag, RE> {}
+impl<LabelFlag, ToFlag, RE: RawEl> Accessible for Link<LabelFlag, ToFlag, RE> {}
 impl<LabelFlag, ToFlag, RE: RawEl> SelectableTextContent for Link<LabelFlag, ToFlag, RE> {}
 
 // ------ ------
@@ -152,7 +154,7 @@ impl<'a, LabelFlag, ToFlag, RE: RawEl> Link<LabelFlag, ToFlag, RE> {
     where
         ToFlag: FlagNotSet,
     {
-        self.raw_el = self.raw_el.attr("href", &to.into_cow_str());
+        self.raw_el = self.raw_el.attr("href", &href(to));
         self.into_type()
     }
 
@@ -163,7 +165,7 @@ impl<'a, LabelFlag, ToFlag, RE: RawEl> Link<LabelFlag, ToFlag, RE> {
     where
         ToFlag: FlagNotSet,
     {
-        self.raw_el = self.raw_el.attr_signal("href", to);
+        self.raw_el = self.raw_el.attr_signal("href", to.map(href));
         self.into_type()
     }
 
@@ -191,3 +193,16 @@ impl<'a, LabelFlag, ToFlag, RE: RawEl> Link<LabelFlag, ToFlag, RE> {
         }
     }
 }
+
+// ------ ------
+//     Extra
+// ------ ------
+
+/// Absolute paths are prefixed with `BASE_PATH`, the router strips it on click.
+fn href<'a>(to: impl IntoCowStr<'a>) -> Cow<'a, str> {
+    let to = to.into_cow_str();
+    if to.starts_with('/') && not(to.starts_with("//")) {
+        return with_base_path(&to).into();
+    }
+    to
+}
@@ -0,0 +1,319 @@
+use crate::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use strum::IntoStaticStr;
+
+thread_local! {
+    static OVERLAY_LAYER: web_sys::HtmlElement = create_overlay_layer();
+    static SCROLL_LOCKS: Cell<u32> = Cell::new(0);
+}
+
+// ------ Placement ------
+
+/// Preferred side of the anchor where `Popover` or `Tooltip` is displayed.
+/// The opposite side is used when there isn't enough space in the viewport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum Placement {
+    Above,
+    #[default]
+    Below,
+    OnLeft,
+    OnRight,
+}
+
+impl Placement {
+    fn opposite(self) -> Self {
+        match self {
+            Self::Above => Self::Below,
+            Self::Below => Self::Above,
+            Self::OnLeft => Self::OnRight,
+            Self::OnRight => Self::OnLeft,
+        }
+    }
+}
+
+// ------ AnchoredOverlay ------
+
+/// Element rendered into the top-level overlay layer next to the anchor,
+/// so `Clip` and `LayerIndex` of the anchor's ancestors don't affect it.
+/// Anchors in an open modal `<dialog>` have their overlay rendered into the dialog,
+/// because the page outside of the dialog's top layer is covered and inert.
+struct AnchoredOverlay {
+    container: web_sys::HtmlElement,
+    dom_handle: Option<dominator::DomHandle>,
+    _position_updater: AnimationLoop,
+}
+
+impl AnchoredOverlay {
+    fn new(anchor: web_sys::Element, element: RawElOrText, placement: Placement, gap: u32) -> Self {
+        let container = document()
+            .create_element("div")
+            .unwrap_throw()
+            .unchecked_into::<web_sys::HtmlElement>();
+        container.set_class_name("anchored_overlay");
+        let style = container.style();
+        style.set_property("position", "fixed").unwrap_throw();
+        style.set_property("pointer-events", "auto").unwrap_throw();
+        match anchor.closest("dialog[open]").unwrap_throw() {
+            Some(dialog) => {
+                style
+                    .set_property("z-index", &LayerIndex::OVERLAYS.to_string())
+                    .unwrap_throw();
+                dialog.append_child(&container).unwrap_throw();
+            }
+            None => OVERLAY_LAYER.with(|layer| layer.append_child(&container).unwrap_throw()),
+        }
+        let dom_handle = dominator::append_dom(&container, element.into_dom());
+
+        update_position(&anchor, &container, placement, gap);
+        // Anchors move with scrolling of any ancestor, so the position is updated on every frame.
+        let positioned_container = container.clone();
+        let position_updater = AnimationLoop::new(move |_| {
+            update_position(&anchor, &positioned_container, placement, gap)
+        });
+        Self {
+            container,
+            dom_handle: Some(dom_handle),
+            _position_updater: position_updater,
+        }
+    }
+
+    fn container(&self) -> &web_sys::HtmlElement {
+        &self.container
+    }
+}
+
+impl Drop for AnchoredOverlay {
+    fn drop(&mut self) {
+        if let Some(dom_handle) = self.dom_handle.take() {
+            dom_handle.discard();
+        }
+        self.container.remove();
+    }
+}
+
+fn create_overlay_layer() -> web_sys::HtmlElement {
+    let layer = document()
+        .create_element("div")
+        .unwrap_throw()
+        .unchecked_into::<web_sys::HtmlElement>();
+    layer.set_class_name("overlay_layer");
+    let style = layer.style();
+    style.set_property("position", "fixed").unwrap_throw();
+    style.set_property("top", "0").unwrap_throw();
+    style.set_property("left", "0").unwrap_throw();
+    style
+        .set_property("z-index", &LayerIndex::OVERLAYS.to_string())
+        .unwrap_throw();
+    dominator::body().append_child(&layer).unwrap_throw();
+    layer
+}
+
+fn update_position(
+    anchor: &web_sys::Element,
+    container: &web_sys::HtmlElement,
+    placement: Placement,
+    gap: u32,
+) {
+    let anchor = anchor.get_bounding_client_rect();
+    let gap = f64::from(gap);
+    let width = f64::from(container.offset_width());
+    let height = f64::from(container.offset_height());
+    let root = document().document_element().unwrap_throw();
+    let viewport_width = f64::from(root.client_width());
+    let viewport_height = f64::from(root.client_height());
+
+    let fits = |placement| match placement {
+        Placement::Above => anchor.top() - gap - height >= 0.,
+        Placement::Below => anchor.bottom() + gap + height <= viewport_height,
+        Placement::OnLeft => anchor.left() - gap - width >= 0.,
+        Placement::OnRight => anchor.right() + gap + width <= viewport_width,
+    };
+    let placement = if fits(placement) || not(fits(placement.opposite())) {
+        placement
+    } else {
+        placement.opposite()
+    };
+    let center_x = anchor.left() + (anchor.width() - width) / 2.;
+    let center_y = anchor.top() + (anchor.height() - height) / 2.;
+    let (x, y) = match placement {
+        Placement::Above => (center_x, anchor.top() - gap - height),
+        Placement::Below => (center_x, anchor.bottom() + gap),
+        Placement::OnLeft => (anchor.left() - gap - width, center_y),
+        Placement::OnRight => (anchor.right() + gap, center_y),
+    };
+    // Shifted to stay in the viewport.
+    let x = x.min(viewport_width - width).max(0.);
+    let y = y.min(viewport_height - height).max(0.);
+
+    let style = container.style();
+    style.set_property("left", &px(x)).unwrap_throw();
+    style.set_property("top", &px(y)).unwrap_throw();
+    let placement: &str = placement.into();
+    container
+        .set_attribute("data-placement", placement)
+        .unwrap_throw();
+}
+
+// ------ DocumentListener ------
+
+/// Event listener on the `document` removed on drop.
+struct DocumentListener {
+    event: &'static str,
+    callback: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl DocumentListener {
+    fn new(event: &'static str, callback: impl FnMut(web_sys::Event) + 'static) -> Self {
+        let callback = Closure::new(callback);
+        document()
+            .add_event_listener_with_callback_and_bool(
+                event,
+                callback.as_ref().unchecked_ref(),
+                true,
+            )
+            .unwrap_throw();
+        Self { event, callback }
+    }
+}
+
+impl Drop for DocumentListener {
+    fn drop(&mut self) {
+        let _ = document().remove_event_listener_with_callback_and_bool(
+            self.event,
+            self.callback.as_ref().unchecked_ref(),
+            true,
+        );
+    }
+}
+
+/// Calls `on_close` on `Escape` or on pointer down outside `anchor` and the overlay.
+fn close_listeners(
+    anchor: web_sys::Element,
+    overlay: web_sys::Element,
+    on_close: Rc<RefCell<dyn FnMut()>>,
+) -> [DocumentListener; 2] {
+    let on_close_by_key = Rc::clone(&on_close);
+    [
+        DocumentListener::new("keydown", move |event| {
+            let event = event.unchecked_into::<web_sys::KeyboardEvent>();
+            if event.key() == "Escape" {
+                // Only the overlay is closed, not also the `Dialog` containing its anchor.
+                event.prevent_default();
+                on_close_by_key.borrow_mut()();
+            }
+        }),
+        DocumentListener::new("pointerdown", move |event| {
+            let target = event
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+            let inside = |element: &web_sys::Element| element.contains(target.as_ref());
+            if not(inside(&anchor) || inside(&overlay)) {
+                on_close.borrow_mut()();
+            }
+        }),
+    ]
+}
+
+// ------ ScrollLock ------
+
+/// Prevents scrolling of the page until dropped.
+pub(crate) struct ScrollLock;
+
+impl ScrollLock {
+    pub(crate) fn new() -> Self {
+        SCROLL_LOCKS.with(|locks| {
+            if locks.get() == 0 {
+                set_body_overflow(Some("hidden"));
+            }
+            locks.set(locks.get() + 1);
+        });
+        Self
+    }
+}
+
+impl Drop for ScrollLock {
+    fn drop(&mut self) {
+        SCROLL_LOCKS.with(|locks| {
+            locks.set(locks.get().saturating_sub(1));
+            if locks.get() == 0 {
+                set_body_overflow(None);
+            }
+        });
+    }
+}
+
+fn set_body_overflow(overflow: Option<&str>) {
+    let style = dominator::body().style();
+    match overflow {
+        Some(overflow) => style.set_property("overflow", overflow).unwrap_throw(),
+        None => {
+            style.remove_property("overflow").unwrap_throw();
+        }
+    }
+}
+
+// ------ AnchoredContent ------
+
+type ContentRenderer = Box<dyn FnMut() -> RawElOrText>;
+
+/// Content of `Popover` and `Tooltip` rendered into `AnchoredOverlay` while it's open.
+#[derive(Default)]
+pub(crate) struct AnchoredContent {
+    renderer: RefCell<Option<ContentRenderer>>,
+    pub(crate) placement: Cell<Placement>,
+    pub(crate) gap: Cell<u32>,
+    on_close: RefCell<Option<Rc<RefCell<dyn FnMut()>>>>,
+    opened: RefCell<Option<(AnchoredOverlay, Option<[DocumentListener; 2]>)>>,
+}
+
+impl AnchoredContent {
+    pub(crate) fn set_renderer<'a, IE: IntoElement<'a> + 'a>(
+        &self,
+        mut renderer: impl FnMut() -> IE + 'static,
+    ) {
+        *self.renderer.borrow_mut() = Some(Box::new(move || renderer().into_element().into_raw()));
+    }
+
+    pub(crate) fn set_on_close(&self, on_close: impl FnMut() + 'static) {
+        *self.on_close.borrow_mut() = Some(Rc::new(RefCell::new(on_close)));
+    }
+
+    pub(crate) fn set_open(&self, anchor: &web_sys::Element, open: bool) {
+        if not(open) {
+            return self.close();
+        }
+        if self.opened.borrow().is_some() {
+            return;
+        }
+        let Some(content) = self
+            .renderer
+            .borrow_mut()
+            .as_mut()
+            .map(|renderer| renderer())
+        else {
+            return;
+        };
+        let overlay = AnchoredOverlay::new(
+            anchor.clone(),
+            content,
+            self.placement.get(),
+            self.gap.get(),
+        );
+        let close_listeners = self.on_close.borrow().as_ref().map(|on_close| {
+            close_listeners(
+                anchor.clone(),
+                overlay.container().clone().into(),
+                Rc::clone(on_close),
+            )
+        });
+        *self.opened.borrow_mut() = Some((overlay, close_listeners));
+    }
+
+    pub(crate) fn close(&self) {
+        self.opened.borrow_mut().take();
+    }
+}
@@ -52,9 +52,12 @@ impl<EmptyFlag, RE: RawEl> KeyboardEventAware for Paragraph<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> MouseEventAware for Paragraph<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> PointerEventAware for Paragraph<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> TouchEventAware for Paragraph<EmptyFlag, RE> {}
+impl<EmptyFlag, RE: RawEl> Draggable for Paragraph<EmptyFlag, RE> {}
+impl<EmptyFlag, RE: RawEl> DropTarget for Paragraph<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> MutableViewport for Paragraph<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> AddNearbyElement<'_> for Paragraph<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> HasIds for Paragraph<EmptyFlag, RE> {}
+impl<EmptyFlag, RE: RawEl> Accessible for Paragraph<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> HasLang for Paragraph<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> SelectableTextContent for Paragraph<EmptyFlag, RE> {}
 
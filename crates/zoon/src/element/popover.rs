@@ -0,0 +1,163 @@
+use crate::*;
+use std::{cell::RefCell, marker::PhantomData, rc::Rc};
+
+const DEFAULT_GAP: u32 = 4;
+
+// ------ ------
+//    Element
+// ------ ------
+
+make_flags!(Anchor, Content, Open);
+
+pub struct Popover<AnchorFlag, ContentFlag, OpenFlag, RE: RawEl> {
+    content: Rc<AnchoredContent>,
+    raw_el: RE,
+    flags: PhantomData<(AnchorFlag, ContentFlag, OpenFlag)>,
+}
+
+impl<RE: RawEl> Element for Popover<AnchorFlagSet, ContentFlagSet, OpenFlagSet, RE> {}
+
+impl Popover<AnchorFlagNotSet, ContentFlagNotSet, OpenFlagNotSet, RawHtmlEl<web_sys::HtmlElement>> {
+    /// Content displayed next to the anchor while `open_signal` is `true`, e.g. a menu.
+    /// It's rendered into the top-level layer so it isn't clipped by the anchor's ancestors.
+    #[track_caller]
+    pub fn new() -> Self {
+        let content = Rc::new(AnchoredContent::default());
+        content.gap.set(DEFAULT_GAP);
+        Self {
+            content,
+            raw_el: RawHtmlEl::new("div")
+                .class("popover")
+                .style("display", "inline-flex")
+                .style("flex-direction", "column"),
+            flags: PhantomData,
+        }
+    }
+}
+
+impl<AnchorFlag, ContentFlag, OpenFlag, RE: RawEl> RawElWrapper
+    for Popover<AnchorFlag, ContentFlag, OpenFlag, RE>
+{
+    type RawEl = RE;
+
+    fn raw_el_mut(&mut self) -> &mut Self::RawEl {
+        &mut self.raw_el
+    }
+}
+
+// ------ ------
+//   Abilities
+// ------ ------
+
+impl<AnchorFlag, ContentFlag, OpenFlag, RE: RawEl> Styleable<'_>
+    for Popover<AnchorFlag, ContentFlag, OpenFlag, RE>
+{
+}
+impl<AnchorFlag, ContentFlag, OpenFlag, RE: RawEl> KeyboardEventAware
+    for Popover<AnchorFlag, ContentFlag, OpenFlag, RE>
+{
+}
+impl<AnchorFlag, ContentFlag, OpenFlag, RE: RawEl> MouseEventAware
+    for Popover<AnchorFlag, ContentFlag, OpenFlag, RE>
+{
+}
+impl<AnchorFlag, ContentFlag, OpenFlag, RE: RawEl> PointerEventAware
+    for Popover<AnchorFlag, ContentFlag, OpenFlag, RE>
+{
+}
+impl<AnchorFlag, ContentFlag, OpenFlag, RE: RawEl> TouchEventAware
+    for Popover<AnchorFlag, ContentFlag, OpenFlag, RE>
+{
+}
+impl<AnchorFlag, ContentFlag, OpenFlag, RE: RawEl> HasIds
+    for Popover<AnchorFlag, ContentFlag, OpenFlag, RE>
+{
+}
+impl<AnchorFlag, ContentFlag, OpenFlag, RE: RawEl> Accessible
+    for Popover<AnchorFlag, ContentFlag, OpenFlag, RE>
+{
+}
+
+// ------ ------
+//  Attributes
+// ------ ------
+
+impl<'a, AnchorFlag, ContentFlag, OpenFlag, RE: RawEl>
+    Popover<AnchorFlag, ContentFlag, OpenFlag, RE>
+{
+    /// Element the content is positioned to, e.g. a button opening a menu.
+    pub fn anchor(
+        mut self,
+        anchor: impl IntoElement<'a> + 'a,
+    ) -> Popover<AnchorFlagSet, ContentFlag, OpenFlag, RE>
+    where
+        AnchorFlag: FlagNotSet,
+    {
+        self.raw_el = self.raw_el.child(anchor);
+        self.into_type()
+    }
+
+    /// `content` is called every time the popover is opened.
+    pub fn content<IE: IntoElement<'a> + 'a>(
+        self,
+        content: impl FnMut() -> IE + 'static,
+    ) -> Popover<AnchorFlag, ContentFlagSet, OpenFlag, RE>
+    where
+        ContentFlag: FlagNotSet,
+    {
+        self.content.set_renderer(content);
+        self.into_type()
+    }
+
+    pub fn open_signal(
+        mut self,
+        open: impl Signal<Item = bool> + Unpin + 'static,
+    ) -> Popover<AnchorFlag, ContentFlag, OpenFlagSet, RE>
+    where
+        OpenFlag: FlagNotSet,
+    {
+        let content = Rc::clone(&self.content);
+        let opener = Rc::new(RefCell::new(None));
+        self.raw_el = self
+            .raw_el
+            .after_insert(clone!((content, opener) move |anchor| {
+                let anchor: web_sys::Element = anchor.into();
+                *opener.borrow_mut() = Some(Task::start_droppable(
+                    open.for_each_sync(move |open| content.set_open(&anchor, open)),
+                ));
+            }))
+            .after_remove(move |_| {
+                opener.borrow_mut().take();
+                content.close();
+            });
+        self.into_type()
+    }
+
+    /// Preferred side of the anchor, `Placement::Below` by default.
+    pub fn placement(self, placement: Placement) -> Self {
+        self.content.placement.set(placement);
+        self
+    }
+
+    /// Distance between the anchor and the content, `4` by default.
+    pub fn gap(self, gap: u32) -> Self {
+        self.content.gap.set(gap);
+        self
+    }
+
+    /// Called on `Escape` or on click outside the anchor and the content while the popover is open.
+    pub fn on_close(self, on_close: impl FnMut() + 'static) -> Self {
+        self.content.set_on_close(on_close);
+        self
+    }
+
+    fn into_type<NewAnchorFlag, NewContentFlag, NewOpenFlag>(
+        self,
+    ) -> Popover<NewAnchorFlag, NewContentFlag, NewOpenFlag, RE> {
+        Popover {
+            content: self.content,
+            raw_el: self.raw_el,
+            flags: PhantomData,
+        }
+    }
+}
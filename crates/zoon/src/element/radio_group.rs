@@ -0,0 +1,260 @@
+use crate::*;
+use std::marker::PhantomData;
+
+// ------ ------
+//    Element
+// ------ ------
+
+make_flags!(Id, OnChange, Label, Options, Selected);
+
+pub struct RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> {
+    choices: Choices<T>,
+    raw_el: RE,
+    flags: PhantomData<(IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag)>,
+}
+
+impl<T, OnChangeFlag, SelectedFlag, RE: RawEl> Element
+    for RadioGroup<T, IdFlagSet, OnChangeFlag, LabelFlagNotSet, OptionsFlagSet, SelectedFlag, RE>
+{
+}
+
+impl<T, OnChangeFlag, SelectedFlag, RE: RawEl> Element
+    for RadioGroup<T, IdFlagNotSet, OnChangeFlag, LabelFlagSet, OptionsFlagSet, SelectedFlag, RE>
+{
+}
+
+impl<T, OnChangeFlag, SelectedFlag, RE: RawEl> Element
+    for RadioGroup<T, IdFlagSet, OnChangeFlag, LabelFlagSet, OptionsFlagSet, SelectedFlag, RE>
+{
+}
+
+impl<T: Clone + PartialEq + 'static>
+    RadioGroup<
+        T,
+        IdFlagNotSet,
+        OnChangeFlagNotSet,
+        LabelFlagNotSet,
+        OptionsFlagNotSet,
+        SelectedFlagNotSet,
+        RawHtmlEl<web_sys::HtmlDivElement>,
+    >
+{
+    /// Options are rendered with `default_icon` and the option label.
+    #[track_caller]
+    pub fn new() -> Self {
+        Self::custom(|option, selected| {
+            Row::new()
+                .s(Gap::new().x(8))
+                .item(default_icon(selected.signal()))
+                .item(option.label.clone())
+        })
+    }
+
+    /// `Mutable<bool>` is `true` when the option is selected.
+    /// The selection is changed by clicking or by arrow keys, `Home` and `End`.
+    #[track_caller]
+    pub fn custom<'a, IE: IntoElement<'a> + 'a>(
+        option_renderer: impl Fn(&ChoiceOption<T>, Mutable<bool>) -> IE + 'static,
+    ) -> Self {
+        let choices = Choices::new();
+        let raw_el = custom_choices_el(
+            &choices,
+            "radiogroup",
+            "radio",
+            into_option_renderer(option_renderer),
+        )
+        .class("radio_group");
+        Self {
+            choices,
+            raw_el,
+            flags: PhantomData,
+        }
+    }
+}
+
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> RawElWrapper
+    for RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+    type RawEl = RE;
+
+    fn raw_el_mut(&mut self) -> &mut Self::RawEl {
+        &mut self.raw_el
+    }
+}
+
+// ------ ------
+//   Abilities
+// ------ ------
+
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> Styleable<'_>
+    for RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> KeyboardEventAware
+    for RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> Focusable
+    for RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+where
+    RE::DomElement: AsRef<web_sys::HtmlElement>,
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> MouseEventAware
+    for RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> PointerEventAware
+    for RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> TouchEventAware
+    for RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> AddNearbyElement<'_>
+    for RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> HasIds
+    for RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> Accessible
+    for RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+
+// ------ ------
+//  Attributes
+// ------ ------
+
+impl<
+        'a,
+        T: Clone + PartialEq + 'static,
+        IdFlag,
+        OnChangeFlag,
+        LabelFlag,
+        OptionsFlag,
+        SelectedFlag,
+        RE: RawEl,
+    > RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+    pub fn id(
+        mut self,
+        id: impl IntoCowStr<'a>,
+    ) -> RadioGroup<T, IdFlagSet, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+    where
+        IdFlag: FlagNotSet,
+    {
+        self.raw_el = self.raw_el.id(id);
+        self.into_type()
+    }
+
+    pub fn options(
+        self,
+        options: impl IntoIterator<Item = ChoiceOption<T>>,
+    ) -> RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlagSet, SelectedFlag, RE>
+    where
+        OptionsFlag: FlagNotSet,
+    {
+        self.choices.set_options(options);
+        self.into_type()
+    }
+
+    pub fn options_signal_vec(
+        mut self,
+        options: impl SignalVec<Item = ChoiceOption<T>> + 'static,
+    ) -> RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlagSet, SelectedFlag, RE>
+    where
+        OptionsFlag: FlagNotSet,
+    {
+        let task_handle = self.choices.options_task(options);
+        self.raw_el = self.raw_el.after_remove(move |_| drop(task_handle));
+        self.into_type()
+    }
+
+    pub fn selected(
+        self,
+        selected: Option<T>,
+    ) -> RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlagSet, RE>
+    where
+        SelectedFlag: FlagNotSet,
+    {
+        self.choices.set_selected(selected);
+        self.into_type()
+    }
+
+    pub fn selected_signal(
+        mut self,
+        selected: impl Signal<Item = Option<T>> + Unpin + 'static,
+    ) -> RadioGroup<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlagSet, RE>
+    where
+        SelectedFlag: FlagNotSet,
+    {
+        let task_handle = self.choices.selected_task(selected);
+        self.raw_el = self.raw_el.after_remove(move |_| drop(task_handle));
+        self.into_type()
+    }
+
+    pub fn on_change(
+        mut self,
+        on_change: impl FnMut(Option<T>) + 'static,
+    ) -> RadioGroup<T, IdFlag, OnChangeFlagSet, LabelFlag, OptionsFlag, SelectedFlag, RE>
+    where
+        OnChangeFlag: FlagNotSet,
+    {
+        let task_handle = self.choices.on_change_task(on_change);
+        self.raw_el = self.raw_el.after_remove(move |_| drop(task_handle));
+        self.into_type()
+    }
+
+    pub fn label_hidden(
+        mut self,
+        label: impl IntoCowStr<'a>,
+    ) -> RadioGroup<T, IdFlag, OnChangeFlag, LabelFlagSet, OptionsFlag, SelectedFlag, RE>
+    where
+        LabelFlag: FlagNotSet,
+    {
+        self.raw_el = self.raw_el.attr("aria-label", &label.into_cow_str());
+        self.into_type()
+    }
+
+    pub fn label_hidden_signal(
+        mut self,
+        label: impl Signal<Item = impl IntoOptionCowStr<'a>> + Unpin + 'static,
+    ) -> RadioGroup<T, IdFlag, OnChangeFlag, LabelFlagSet, OptionsFlag, SelectedFlag, RE>
+    where
+        LabelFlag: FlagNotSet,
+    {
+        self.raw_el = self.raw_el.attr_signal("aria-label", label);
+        self.into_type()
+    }
+
+    fn into_type<NewIdFlag, NewOnChangeFlag, NewLabelFlag, NewOptionsFlag, NewSelectedFlag>(
+        self,
+    ) -> RadioGroup<T, NewIdFlag, NewOnChangeFlag, NewLabelFlag, NewOptionsFlag, NewSelectedFlag, RE>
+    {
+        RadioGroup {
+            choices: self.choices,
+            raw_el: self.raw_el,
+            flags: PhantomData,
+        }
+    }
+}
+
+// ------ ------
+//     Extra
+// ------ ------
+
+pub fn default_icon(selected_signal: MutableSignal<bool>) -> impl Element {
+    // @TODO replace with better custom icons
+    static UNSELECTED_ICON: &str = "data:image/svg+xml;utf8,%3Csvg%20xmlns%3D%22http%3A//www.w3.org/2000/svg%22%20width%3D%2220%22%20height%3D%2220%22%20viewBox%3D%220%200%2020%2020%22%3E%3Ccircle%20cx%3D%2210%22%20cy%3D%2210%22%20r%3D%229%22%20fill%3D%22none%22%20stroke%3D%22%23bdbdbd%22%20stroke-width%3D%222%22/%3E%3C/svg%3E";
+    static SELECTED_ICON: &str = "data:image/svg+xml;utf8,%3Csvg%20xmlns%3D%22http%3A//www.w3.org/2000/svg%22%20width%3D%2220%22%20height%3D%2220%22%20viewBox%3D%220%200%2020%2020%22%3E%3Ccircle%20cx%3D%2210%22%20cy%3D%2210%22%20r%3D%229%22%20fill%3D%22none%22%20stroke%3D%22%235dc2af%22%20stroke-width%3D%222%22/%3E%3Ccircle%20cx%3D%2210%22%20cy%3D%2210%22%20r%3D%225%22%20fill%3D%22%235dc2af%22/%3E%3C/svg%3E";
+
+    El::new()
+        .s(Width::exact(20))
+        .s(Height::exact(20))
+        .s(Background::new()
+            .url_signal(selected_signal.map_bool(|| SELECTED_ICON, || UNSELECTED_ICON)))
+}
@@ -260,11 +260,13 @@ pub trait RawEl: Sized + Into<RawElOrText> {
             self = self.class_signal(class, enabled);
         }
 
-        if group.selector.is_empty() {
+        if group.selector.is_empty() && not(group.is_conditional()) {
             let StyleGroup {
                 selector: _,
                 static_css_props,
                 dynamic_css_props,
+                media_query: _,
+                container_query: _,
                 static_css_classes: _,
                 dynamic_css_classes: _,
                 mut resize_handlers,
@@ -86,6 +86,8 @@ impl<EmptyFlag, MultilineFlag, RE: RawEl> KeyboardEventAware for Row<EmptyFlag,
 impl<EmptyFlag, MultilineFlag, RE: RawEl> MouseEventAware for Row<EmptyFlag, MultilineFlag, RE> {}
 impl<EmptyFlag, MultilineFlag, RE: RawEl> PointerEventAware for Row<EmptyFlag, MultilineFlag, RE> {}
 impl<EmptyFlag, MultilineFlag, RE: RawEl> TouchEventAware for Row<EmptyFlag, MultilineFlag, RE> {}
+impl<EmptyFlag, MultilineFlag, RE: RawEl> Draggable for Row<EmptyFlag, MultilineFlag, RE> {}
+impl<EmptyFlag, MultilineFlag, RE: RawEl> DropTarget for Row<EmptyFlag, MultilineFlag, RE> {}
 impl<EmptyFlag, MultilineFlag, RE: RawEl> MutableViewport for Row<EmptyFlag, MultilineFlag, RE> {}
 impl<EmptyFlag, MultilineFlag, RE: RawEl> ResizableViewport for Row<EmptyFlag, MultilineFlag, RE> {}
 impl<EmptyFlag, MultilineFlag, RE: RawEl> AddNearbyElement<'_>
@@ -93,6 +95,7 @@ impl<EmptyFlag, MultilineFlag, RE: RawEl> AddNearbyElement<'_>
 {
 }
 impl<EmptyFlag, MultilineFlag, RE: RawEl> HasIds for Row<EmptyFlag, MultilineFlag, RE> {}
+impl<EmptyFlag, MultilineFlag, RE: RawEl> Accessible for Row<EmptyFlag, MultilineFlag, RE> {}
 impl<EmptyFlag, MultilineFlag, RE: RawEl> SelectableTextContent
     for Row<EmptyFlag, MultilineFlag, RE>
 {
@@ -0,0 +1,314 @@
+use crate::*;
+use std::marker::PhantomData;
+
+// ------ ------
+//    Element
+// ------ ------
+
+make_flags!(Id, OnChange, Label, Options, Selected);
+
+pub struct Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> {
+    choices: Choices<T>,
+    raw_el: RE,
+    flags: PhantomData<(IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag)>,
+}
+
+impl<T, OnChangeFlag, SelectedFlag, RE: RawEl> Element
+    for Select<T, IdFlagSet, OnChangeFlag, LabelFlagNotSet, OptionsFlagSet, SelectedFlag, RE>
+{
+}
+
+impl<T, OnChangeFlag, SelectedFlag, RE: RawEl> Element
+    for Select<T, IdFlagNotSet, OnChangeFlag, LabelFlagSet, OptionsFlagSet, SelectedFlag, RE>
+{
+}
+
+impl<T, OnChangeFlag, SelectedFlag, RE: RawEl> Element
+    for Select<T, IdFlagSet, OnChangeFlag, LabelFlagSet, OptionsFlagSet, SelectedFlag, RE>
+{
+}
+
+impl<T: Clone + PartialEq + 'static>
+    Select<
+        T,
+        IdFlagNotSet,
+        OnChangeFlagNotSet,
+        LabelFlagNotSet,
+        OptionsFlagNotSet,
+        SelectedFlagNotSet,
+        RawHtmlEl<web_sys::HtmlSelectElement>,
+    >
+{
+    /// Native `<select>` element. It's empty when nothing is selected.
+    /// Only a single option can be selected, use `Checkbox`es for multiple choices.
+    #[track_caller]
+    pub fn new() -> Self {
+        let choices = Choices::new();
+        let raw_el = RawHtmlEl::<web_sys::HtmlSelectElement>::new("select").class("select");
+        let dom_element = raw_el.dom_element();
+        let raw_el = raw_el
+            .event_handler(clone!((choices) move |_: events::Change| {
+                // The first option is the placeholder.
+                let index = usize::try_from(dom_element.selected_index())
+                    .ok()
+                    .and_then(|index| index.checked_sub(1));
+                if let Some(index) = index {
+                    choices.select_index(index);
+                }
+            }))
+            .child(native_placeholder_el(&choices))
+            .children_signal_vec(
+                choices
+                    .options
+                    .signal_vec_cloned()
+                    .map(clone!((choices) move |option| native_option_el(&choices, option))),
+            );
+        Self {
+            choices,
+            raw_el,
+            flags: PhantomData,
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + 'static>
+    Select<
+        T,
+        IdFlagNotSet,
+        OnChangeFlagNotSet,
+        LabelFlagNotSet,
+        OptionsFlagNotSet,
+        SelectedFlagNotSet,
+        RawHtmlEl<web_sys::HtmlDivElement>,
+    >
+{
+    /// Always open `listbox` with options rendered by `option_renderer`.
+    /// `Mutable<bool>` is `true` when the option is selected.
+    /// The selection is changed by clicking or by arrow keys, `Home` and `End`.
+    #[track_caller]
+    pub fn custom<'a, IE: IntoElement<'a> + 'a>(
+        option_renderer: impl Fn(&ChoiceOption<T>, Mutable<bool>) -> IE + 'static,
+    ) -> Self {
+        let choices = Choices::new();
+        let raw_el = custom_choices_el(
+            &choices,
+            "listbox",
+            "option",
+            into_option_renderer(option_renderer),
+        )
+        .class("select");
+        Self {
+            choices,
+            raw_el,
+            flags: PhantomData,
+        }
+    }
+}
+
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> RawElWrapper
+    for Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+    type RawEl = RE;
+
+    fn raw_el_mut(&mut self) -> &mut Self::RawEl {
+        &mut self.raw_el
+    }
+}
+
+// ------ ------
+//   Abilities
+// ------ ------
+
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> Styleable<'_>
+    for Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> KeyboardEventAware
+    for Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> Focusable
+    for Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+where
+    RE::DomElement: AsRef<web_sys::HtmlElement>,
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> MouseEventAware
+    for Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> PointerEventAware
+    for Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> TouchEventAware
+    for Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> AddNearbyElement<'_>
+    for Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> HasIds
+    for Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE: RawEl> Accessible
+    for Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+}
+
+// ------ ------
+//  Attributes
+// ------ ------
+
+impl<
+        'a,
+        T: Clone + PartialEq + 'static,
+        IdFlag,
+        OnChangeFlag,
+        LabelFlag,
+        OptionsFlag,
+        SelectedFlag,
+        RE: RawEl,
+    > Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+{
+    pub fn id(
+        mut self,
+        id: impl IntoCowStr<'a>,
+    ) -> Select<T, IdFlagSet, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlag, RE>
+    where
+        IdFlag: FlagNotSet,
+    {
+        self.raw_el = self.raw_el.id(id);
+        self.into_type()
+    }
+
+    pub fn options(
+        self,
+        options: impl IntoIterator<Item = ChoiceOption<T>>,
+    ) -> Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlagSet, SelectedFlag, RE>
+    where
+        OptionsFlag: FlagNotSet,
+    {
+        self.choices.set_options(options);
+        self.into_type()
+    }
+
+    pub fn options_signal_vec(
+        mut self,
+        options: impl SignalVec<Item = ChoiceOption<T>> + 'static,
+    ) -> Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlagSet, SelectedFlag, RE>
+    where
+        OptionsFlag: FlagNotSet,
+    {
+        let task_handle = self.choices.options_task(options);
+        self.raw_el = self.raw_el.after_remove(move |_| drop(task_handle));
+        self.into_type()
+    }
+
+    pub fn selected(
+        self,
+        selected: Option<T>,
+    ) -> Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlagSet, RE>
+    where
+        SelectedFlag: FlagNotSet,
+    {
+        self.choices.set_selected(selected);
+        self.into_type()
+    }
+
+    pub fn selected_signal(
+        mut self,
+        selected: impl Signal<Item = Option<T>> + Unpin + 'static,
+    ) -> Select<T, IdFlag, OnChangeFlag, LabelFlag, OptionsFlag, SelectedFlagSet, RE>
+    where
+        SelectedFlag: FlagNotSet,
+    {
+        let task_handle = self.choices.selected_task(selected);
+        self.raw_el = self.raw_el.after_remove(move |_| drop(task_handle));
+        self.into_type()
+    }
+
+    pub fn on_change(
+        mut self,
+        on_change: impl FnMut(Option<T>) + 'static,
+    ) -> Select<T, IdFlag, OnChangeFlagSet, LabelFlag, OptionsFlag, SelectedFlag, RE>
+    where
+        OnChangeFlag: FlagNotSet,
+    {
+        let task_handle = self.choices.on_change_task(on_change);
+        self.raw_el = self.raw_el.after_remove(move |_| drop(task_handle));
+        self.into_type()
+    }
+
+    pub fn label_hidden(
+        mut self,
+        label: impl IntoCowStr<'a>,
+    ) -> Select<T, IdFlag, OnChangeFlag, LabelFlagSet, OptionsFlag, SelectedFlag, RE>
+    where
+        LabelFlag: FlagNotSet,
+    {
+        self.raw_el = self.raw_el.attr("aria-label", &label.into_cow_str());
+        self.into_type()
+    }
+
+    pub fn label_hidden_signal(
+        mut self,
+        label: impl Signal<Item = impl IntoOptionCowStr<'a>> + Unpin + 'static,
+    ) -> Select<T, IdFlag, OnChangeFlag, LabelFlagSet, OptionsFlag, SelectedFlag, RE>
+    where
+        LabelFlag: FlagNotSet,
+    {
+        self.raw_el = self.raw_el.attr_signal("aria-label", label);
+        self.into_type()
+    }
+
+    fn into_type<NewIdFlag, NewOnChangeFlag, NewLabelFlag, NewOptionsFlag, NewSelectedFlag>(
+        self,
+    ) -> Select<T, NewIdFlag, NewOnChangeFlag, NewLabelFlag, NewOptionsFlag, NewSelectedFlag, RE>
+    {
+        Select {
+            choices: self.choices,
+            raw_el: self.raw_el,
+            flags: PhantomData,
+        }
+    }
+}
+
+// ------ ------
+//     Extra
+// ------ ------
+
+/// Hidden empty option displayed by the browser when no option is selected.
+fn native_placeholder_el<T: Clone + PartialEq + 'static>(
+    choices: &Choices<T>,
+) -> RawHtmlEl<web_sys::HtmlOptionElement> {
+    let is_selected = choices.is_nothing_selected_signal();
+    RawHtmlEl::<web_sys::HtmlOptionElement>::new("option")
+        .attr("value", "")
+        .attr("disabled", "")
+        .attr("hidden", "")
+        .use_dom_element(move |raw_el, dom_element| {
+            let selected_setter = Task::start_droppable(
+                is_selected.for_each_sync(move |selected| dom_element.set_selected(selected)),
+            );
+            raw_el.after_remove(move |_| drop(selected_setter))
+        })
+}
+
+fn native_option_el<T: Clone + PartialEq + 'static>(
+    choices: &Choices<T>,
+    option: ChoiceOption<T>,
+) -> RawHtmlEl<web_sys::HtmlOptionElement> {
+    let is_selected = choices.is_selected_signal(option.value);
+    RawHtmlEl::<web_sys::HtmlOptionElement>::new("option")
+        .child(option.label)
+        .use_dom_element(move |raw_el, dom_element| {
+            dom_element.set_disabled(option.disabled);
+            let selected_setter = Task::start_droppable(
+                is_selected.for_each_sync(move |selected| dom_element.set_selected(selected)),
+            );
+            raw_el.after_remove(move |_| drop(selected_setter))
+        })
+}
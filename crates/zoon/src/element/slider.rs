@@ -0,0 +1,280 @@
+use crate::*;
+use std::marker::PhantomData;
+
+// ------ ------
+//    Element
+// ------ ------
+
+make_flags!(Id, OnChange, Label, Value);
+
+pub struct Slider<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE: RawEl> {
+    raw_el: RE,
+    flags: PhantomData<(T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag)>,
+}
+
+impl<T, OnChangeFlag, ValueFlag, RE: RawEl> Element
+    for Slider<T, IdFlagSet, OnChangeFlag, LabelFlagNotSet, ValueFlag, RE>
+{
+}
+
+impl<T, OnChangeFlag, ValueFlag, RE: RawEl> Element
+    for Slider<T, IdFlagNotSet, OnChangeFlag, LabelFlagSet, ValueFlag, RE>
+{
+}
+
+impl<T, OnChangeFlag, ValueFlag, RE: RawEl> Element
+    for Slider<T, IdFlagSet, OnChangeFlag, LabelFlagSet, ValueFlag, RE>
+{
+}
+
+impl<T: SliderValue>
+    Slider<
+        T,
+        IdFlagNotSet,
+        OnChangeFlagNotSet,
+        LabelFlagNotSet,
+        ValueFlagNotSet,
+        RawHtmlEl<web_sys::HtmlInputElement>,
+    >
+{
+    /// Native `<input type="range">`, `min` is `0`, `max` is `100` and `step` is `1` by default.
+    /// Arrow keys, `Page Up`, `Page Down`, `Home` and `End` change the value.
+    #[track_caller]
+    pub fn new() -> Self {
+        Self {
+            raw_el: RawHtmlEl::<web_sys::HtmlInputElement>::new("input")
+                .class("slider")
+                .attr("type", "range")
+                .attr("role", "slider")
+                .attr("aria-valuemin", "0")
+                .attr("aria-valuemax", "100")
+                .style("cursor", "pointer"),
+            flags: PhantomData,
+        }
+    }
+}
+
+impl<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE: RawEl> RawElWrapper
+    for Slider<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE>
+{
+    type RawEl = RE;
+
+    fn raw_el_mut(&mut self) -> &mut Self::RawEl {
+        &mut self.raw_el
+    }
+}
+
+// ------ ------
+//   Abilities
+// ------ ------
+
+impl<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE: RawEl> Styleable<'_>
+    for Slider<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE: RawEl> KeyboardEventAware
+    for Slider<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE: RawEl> Focusable
+    for Slider<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE>
+where
+    RE::DomElement: AsRef<web_sys::HtmlElement>,
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE: RawEl> MouseEventAware
+    for Slider<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE: RawEl> PointerEventAware
+    for Slider<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE: RawEl> TouchEventAware
+    for Slider<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE: RawEl> AddNearbyElement<'_>
+    for Slider<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE: RawEl> HasIds
+    for Slider<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE>
+{
+}
+impl<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE: RawEl> Accessible
+    for Slider<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE>
+{
+}
+
+// ------ ------
+//  Attributes
+// ------ ------
+
+impl<'a, T: SliderValue, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE: RawEl>
+    Slider<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlag, RE>
+{
+    pub fn id(
+        mut self,
+        id: impl IntoCowStr<'a>,
+    ) -> Slider<T, IdFlagSet, OnChangeFlag, LabelFlag, ValueFlag, RE>
+    where
+        IdFlag: FlagNotSet,
+    {
+        self.raw_el = self.raw_el.id(id);
+        self.into_type()
+    }
+
+    pub fn min(mut self, min: T) -> Self {
+        let min = min.into_f64().to_string();
+        self.raw_el = self.raw_el.attr("min", &min).attr("aria-valuemin", &min);
+        self
+    }
+
+    pub fn max(mut self, max: T) -> Self {
+        let max = max.into_f64().to_string();
+        self.raw_el = self.raw_el.attr("max", &max).attr("aria-valuemax", &max);
+        self
+    }
+
+    pub fn step(mut self, step: T) -> Self {
+        self.raw_el = self.raw_el.attr("step", &step.into_f64().to_string());
+        self
+    }
+
+    pub fn vertical(mut self) -> Self {
+        // `writing-mode` and `direction` put the minimum at the bottom, `orient` is for Firefox < 120.
+        self.raw_el = self
+            .raw_el
+            .attr("aria-orientation", "vertical")
+            .attr("orient", "vertical")
+            .style("writing-mode", "vertical-lr")
+            .style("direction", "rtl");
+        self
+    }
+
+    pub fn value(mut self, value: T) -> Slider<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlagSet, RE>
+    where
+        ValueFlag: FlagNotSet,
+    {
+        // The attribute (the default value) is clamped again when `min` or `max` is set later.
+        self.raw_el = self.raw_el.attr("value", &value.into_f64().to_string());
+        self.into_type()
+    }
+
+    pub fn value_signal(
+        self,
+        value: impl Signal<Item = T> + Unpin + 'static,
+    ) -> Slider<T, IdFlag, OnChangeFlag, LabelFlag, ValueFlagSet, RE>
+    where
+        ValueFlag: FlagNotSet,
+        RE::DomElement: AsRef<web_sys::HtmlInputElement>,
+    {
+        let dom_element = self.raw_el.dom_element();
+        let value_setter = Task::start_droppable(value.for_each_sync(move |value| {
+            dom_element
+                .as_ref()
+                .set_value(&value.into_f64().to_string())
+        }));
+        self.after_remove(move |_| drop(value_setter)).into_type()
+    }
+
+    pub fn on_change(
+        mut self,
+        mut on_change: impl FnMut(T) + 'static,
+    ) -> Slider<T, IdFlag, OnChangeFlagSet, LabelFlag, ValueFlag, RE>
+    where
+        OnChangeFlag: FlagNotSet,
+        RE::DomElement: AsRef<web_sys::HtmlInputElement>,
+    {
+        let dom_element = self.raw_el.dom_element();
+        self.raw_el = self.raw_el.event_handler(move |_: events::Input| {
+            let value = dom_element.as_ref().value_as_number();
+            if not(value.is_nan()) {
+                on_change(T::from_f64(value));
+            }
+        });
+        self.into_type()
+    }
+
+    pub fn label_hidden(
+        mut self,
+        label: impl IntoCowStr<'a>,
+    ) -> Slider<T, IdFlag, OnChangeFlag, LabelFlagSet, ValueFlag, RE>
+    where
+        LabelFlag: FlagNotSet,
+    {
+        self.raw_el = self.raw_el.attr("aria-label", &label.into_cow_str());
+        self.into_type()
+    }
+
+    pub fn label_hidden_signal(
+        mut self,
+        label: impl Signal<Item = impl IntoOptionCowStr<'a>> + Unpin + 'static,
+    ) -> Slider<T, IdFlag, OnChangeFlag, LabelFlagSet, ValueFlag, RE>
+    where
+        LabelFlag: FlagNotSet,
+    {
+        self.raw_el = self.raw_el.attr_signal("aria-label", label);
+        self.into_type()
+    }
+
+    fn into_type<NewIdFlag, NewOnChangeFlag, NewLabelFlag, NewValueFlag>(
+        self,
+    ) -> Slider<T, NewIdFlag, NewOnChangeFlag, NewLabelFlag, NewValueFlag, RE> {
+        Slider {
+            raw_el: self.raw_el,
+            flags: PhantomData,
+        }
+    }
+}
+
+// ------ ------
+//     Extra
+// ------ ------
+
+// ------ SliderValue ------
+
+/// Numbers that can be set and read from `Slider`. Integers are rounded.
+pub trait SliderValue: Copy + 'static {
+    fn into_f64(self) -> f64;
+
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! make_slider_value_impls {
+    ($($type:ty),*) => (
+        $(
+        impl SliderValue for $type {
+            fn into_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn from_f64(value: f64) -> Self {
+                value.round() as $type
+            }
+        }
+        )*
+    )
+}
+make_slider_value_impls!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl SliderValue for f32 {
+    fn into_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl SliderValue for f64 {
+    fn into_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
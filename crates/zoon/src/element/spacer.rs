@@ -69,3 +69,4 @@ impl RawElWrapper for Spacer {
 impl ResizableViewport for Spacer {}
 impl AddNearbyElement<'_> for Spacer {}
 impl HasIds for Spacer {}
+impl Accessible for Spacer {}
@@ -90,10 +90,13 @@ impl<EmptyFlag, RE: RawEl> KeyboardEventAware for Stack<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> MouseEventAware for Stack<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> PointerEventAware for Stack<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> TouchEventAware for Stack<EmptyFlag, RE> {}
+impl<EmptyFlag, RE: RawEl> Draggable for Stack<EmptyFlag, RE> {}
+impl<EmptyFlag, RE: RawEl> DropTarget for Stack<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> MutableViewport for Stack<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> ResizableViewport for Stack<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> AddNearbyElement<'_> for Stack<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> HasIds for Stack<EmptyFlag, RE> {}
+impl<EmptyFlag, RE: RawEl> Accessible for Stack<EmptyFlag, RE> {}
 impl<EmptyFlag, RE: RawEl> SelectableTextContent for Stack<EmptyFlag, RE> {}
 
 // ------ ------
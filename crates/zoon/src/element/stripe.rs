@@ -172,6 +172,8 @@ impl<EmptyFlag, MultilineFlag, RE: RawEl> PointerEventAware
 {
 }
 impl<EmptyFlag, MultilineFlag, RE: RawEl> TouchEventAware for Stripe<EmptyFlag, MultilineFlag, RE> {}
+impl<EmptyFlag, MultilineFlag, RE: RawEl> Draggable for Stripe<EmptyFlag, MultilineFlag, RE> {}
+impl<EmptyFlag, MultilineFlag, RE: RawEl> DropTarget for Stripe<EmptyFlag, MultilineFlag, RE> {}
 impl<EmptyFlag, MultilineFlag, RE: RawEl> MutableViewport for Stripe<EmptyFlag, MultilineFlag, RE> {}
 impl<EmptyFlag, MultilineFlag, RE: RawEl> ResizableViewport
     for Stripe<EmptyFlag, MultilineFlag, RE>
@@ -182,6 +184,7 @@ impl<EmptyFlag, MultilineFlag, RE: RawEl> AddNearbyElement<'_>
 {
 }
 impl<EmptyFlag, MultilineFlag, RE: RawEl> HasIds for Stripe<EmptyFlag, MultilineFlag, RE> {}
+impl<EmptyFlag, MultilineFlag, RE: RawEl> Accessible for Stripe<EmptyFlag, MultilineFlag, RE> {}
 impl<EmptyFlag, MultilineFlag, RE: RawEl> SelectableTextContent
     for Stripe<EmptyFlag, MultilineFlag, RE>
 {
@@ -133,6 +133,10 @@ impl<IdFlag, OnChangeFlag, PlaceholderFlag, TextFlag, LabelFlag, ReadOnlyFlag, R
     for TextArea<IdFlag, OnChangeFlag, PlaceholderFlag, TextFlag, LabelFlag, ReadOnlyFlag, RE>
 {
 }
+impl<IdFlag, OnChangeFlag, PlaceholderFlag, TextFlag, LabelFlag, ReadOnlyFlag, RE: RawEl> Accessible
+    for TextArea<IdFlag, OnChangeFlag, PlaceholderFlag, TextFlag, LabelFlag, ReadOnlyFlag, RE>
+{
+}
 impl<IdFlag, OnChangeFlag, PlaceholderFlag, TextFlag, LabelFlag, ReadOnlyFlag, RE: RawEl>
     SelectableTextContent
     for TextArea<IdFlag, OnChangeFlag, PlaceholderFlag, TextFlag, LabelFlag, ReadOnlyFlag, RE>
@@ -246,6 +250,27 @@ impl<'a, IdFlag, OnChangeFlag, PlaceholderFlag, TextFlag, LabelFlag, ReadOnlyFla
         self.into_type()
     }
 
+    /// Binds the text and changes to the field, touches it on blur and sets `aria-invalid`.
+    pub fn field<T: FieldInput + Clone + PartialEq + 'static>(
+        self,
+        field: &Field<T>,
+    ) -> TextArea<IdFlag, OnChangeFlagSet, PlaceholderFlag, TextFlagSet, LabelFlag, ReadOnlyFlag, RE>
+    where
+        OnChangeFlag: FlagNotSet,
+        TextFlag: FlagNotSet,
+        RE::DomElement: AsRef<web_sys::HtmlTextAreaElement>,
+    {
+        let aria_invalid = field.aria_invalid_signal();
+        let touched_field = field.clone();
+        self.text_signal(field.input_signal())
+            .on_change(clone!((field) move |text| field.set_input(&text)))
+            .update_raw_el(|raw_el| {
+                raw_el
+                    .attr_signal("aria-invalid", aria_invalid)
+                    .event_handler(move |_: events::Blur| touched_field.touch())
+            })
+    }
+
     pub fn label_hidden(
         mut self,
         label: impl IntoCowStr<'a>,
@@ -305,6 +305,28 @@ impl<
     >
 {
 }
+impl<
+        IdFlag,
+        OnChangeFlag,
+        PlaceholderFlag,
+        TextFlag,
+        LabelFlag,
+        InputTypeFlag,
+        ReadOnlyFlag,
+        RE: RawEl,
+    > Accessible
+    for TextInput<
+        IdFlag,
+        OnChangeFlag,
+        PlaceholderFlag,
+        TextFlag,
+        LabelFlag,
+        InputTypeFlag,
+        ReadOnlyFlag,
+        RE,
+    >
+{
+}
 impl<
         IdFlag,
         OnChangeFlag,
@@ -537,6 +559,36 @@ impl<
         self.into_type()
     }
 
+    /// Binds the text and changes to the field, touches it on blur and sets `aria-invalid`.
+    pub fn field<T: FieldInput + Clone + PartialEq + 'static>(
+        self,
+        field: &Field<T>,
+    ) -> TextInput<
+        IdFlag,
+        OnChangeFlagSet,
+        PlaceholderFlag,
+        TextFlagSet,
+        LabelFlag,
+        InputTypeFlag,
+        ReadOnlyFlag,
+        RE,
+    >
+    where
+        OnChangeFlag: FlagNotSet,
+        TextFlag: FlagNotSet,
+        RE::DomElement: AsRef<web_sys::HtmlInputElement>,
+    {
+        let aria_invalid = field.aria_invalid_signal();
+        let touched_field = field.clone();
+        self.text_signal(field.input_signal())
+            .on_change(clone!((field) move |text| field.set_input(&text)))
+            .update_raw_el(|raw_el| {
+                raw_el
+                    .attr_signal("aria-invalid", aria_invalid)
+                    .event_handler(move |_: events::Blur| touched_field.touch())
+            })
+    }
+
     pub fn label_hidden(
         mut self,
         label: impl IntoCowStr<'a>,
@@ -0,0 +1,227 @@
+use crate::*;
+use std::{
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+    rc::Rc,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+const DEFAULT_DELAY_MS: u32 = 500;
+const DEFAULT_GAP: u32 = 4;
+
+static NEXT_TOOLTIP_ID: AtomicU32 = AtomicU32::new(0);
+
+// ------ ------
+//    Element
+// ------ ------
+
+make_flags!(Anchor, Content);
+
+pub struct Tooltip<AnchorFlag, ContentFlag, RE: RawEl> {
+    state: Rc<TooltipState>,
+    raw_el: RE,
+    flags: PhantomData<(AnchorFlag, ContentFlag)>,
+}
+
+impl<RE: RawEl> Element for Tooltip<AnchorFlagSet, ContentFlagSet, RE> {}
+
+impl Tooltip<AnchorFlagNotSet, ContentFlagNotSet, RawHtmlEl<web_sys::HtmlElement>> {
+    /// Short description of the anchor displayed on hover (after a delay) or on focus.
+    /// The anchor is described by the tooltip for screen readers (`aria-describedby`).
+    #[track_caller]
+    pub fn new() -> Self {
+        let state = Rc::new(TooltipState {
+            content: AnchoredContent::default(),
+            delay: Cell::new(DEFAULT_DELAY_MS),
+            id: format!(
+                "tooltip_{}",
+                NEXT_TOOLTIP_ID.fetch_add(1, Ordering::Relaxed)
+            ),
+            opener: RefCell::new(None),
+        });
+        state.content.placement.set(Placement::Above);
+        state.content.gap.set(DEFAULT_GAP);
+
+        let raw_el = RawHtmlEl::<web_sys::HtmlElement>::new("div")
+            .class("tooltip_anchor")
+            .style("display", "inline-flex")
+            .style("flex-direction", "column");
+        let anchor: web_sys::Element = raw_el.dom_element().into();
+        let raw_el = raw_el
+            .event_handler(clone!((state, anchor) move |_: events::MouseEnter| {
+                state.open(&anchor, state.delay.get())
+            }))
+            .event_handler(clone!((state, anchor) move |_: events::MouseLeave| {
+                state.close(&anchor)
+            }))
+            // `focus` and `blur` of descendants don't bubble, they are caught while capturing.
+            .event_handler_with_options(
+                EventOptions::new().parents_first(),
+                clone!((state, anchor) move |_: events::Focus| state.open(&anchor, 0)),
+            )
+            .event_handler_with_options(
+                EventOptions::new().parents_first(),
+                clone!((state, anchor) move |_: events::Blur| state.close(&anchor)),
+            )
+            .event_handler(clone!((state, anchor) move |event: events::KeyDown| {
+                if event.key() == "Escape" {
+                    state.close(&anchor);
+                }
+            }))
+            .after_remove(clone!((state) move |_| state.close(&anchor)));
+        Self {
+            state,
+            raw_el,
+            flags: PhantomData,
+        }
+    }
+}
+
+impl<AnchorFlag, ContentFlag, RE: RawEl> RawElWrapper for Tooltip<AnchorFlag, ContentFlag, RE> {
+    type RawEl = RE;
+
+    fn raw_el_mut(&mut self) -> &mut Self::RawEl {
+        &mut self.raw_el
+    }
+}
+
+// ------ ------
+//   Abilities
+// ------ ------
+
+impl<AnchorFlag, ContentFlag, RE: RawEl> Styleable<'_> for Tooltip<AnchorFlag, ContentFlag, RE> {}
+impl<AnchorFlag, ContentFlag, RE: RawEl> HasIds for Tooltip<AnchorFlag, ContentFlag, RE> {}
+
+// ------ ------
+//  Attributes
+// ------ ------
+
+impl<'a, AnchorFlag, ContentFlag, RE: RawEl> Tooltip<AnchorFlag, ContentFlag, RE> {
+    /// Described element, usually a focusable one like `Button` or `Link`.
+    pub fn anchor(
+        mut self,
+        anchor: impl IntoElement<'a> + 'a,
+    ) -> Tooltip<AnchorFlagSet, ContentFlag, RE>
+    where
+        AnchorFlag: FlagNotSet,
+    {
+        self.raw_el = self.raw_el.child(anchor);
+        self.into_type()
+    }
+
+    pub fn text(self, text: impl IntoCowStr<'a>) -> Tooltip<AnchorFlag, ContentFlagSet, RE>
+    where
+        ContentFlag: FlagNotSet,
+    {
+        let text = text.into_cow_str().into_owned();
+        self.content(move || Text::new(&text))
+    }
+
+    /// `content` is called every time the tooltip is displayed.
+    pub fn content<IE: IntoElement<'a> + 'a>(
+        self,
+        mut content: impl FnMut() -> IE + 'static,
+    ) -> Tooltip<AnchorFlag, ContentFlagSet, RE>
+    where
+        ContentFlag: FlagNotSet,
+    {
+        let id = self.state.id.clone();
+        self.state.content.set_renderer(move || {
+            RawHtmlEl::<web_sys::HtmlElement>::new("div")
+                .class("tooltip")
+                .attr("role", "tooltip")
+                .attr("id", &id)
+                .child(content())
+        });
+        self.into_type()
+    }
+
+    /// Preferred side of the anchor, `Placement::Above` by default.
+    pub fn placement(self, placement: Placement) -> Self {
+        self.state.content.placement.set(placement);
+        self
+    }
+
+    /// Distance between the anchor and the tooltip, `4` by default.
+    pub fn gap(self, gap: u32) -> Self {
+        self.state.content.gap.set(gap);
+        self
+    }
+
+    /// Hover duration before the tooltip is displayed, `500` ms by default.
+    pub fn delay(self, delay_ms: u32) -> Self {
+        self.state.delay.set(delay_ms);
+        self
+    }
+
+    fn into_type<NewAnchorFlag, NewContentFlag>(
+        self,
+    ) -> Tooltip<NewAnchorFlag, NewContentFlag, RE> {
+        Tooltip {
+            state: self.state,
+            raw_el: self.raw_el,
+            flags: PhantomData,
+        }
+    }
+}
+
+// ------ ------
+//     Extra
+// ------ ------
+
+struct TooltipState {
+    content: AnchoredContent,
+    delay: Cell<u32>,
+    id: String,
+    opener: RefCell<Option<TaskHandle>>,
+}
+
+impl TooltipState {
+    fn open(self: &Rc<Self>, anchor: &web_sys::Element, delay_ms: u32) {
+        let state = Rc::clone(self);
+        let anchor = anchor.clone();
+        *self.opener.borrow_mut() = Some(Task::start_droppable(async move {
+            Timer::sleep(delay_ms).await;
+            state.content.set_open(&anchor, true);
+            update_described_by(&anchor, |ids| {
+                if not(ids.contains(&state.id)) {
+                    ids.push(state.id.clone());
+                }
+            });
+        }));
+    }
+
+    fn close(&self, anchor: &web_sys::Element) {
+        self.opener.borrow_mut().take();
+        self.content.close();
+        update_described_by(anchor, |ids| ids.retain(|id| *id != self.id));
+    }
+}
+
+/// Updates only the tooltip's id in `aria-describedby`,
+/// other ids (e.g. set by `Accessible::described_by`) are kept.
+fn update_described_by(anchor: &web_sys::Element, update: impl FnOnce(&mut Vec<String>)) {
+    let element = described_element(anchor);
+    let described_by = element
+        .get_attribute("aria-describedby")
+        .unwrap_or_default();
+    let mut ids = described_by
+        .split_ascii_whitespace()
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    update(&mut ids);
+    if ids.is_empty() {
+        let _ = element.remove_attribute("aria-describedby");
+    } else {
+        element
+            .set_attribute("aria-describedby", &ids.join(" "))
+            .unwrap_throw();
+    }
+}
+
+/// The anchor child element, the tooltip anchor container doesn't have a role.
+fn described_element(anchor: &web_sys::Element) -> web_sys::Element {
+    anchor
+        .first_element_child()
+        .unwrap_or_else(|| anchor.clone())
+}
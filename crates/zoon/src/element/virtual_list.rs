@@ -0,0 +1,493 @@
+use crate::*;
+use std::{
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+    ops::Range,
+    rc::Rc,
+};
+
+const DEFAULT_ESTIMATED_ROW_HEIGHT: u32 = 40;
+const DEFAULT_OVERSCAN: usize = 5;
+
+// ------ ------
+//    Element
+// ------ ------
+
+make_flags!(Header, Items);
+
+/// Scrollable list (or grid with `columns`) that renders only the rows in the viewport.
+/// Set its height, e.g. `.s(Height::exact(600))` or `.s(Height::fill())`.
+pub struct VirtualList<T, HeaderFlag, ItemsFlag, RE: RawEl> {
+    layout: Rc<Layout>,
+    items: MutableVec<T>,
+    raw_el: RE,
+    flags: PhantomData<(HeaderFlag, ItemsFlag)>,
+}
+
+impl<T, HeaderFlag, RE: RawEl> Element for VirtualList<T, HeaderFlag, ItemsFlagSet, RE> {}
+
+impl<T: Clone + 'static>
+    VirtualList<T, HeaderFlagNotSet, ItemsFlagNotSet, RawHtmlEl<web_sys::HtmlDivElement>>
+{
+    #[track_caller]
+    pub fn new() -> Self {
+        let layout = Rc::new(Layout::default());
+        let raw_el = RawHtmlEl::<web_sys::HtmlDivElement>::new("div")
+            .class("virtual_list")
+            .style("display", "flex")
+            .style("flex-direction", "column")
+            .style("overflow-y", "auto")
+            .style("position", "relative");
+        let dom_element = raw_el.dom_element();
+        let raw_el = raw_el
+            .event_handler(clone!((layout) move |_: events::Scroll| {
+                let scroll_top = u32::try_from(dom_element.scroll_top()).unwrap_or_default();
+                layout.scroll_top.set_neq(scroll_top);
+            }))
+            .on_resize(clone!((layout) move |_, height| layout.viewport_height.set_neq(height)));
+        Self {
+            layout,
+            items: MutableVec::new(),
+            raw_el,
+            flags: PhantomData,
+        }
+    }
+}
+
+impl<T, HeaderFlag, ItemsFlag, RE: RawEl> RawElWrapper
+    for VirtualList<T, HeaderFlag, ItemsFlag, RE>
+{
+    type RawEl = RE;
+
+    fn raw_el_mut(&mut self) -> &mut Self::RawEl {
+        &mut self.raw_el
+    }
+}
+
+// ------ ------
+//   Abilities
+// ------ ------
+
+impl<T, HeaderFlag, ItemsFlag, RE: RawEl> Styleable<'_>
+    for VirtualList<T, HeaderFlag, ItemsFlag, RE>
+{
+}
+impl<T, HeaderFlag, ItemsFlag, RE: RawEl> KeyboardEventAware
+    for VirtualList<T, HeaderFlag, ItemsFlag, RE>
+{
+}
+impl<T, HeaderFlag, ItemsFlag, RE: RawEl> MouseEventAware
+    for VirtualList<T, HeaderFlag, ItemsFlag, RE>
+{
+}
+impl<T, HeaderFlag, ItemsFlag, RE: RawEl> PointerEventAware
+    for VirtualList<T, HeaderFlag, ItemsFlag, RE>
+{
+}
+impl<T, HeaderFlag, ItemsFlag, RE: RawEl> TouchEventAware
+    for VirtualList<T, HeaderFlag, ItemsFlag, RE>
+{
+}
+impl<T, HeaderFlag, ItemsFlag, RE: RawEl> Draggable for VirtualList<T, HeaderFlag, ItemsFlag, RE> {}
+impl<T, HeaderFlag, ItemsFlag, RE: RawEl> DropTarget for VirtualList<T, HeaderFlag, ItemsFlag, RE> {}
+impl<T, HeaderFlag, ItemsFlag, RE: RawEl> MutableViewport
+    for VirtualList<T, HeaderFlag, ItemsFlag, RE>
+{
+}
+impl<T, HeaderFlag, ItemsFlag, RE: RawEl> ResizableViewport
+    for VirtualList<T, HeaderFlag, ItemsFlag, RE>
+{
+}
+impl<T, HeaderFlag, ItemsFlag, RE: RawEl> AddNearbyElement<'_>
+    for VirtualList<T, HeaderFlag, ItemsFlag, RE>
+{
+}
+impl<T, HeaderFlag, ItemsFlag, RE: RawEl> HasIds for VirtualList<T, HeaderFlag, ItemsFlag, RE> {}
+impl<T, HeaderFlag, ItemsFlag, RE: RawEl> Accessible for VirtualList<T, HeaderFlag, ItemsFlag, RE> {}
+
+// ------ ------
+//  Attributes
+// ------ ------
+
+impl<'a, T: Clone + 'static, HeaderFlag, ItemsFlag, RE: RawEl>
+    VirtualList<T, HeaderFlag, ItemsFlag, RE>
+{
+    /// Height used for rows that haven't been rendered yet, `40` by default.
+    /// Rendered rows are measured, so their heights may differ.
+    pub fn estimated_row_height(self, height: u32) -> Self {
+        self.layout.estimated_row_height.set(height);
+        self.layout.invalidate_row_offsets();
+        self.layout.relayout();
+        self
+    }
+
+    /// Number of rows rendered above and below the viewport, `5` by default.
+    pub fn overscan(self, rows: usize) -> Self {
+        self.layout.overscan.set(rows);
+        self.layout.relayout();
+        self
+    }
+
+    /// Renders items in rows of `columns` equally wide cells.
+    pub fn columns(self, columns: usize) -> Self {
+        self.layout.columns.set(columns.max(1));
+        self.layout.row_heights.borrow_mut().clear();
+        self.layout.invalidate_row_offsets();
+        self.layout.rerender.set(true);
+        self.layout.relayout();
+        self
+    }
+
+    /// Sticky header above the rows, e.g. column names of a table.
+    pub fn header(
+        mut self,
+        header: impl IntoElement<'a> + 'a,
+    ) -> VirtualList<T, HeaderFlagSet, ItemsFlag, RE>
+    where
+        HeaderFlag: FlagNotSet,
+        ItemsFlag: FlagNotSet,
+    {
+        let layout = Rc::clone(&self.layout);
+        self.raw_el = self.raw_el.child(
+            RawHtmlEl::<web_sys::HtmlDivElement>::new("div")
+                .class("virtual_list_header")
+                .style("position", "sticky")
+                .style("top", "0")
+                .style("z-index", "1")
+                .on_resize(move |_, height| layout.header_height.set_neq(height))
+                .child(header),
+        );
+        self.into_type()
+    }
+
+    /// Scrolls to the row with the item at the given index.
+    pub fn scroll_to_index_signal(
+        mut self,
+        index: impl Signal<Item = Option<usize>> + Unpin + 'static,
+    ) -> Self {
+        let layout = Rc::clone(&self.layout);
+        let dom_element: web_sys::Element = self.raw_el.dom_element().into();
+        let scroller = Task::start_droppable(index.for_each_sync(move |index| {
+            if let Some(index) = index {
+                let offset = layout.row_offset(index / layout.columns.get());
+                dom_element.set_scroll_top(i32::try_from(offset).unwrap_or(i32::MAX));
+            }
+        }));
+        self.raw_el = self.raw_el.after_remove(move |_| drop(scroller));
+        self
+    }
+
+    /// Items are rendered by `item_renderer` only when their row is close to the viewport.
+    pub fn items_signal_vec<IE: IntoElement<'a> + 'a>(
+        mut self,
+        items: impl SignalVec<Item = T> + 'static,
+        item_renderer: impl Fn(T) -> IE + 'static,
+    ) -> VirtualList<T, HeaderFlag, ItemsFlagSet, RE>
+    where
+        ItemsFlag: FlagNotSet,
+    {
+        let layout = Rc::clone(&self.layout);
+        let list_items = self.items.clone();
+        let items_updater = Task::start_droppable(items.for_each(move |diff| {
+            layout.on_items_diff(&diff);
+            let mut list_items = list_items.lock_mut();
+            MutableVecLockMut::apply_vec_diff(&mut list_items, diff);
+            layout.item_count.set(list_items.len());
+            layout.invalidate_row_offsets();
+            layout.rerender.set(true);
+            layout.relayout();
+            async {}
+        }));
+
+        let rows = MutableVec::<usize>::new();
+        let offset = Mutable::new(0);
+        let total_height = Mutable::new(0);
+        let layout = Rc::clone(&self.layout);
+        let rows_updater = Task::start_droppable(
+            map_ref! {
+                let _scroll_top = layout.scroll_top.signal(),
+                let _viewport_height = layout.viewport_height.signal(),
+                let _header_height = layout.header_height.signal(),
+                let _version = layout.version.signal() => ()
+            }
+            .for_each_sync(clone!((rows, offset, total_height) move |_| {
+                let window = layout.window();
+                offset.set_neq(window.offset);
+                total_height.set_neq(window.total_height);
+                if layout.rerender.replace(false) {
+                    rows.lock_mut().replace_cloned(window.rows.collect());
+                } else {
+                    update_rows(&mut rows.lock_mut(), window.rows);
+                }
+            })),
+        );
+
+        let item_renderer: ItemRenderer<T> =
+            Rc::new(move |item| item_renderer(item).into_element().into_raw());
+        let layout = Rc::clone(&self.layout);
+        let items = self.items.clone();
+        let window = RawHtmlEl::<web_sys::HtmlDivElement>::new("div")
+            .class("virtual_list_window")
+            .style_signal(
+                "transform",
+                offset
+                    .signal()
+                    .map(|offset| format!("translateY({offset}px)")),
+            )
+            .children_signal_vec(
+                rows.signal_vec()
+                    .map(move |row| row_el(&layout, &items, &item_renderer, row)),
+            );
+        let scene = RawHtmlEl::<web_sys::HtmlDivElement>::new("div")
+            .class("virtual_list_scene")
+            .style("flex-shrink", "0")
+            .style_signal("height", total_height.signal().map(px))
+            .child(window);
+
+        self.raw_el = self.raw_el.child(scene).after_remove(move |_| {
+            drop(items_updater);
+            drop(rows_updater);
+        });
+        self.into_type()
+    }
+
+    fn into_type<NewHeaderFlag, NewItemsFlag>(
+        self,
+    ) -> VirtualList<T, NewHeaderFlag, NewItemsFlag, RE> {
+        VirtualList {
+            layout: self.layout,
+            items: self.items,
+            raw_el: self.raw_el,
+            flags: PhantomData,
+        }
+    }
+}
+
+// ------ ------
+//     Extra
+// ------ ------
+
+type ItemRenderer<T> = Rc<dyn Fn(T) -> RawElOrText>;
+
+// ------ Layout ------
+
+struct Layout {
+    columns: Cell<usize>,
+    estimated_row_height: Cell<u32>,
+    overscan: Cell<usize>,
+    item_count: Cell<usize>,
+    // Measured heights of rendered rows, `None` when not measured yet.
+    row_heights: RefCell<Vec<Option<u32>>>,
+    // Prefix sums of row heights with the total height at the end, `None` when invalidated.
+    row_offsets: RefCell<Option<Vec<u32>>>,
+    // Rendered rows have to be replaced because their items have changed.
+    rerender: Cell<bool>,
+    scroll_top: Mutable<u32>,
+    viewport_height: Mutable<u32>,
+    // The sticky header covers the top of the viewport.
+    header_height: Mutable<u32>,
+    version: Mutable<u64>,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            columns: Cell::new(1),
+            estimated_row_height: Cell::new(DEFAULT_ESTIMATED_ROW_HEIGHT),
+            overscan: Cell::new(DEFAULT_OVERSCAN),
+            item_count: Cell::new(0),
+            row_heights: RefCell::default(),
+            row_offsets: RefCell::default(),
+            rerender: Cell::new(false),
+            scroll_top: Mutable::new(0),
+            viewport_height: Mutable::new(0),
+            header_height: Mutable::new(0),
+            version: Mutable::new(0),
+        }
+    }
+}
+
+struct Window {
+    rows: Range<usize>,
+    offset: u32,
+    total_height: u32,
+}
+
+impl Layout {
+    fn relayout(&self) {
+        self.version.replace_with(|version| version.wrapping_add(1));
+    }
+
+    fn row_count(&self) -> usize {
+        let columns = self.columns.get();
+        (self.item_count.get() + columns - 1) / columns
+    }
+
+    fn row_height(&self, row_heights: &[Option<u32>], row: usize) -> u32 {
+        row_heights
+            .get(row)
+            .copied()
+            .flatten()
+            .unwrap_or_else(|| self.estimated_row_height.get())
+    }
+
+    fn row_offset(&self, row: usize) -> u32 {
+        self.with_row_offsets(|row_offsets| row_offsets[row.min(row_offsets.len() - 1)])
+    }
+
+    /// Row offsets are computed only after measurements or items have changed, not on scroll.
+    fn with_row_offsets<R>(&self, f: impl FnOnce(&[u32]) -> R) -> R {
+        let mut row_offsets = self.row_offsets.borrow_mut();
+        let row_offsets = row_offsets.get_or_insert_with(|| {
+            let row_heights = self.row_heights.borrow();
+            let mut total_height = 0;
+            let mut row_offsets = Vec::with_capacity(self.row_count() + 1);
+            row_offsets.push(total_height);
+            for row in 0..self.row_count() {
+                total_height += self.row_height(&row_heights, row);
+                row_offsets.push(total_height);
+            }
+            row_offsets
+        });
+        f(row_offsets)
+    }
+
+    fn invalidate_row_offsets(&self) {
+        self.row_offsets.borrow_mut().take();
+    }
+
+    fn set_row_height(&self, row: usize, height: u32) {
+        let mut row_heights = self.row_heights.borrow_mut();
+        if row_heights.len() <= row {
+            row_heights.resize(row + 1, None);
+        }
+        if row_heights[row] != Some(height) {
+            row_heights[row] = Some(height);
+            drop(row_heights);
+            self.invalidate_row_offsets();
+            self.relayout();
+        }
+    }
+
+    /// Keeps measured heights aligned with rows. Heights are forgotten
+    /// when items move between rows of a grid.
+    fn on_items_diff<T>(&self, diff: &VecDiff<T>) {
+        let mut row_heights = self.row_heights.borrow_mut();
+        if self.columns.get() > 1 {
+            if not(matches!(
+                diff,
+                VecDiff::UpdateAt { .. } | VecDiff::Push { .. }
+            )) {
+                row_heights.clear();
+            }
+            return;
+        }
+        match diff {
+            VecDiff::Replace { .. } | VecDiff::Clear {} => row_heights.clear(),
+            VecDiff::InsertAt { index, .. } if *index <= row_heights.len() => {
+                row_heights.insert(*index, None)
+            }
+            VecDiff::RemoveAt { index } if *index < row_heights.len() => {
+                row_heights.remove(*index);
+            }
+            VecDiff::UpdateAt { index, .. } if *index < row_heights.len() => {
+                row_heights[*index] = None
+            }
+            VecDiff::Move {
+                old_index,
+                new_index,
+            } => {
+                let max_index = (*old_index).max(*new_index);
+                if row_heights.len() <= max_index {
+                    row_heights.resize(max_index + 1, None);
+                }
+                let height = row_heights.remove(*old_index);
+                row_heights.insert(*new_index, height);
+            }
+            VecDiff::Pop {} => {
+                row_heights.truncate(self.item_count.get().saturating_sub(1));
+            }
+            _ => (),
+        }
+    }
+
+    /// Rows intersecting the viewport extended by `overscan`.
+    fn window(&self) -> Window {
+        let top = self.scroll_top.get();
+        let bottom = top
+            + self
+                .viewport_height
+                .get()
+                .saturating_sub(self.header_height.get());
+        self.with_row_offsets(|row_offsets| {
+            let row_count = row_offsets.len() - 1;
+            // The first row ending below `top` and the first row starting at or below `bottom`.
+            let first_visible = row_offsets[1..].partition_point(|row_end| *row_end <= top);
+            let last_visible =
+                row_offsets[..row_count].partition_point(|row_start| *row_start < bottom);
+
+            let overscan = self.overscan.get();
+            let start = first_visible.saturating_sub(overscan);
+            let end = (last_visible + overscan).min(row_count).max(start);
+            Window {
+                rows: start..end,
+                offset: row_offsets[start],
+                total_height: row_offsets[row_count],
+            }
+        })
+    }
+}
+
+// ------ rows ------
+
+/// Removes and inserts only the rows entering or leaving the window.
+fn update_rows(rows: &mut MutableVecLockMut<usize>, window: Range<usize>) {
+    let current = match (rows.first(), rows.last()) {
+        (Some(first), Some(last)) => *first..*last + 1,
+        _ => 0..0,
+    };
+    if current.end <= window.start || window.end <= current.start {
+        return rows.replace_cloned(window.collect());
+    }
+    while rows.last().is_some_and(|row| *row >= window.end) {
+        rows.pop();
+    }
+    while rows.first().is_some_and(|row| *row < window.start) {
+        rows.remove(0);
+    }
+    for row in (window.start..current.start).rev() {
+        rows.insert(0, row);
+    }
+    for row in current.end..window.end {
+        rows.push(row);
+    }
+}
+
+fn row_el<T: Clone + 'static>(
+    layout: &Rc<Layout>,
+    items: &MutableVec<T>,
+    item_renderer: &ItemRenderer<T>,
+    row: usize,
+) -> RawHtmlEl<web_sys::HtmlDivElement> {
+    let columns = layout.columns.get();
+    let row_items = {
+        let items = items.lock_ref();
+        let start = (row * columns).min(items.len());
+        let end = (start + columns).min(items.len());
+        items[start..end].to_vec()
+    };
+    let raw_el = RawHtmlEl::<web_sys::HtmlDivElement>::new("div").class("virtual_list_row");
+    let raw_el = if columns > 1 {
+        raw_el.style("display", "grid").style(
+            "grid-template-columns",
+            &format!("repeat({columns}, minmax(0, 1fr))"),
+        )
+    } else {
+        raw_el
+            .style("display", "flex")
+            .style("flex-direction", "column")
+    };
+    raw_el
+        .on_resize(clone!((layout) move |_, height| layout.set_row_height(row, height)))
+        .children(row_items.into_iter().map(item_renderer.as_ref()))
+}
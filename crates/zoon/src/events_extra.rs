@@ -91,6 +91,11 @@ make_mouse_event!(MouseOut, "mouseout");
 
 make_event!(WheelEvent, "wheel" => web_sys::WheelEvent);
 
+// dialog events
+
+make_event!(DialogCancel, "cancel" => web_sys::Event);
+make_event!(DialogClose, "close" => web_sys::Event);
+
 // WheelEvent is a subtype of MouseEvent. It implements what MouseEvent implements plus
 impl WheelEvent {
     #[inline]
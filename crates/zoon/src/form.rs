@@ -0,0 +1,452 @@
+//! Form fields with parsing, validation and dirty / touched tracking.
+//!
+//! Bind fields to elements with `TextInput::field`, `TextArea::field` or `Checkbox::field`
+//! and group them in a `Form` to enable the submit button.
+
+use crate::*;
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+};
+
+/// Async validators run after the value hasn't changed for this time.
+pub const ASYNC_VALIDATION_DEBOUNCE_MS: u32 = 300;
+
+type SyncValidator<T> = Arc<dyn Fn(&T) -> Result<(), String> + Send + Sync>;
+type AsyncValidator<T> =
+    Arc<dyn Fn(T) -> Pin<Box<dyn Future<Output = Result<(), String>>>> + Send + Sync>;
+
+type BoolSignal = Pin<Box<dyn Signal<Item = bool>>>;
+
+struct Validators<T> {
+    sync_validators: Vec<SyncValidator<T>>,
+    async_validators: Vec<AsyncValidator<T>>,
+}
+
+// ------ FieldInput ------
+
+/// Conversion between a field value and the text of `TextInput` or `TextArea`.
+pub trait FieldInput: Sized {
+    fn to_input(&self) -> String;
+
+    fn from_input(input: &str) -> Result<Self, String>;
+}
+
+impl FieldInput for String {
+    fn to_input(&self) -> String {
+        self.clone()
+    }
+
+    fn from_input(input: &str) -> Result<Self, String> {
+        Ok(input.to_owned())
+    }
+}
+
+/// Empty input is `None`.
+impl<T: FieldInput> FieldInput for Option<T> {
+    fn to_input(&self) -> String {
+        self.as_ref().map(T::to_input).unwrap_or_default()
+    }
+
+    fn from_input(input: &str) -> Result<Self, String> {
+        if input.trim().is_empty() {
+            return Ok(None);
+        }
+        T::from_input(input).map(Some)
+    }
+}
+
+macro_rules! make_field_input_impls {
+    ($($type:ty),*) => (
+        $(
+        impl FieldInput for $type {
+            fn to_input(&self) -> String {
+                self.to_string()
+            }
+
+            fn from_input(input: &str) -> Result<Self, String> {
+                input.trim().parse().map_err(|_| String::from("Invalid number"))
+            }
+        }
+        )*
+    )
+}
+make_field_input_impls!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+// ------ Field ------
+
+pub struct Field<T> {
+    value: Mutable<T>,
+    initial_value: Arc<T>,
+    // Text entered by the user, `None` when the value has been set by `set` or `reset`.
+    input: Mutable<Option<String>>,
+    parse_error: Mutable<Option<String>>,
+    validation_errors: Mutable<Vec<String>>,
+    touched: Mutable<bool>,
+    validating: Mutable<bool>,
+    validation_generation: Arc<AtomicU64>,
+    // Set when the first validation starts, see `validate_initially`.
+    validation_started: Arc<AtomicBool>,
+    // Shared by all clones, e.g. by the one bound to `TextInput::field`.
+    validators: Arc<RwLock<Validators<T>>>,
+}
+
+impl<T> Clone for Field<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            initial_value: Arc::clone(&self.initial_value),
+            input: self.input.clone(),
+            parse_error: self.parse_error.clone(),
+            validation_errors: self.validation_errors.clone(),
+            touched: self.touched.clone(),
+            validating: self.validating.clone(),
+            validation_generation: Arc::clone(&self.validation_generation),
+            validation_started: Arc::clone(&self.validation_started),
+            validators: Arc::clone(&self.validators),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Field<T> {
+    pub fn new(initial_value: T) -> Self {
+        Self {
+            value: Mutable::new(initial_value.clone()),
+            initial_value: Arc::new(initial_value),
+            input: Mutable::new(None),
+            parse_error: Mutable::new(None),
+            validation_errors: Mutable::new(Vec::new()),
+            touched: Mutable::new(false),
+            validating: Mutable::new(false),
+            validation_generation: Arc::new(AtomicU64::new(0)),
+            validation_started: Arc::new(AtomicBool::new(false)),
+            validators: Arc::new(RwLock::new(Validators {
+                sync_validators: Vec::new(),
+                async_validators: Vec::new(),
+            })),
+        }
+    }
+
+    /// Return `Err` with a message to display when the value is invalid.
+    pub fn validator(
+        self,
+        validator: impl Fn(&T) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.validators
+            .write()
+            .unwrap_throw()
+            .sync_validators
+            .push(Arc::new(validator));
+        self
+    }
+
+    /// Runs when the sync validators pass, e.g. to ask Moon through `Connection::exchange_msgs`
+    /// whether the username is still available.
+    pub fn async_validator<F>(self, validator: impl Fn(T) -> F + Send + Sync + 'static) -> Self
+    where
+        F: Future<Output = Result<(), String>> + 'static,
+    {
+        self.validators
+            .write()
+            .unwrap_throw()
+            .async_validators
+            .push(Arc::new(move |value| Box::pin(validator(value))));
+        self
+    }
+
+    pub fn value(&self) -> T {
+        self.value.get_cloned()
+    }
+
+    pub fn value_signal(&self) -> impl Signal<Item = T> + Unpin {
+        self.value.signal_cloned()
+    }
+
+    pub fn set(&self, value: T) {
+        self.input.set_neq(None);
+        self.parse_error.set_neq(None);
+        self.value.set_neq(value);
+        self.changed();
+    }
+
+    /// Sets the value parsed from the text entered by the user.
+    pub fn set_input(&self, input: &str)
+    where
+        T: FieldInput,
+    {
+        self.input.set(Some(input.to_owned()));
+        match T::from_input(input) {
+            Ok(value) => {
+                self.parse_error.set_neq(None);
+                self.value.set_neq(value);
+            }
+            Err(error) => self.parse_error.set_neq(Some(error)),
+        }
+        self.changed();
+    }
+
+    /// The text entered by the user or the formatted value.
+    pub fn input_signal(&self) -> impl Signal<Item = String> + Unpin
+    where
+        T: FieldInput,
+    {
+        map_ref! {
+            let value = self.value.signal_cloned(),
+            let input = self.input.signal_cloned() =>
+            input.clone().unwrap_or_else(|| value.to_input())
+        }
+    }
+
+    /// Marks the field as visited, usually on blur.
+    pub fn touch(&self) {
+        self.touched.set_neq(true);
+    }
+
+    pub fn touched_signal(&self) -> impl Signal<Item = bool> + Unpin {
+        self.touched.signal()
+    }
+
+    pub fn is_dirty_signal(&self) -> impl Signal<Item = bool> + Unpin {
+        let initial_value = Arc::clone(&self.initial_value);
+        self.value
+            .signal_ref(move |value| value != &*initial_value)
+            .dedupe()
+    }
+
+    pub fn is_validating_signal(&self) -> impl Signal<Item = bool> + Unpin {
+        self.validate_initially();
+        self.validating.signal()
+    }
+
+    /// The parse error followed by validation errors.
+    pub fn errors_signal(&self) -> impl Signal<Item = Vec<String>> + Unpin {
+        self.validate_initially();
+        map_ref! {
+            let parse_error = self.parse_error.signal_cloned(),
+            let validation_errors = self.validation_errors.signal_cloned() =>
+            parse_error.iter().chain(validation_errors).cloned().collect::<Vec<_>>()
+        }
+    }
+
+    /// `errors_signal` output once the field has been touched.
+    pub fn visible_errors_signal(&self) -> impl Signal<Item = Vec<String>> + Unpin {
+        map_ref! {
+            let touched = self.touched.signal(),
+            let errors = self.errors_signal() =>
+            if *touched { errors.clone() } else { Vec::new() }
+        }
+    }
+
+    /// `false` also while async validators are running.
+    pub fn is_valid_signal(&self) -> impl Signal<Item = bool> + Unpin {
+        self.validate_initially();
+        map_ref! {
+            let has_parse_error = self.parse_error.signal_ref(Option::is_some),
+            let has_validation_errors = self.validation_errors.signal_ref(|errors| not(errors.is_empty())),
+            let validating = self.validating.signal() =>
+            not(*has_parse_error || *has_validation_errors || *validating)
+        }
+        .dedupe()
+    }
+
+    pub fn reset(&self) {
+        self.set(T::clone(&self.initial_value));
+        self.touched.set_neq(false);
+    }
+
+    /// Runs all validators immediately and returns `true` when the value is valid.
+    pub async fn validate(&self) -> bool {
+        self.validation_started.store(true, Ordering::SeqCst);
+        self.run_validators(false).await
+    }
+
+    /// Validates the initial value once, when the validation state is read for the first time.
+    fn validate_initially(&self) {
+        if not(self.validation_started.load(Ordering::SeqCst)) {
+            self.changed();
+        }
+    }
+
+    fn changed(&self) {
+        self.validation_started.store(true, Ordering::SeqCst);
+        let this = self.clone();
+        Task::start(async move {
+            this.run_validators(true).await;
+        });
+    }
+
+    async fn run_validators(&self, debounce: bool) -> bool {
+        let generation = self.validation_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let is_current = || self.validation_generation.load(Ordering::SeqCst) == generation;
+
+        if self.parse_error.lock_ref().is_some() {
+            self.validation_errors.set(Vec::new());
+            self.validating.set_neq(false);
+            return false;
+        }
+        let value = self.value.get_cloned();
+        // Cloned so validators can be added while async validators are running.
+        let (sync_validators, async_validators) = {
+            let validators = self.validators.read().unwrap_throw();
+            (
+                validators.sync_validators.clone(),
+                validators.async_validators.clone(),
+            )
+        };
+        let errors = sync_validators
+            .iter()
+            .filter_map(|validator| validator(&value).err())
+            .collect::<Vec<_>>();
+        if not(errors.is_empty()) || async_validators.is_empty() {
+            let is_valid = errors.is_empty();
+            self.validation_errors.set(errors);
+            self.validating.set_neq(false);
+            return is_valid;
+        }
+
+        self.validation_errors.set(Vec::new());
+        self.validating.set_neq(true);
+        if debounce {
+            Timer::sleep(ASYNC_VALIDATION_DEBOUNCE_MS).await;
+            if not(is_current()) {
+                return false;
+            }
+        }
+        let mut errors = Vec::new();
+        for validator in &async_validators {
+            if let Err(error) = validator(value.clone()).await {
+                errors.push(error);
+            }
+        }
+        let is_valid = errors.is_empty();
+        // Results of outdated validations are dropped.
+        if is_current() {
+            self.validation_errors.set(errors);
+            self.validating.set_neq(false);
+        }
+        is_valid
+    }
+
+    pub(crate) fn aria_invalid_signal(&self) -> impl Signal<Item = Option<&'static str>> + Unpin {
+        self.visible_errors_signal()
+            .map(|errors| (not(errors.is_empty())).then_some("true"))
+    }
+}
+
+// ------ Form ------
+
+#[derive(Clone, Default)]
+pub struct Form {
+    fields: Vec<Arc<dyn FormField>>,
+}
+
+impl Form {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn field<T: Clone + PartialEq + Send + Sync + 'static>(mut self, field: &Field<T>) -> Self {
+        self.fields.push(Arc::new(field.clone()));
+        self
+    }
+
+    pub fn is_valid_signal(&self) -> impl Signal<Item = bool> + Unpin {
+        all(self.fields.iter().map(|field| field.is_valid_signal()))
+    }
+
+    pub fn is_dirty_signal(&self) -> impl Signal<Item = bool> + Unpin {
+        any(self.fields.iter().map(|field| field.is_dirty_signal()))
+    }
+
+    pub fn is_validating_signal(&self) -> impl Signal<Item = bool> + Unpin {
+        any(self.fields.iter().map(|field| field.is_validating_signal()))
+    }
+
+    /// Touches and validates all fields. Call it before sending the form data.
+    pub async fn validate(&self) -> bool {
+        let mut is_valid = true;
+        for field in &self.fields {
+            field.touch();
+            is_valid &= field.validate().await;
+        }
+        is_valid
+    }
+
+    pub fn reset(&self) {
+        for field in &self.fields {
+            field.reset();
+        }
+    }
+}
+
+// ------ FormField ------
+
+trait FormField: Send + Sync {
+    fn is_valid_signal(&self) -> BoolSignal;
+
+    fn is_dirty_signal(&self) -> BoolSignal;
+
+    fn is_validating_signal(&self) -> BoolSignal;
+
+    fn validate(&self) -> Pin<Box<dyn Future<Output = bool>>>;
+
+    fn touch(&self);
+
+    fn reset(&self);
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> FormField for Field<T> {
+    fn is_valid_signal(&self) -> BoolSignal {
+        Box::pin(Field::is_valid_signal(self))
+    }
+
+    fn is_dirty_signal(&self) -> BoolSignal {
+        Box::pin(Field::is_dirty_signal(self))
+    }
+
+    fn is_validating_signal(&self) -> BoolSignal {
+        Box::pin(Field::is_validating_signal(self))
+    }
+
+    fn validate(&self) -> Pin<Box<dyn Future<Output = bool>>> {
+        let this = self.clone();
+        Box::pin(async move { this.validate().await })
+    }
+
+    fn touch(&self) {
+        Field::touch(self)
+    }
+
+    fn reset(&self) {
+        Field::reset(self)
+    }
+}
+
+// ------ helpers ------
+
+fn all(signals: impl Iterator<Item = BoolSignal>) -> impl Signal<Item = bool> + Unpin {
+    signals
+        .fold(Box::pin(always(true)) as BoolSignal, |all, signal| {
+            Box::pin(map_ref! {
+                let all = all,
+                let value = signal =>
+                *all && *value
+            })
+        })
+        .dedupe()
+}
+
+fn any(signals: impl Iterator<Item = BoolSignal>) -> impl Signal<Item = bool> + Unpin {
+    signals
+        .fold(Box::pin(always(false)) as BoolSignal, |any, signal| {
+            Box::pin(map_ref! {
+                let any = any,
+                let value = signal =>
+                *any || *value
+            })
+        })
+        .dedupe()
+}
@@ -0,0 +1,144 @@
+//! Translations and locale-aware formatting bound to the current language.
+//!
+//! ```no_run
+//! use zoon::{*, i18n::{self, Args}};
+//!
+//! i18n::add_ftl(Lang::English, "unread = { $count ->\n [one] One message\n *[other] { $count } messages\n}").unwrap();
+//! i18n::add_ftl(Lang::Czech, "unread = { $count ->\n [one] Jedna zpráva\n [few] { $count } zprávy\n *[other] { $count } zpráv\n}").unwrap();
+//!
+//! let count = Mutable::new(3);
+//! Text::with_signal(i18n::t_with_signal("unread", count.signal().map(|count| Args::new().set("count", count))));
+//!
+//! i18n::current_lang().set(Lang::Czech);
+//! ```
+
+use crate::*;
+use std::{borrow::Cow, sync::Mutex};
+
+pub use lang::{format, ArgValue, Args, Catalog, CatalogError};
+
+static CURRENT_LANG: Lazy<Mutable<Lang>> = Lazy::new(|| {
+    let lang = window()
+        .navigator()
+        .language()
+        .and_then(|tag| Lang::new(&tag).ok())
+        .unwrap_or_default();
+    let current_lang = Mutable::new(lang);
+    Task::start(current_lang.signal_cloned().for_each_sync(|lang| {
+        if let Some(html) = document().document_element() {
+            html.set_attribute("lang", lang.as_str()).unwrap_throw();
+        }
+    }));
+    current_lang
+});
+
+static CATALOG: Lazy<Mutex<Catalog>> = Lazy::new(|| Mutex::new(Catalog::new(Lang::default())));
+// Incremented on every catalog change to retranslate texts.
+static CATALOG_VERSION: Lazy<Mutable<u64>> = Lazy::new(<_>::default);
+
+// ------ current_lang ------
+
+/// Language used by `t`, `translate` and the `format_*` functions.
+/// Initialized from the browser language and mirrored to the `lang` attribute of `<html>`.
+pub fn current_lang() -> &'static Mutable<Lang> {
+    &CURRENT_LANG
+}
+
+/// The best match of the browser's preferred languages (`navigator.languages`) in `available`.
+pub fn preferred_lang(available: &[Lang]) -> Option<Lang> {
+    let requested = window()
+        .navigator()
+        .languages()
+        .iter()
+        .filter_map(|tag| Lang::new(&tag.as_string()?).ok())
+        .collect::<Vec<_>>();
+    Lang::negotiate(&requested, available)
+}
+
+// ------ catalog ------
+
+/// Replaces all messages, e.g. to change the fallback language.
+pub fn set_catalog(catalog: Catalog) {
+    *CATALOG.lock().unwrap_throw() = catalog;
+    CATALOG_VERSION.replace_with(|version| *version + 1);
+}
+
+/// Adds [Fluent](https://projectfluent.org/) messages to the language, see [`Catalog::add_ftl`].
+pub fn add_ftl(lang: Lang, ftl: impl Into<String>) -> Result<(), CatalogError> {
+    let result = CATALOG.lock().unwrap_throw().add_ftl(lang, ftl);
+    CATALOG_VERSION.replace_with(|version| *version + 1);
+    result
+}
+
+/// Downloads an `.ftl` file (e.g. `public_url("locales/cs.ftl")`) and adds its messages to the language.
+pub async fn load_ftl(lang: Lang, url: impl AsRef<str>) -> Result<(), LoadFtlError> {
+    let response = JsFuture::from(window().fetch_with_str(url.as_ref()))
+        .await
+        .map_err(LoadFtlError::Fetch)?
+        .unchecked_into::<web_sys::Response>();
+    if not(response.ok()) {
+        return Err(LoadFtlError::Status(response.status()));
+    }
+    let ftl = JsFuture::from(response.text().map_err(LoadFtlError::Fetch)?)
+        .await
+        .map_err(LoadFtlError::Fetch)?
+        .as_string()
+        .unwrap_or_default();
+    add_ftl(lang, ftl).map_err(LoadFtlError::Catalog)
+}
+
+#[derive(Debug)]
+pub enum LoadFtlError {
+    Fetch(JsValue),
+    Status(u16),
+    Catalog(CatalogError),
+}
+
+// ------ translate ------
+
+/// Message `id` in the current language, it's retranslated when the language or the catalog changes.
+pub fn t(id: impl Into<Cow<'static, str>>) -> impl Signal<Item = String> + Unpin {
+    t_with(id, Args::new())
+}
+
+pub fn t_with(id: impl Into<Cow<'static, str>>, args: Args) -> impl Signal<Item = String> + Unpin {
+    t_with_signal(id, always(args))
+}
+
+/// Use for changing arguments like `$count`, the plural form is selected by the current language.
+pub fn t_with_signal(
+    id: impl Into<Cow<'static, str>>,
+    args: impl Signal<Item = Args> + Unpin,
+) -> impl Signal<Item = String> + Unpin {
+    let id = id.into();
+    map_ref! {
+        let lang = CURRENT_LANG.signal_cloned(),
+        let _version = CATALOG_VERSION.signal(),
+        let args = args =>
+        CATALOG.lock().unwrap_throw().translate(lang, &id, args)
+    }
+}
+
+/// Message `id` in the current language, see [`Catalog::translate`].
+pub fn translate(id: &str, args: &Args) -> String {
+    let lang = CURRENT_LANG.lock_ref();
+    CATALOG.lock().unwrap_throw().translate(&lang, id, args)
+}
+
+// ------ format ------
+
+/// See [`format::format_number`].
+/// Use `current_lang().signal_ref(..)` to reformat the number when the language changes.
+pub fn format_number(number: f64, fraction_digits: usize) -> String {
+    format::format_number(&CURRENT_LANG.lock_ref(), number, fraction_digits)
+}
+
+#[cfg(feature = "chrono")]
+pub fn format_date(date: NaiveDate) -> String {
+    format::format_date(&CURRENT_LANG.lock_ref(), date)
+}
+
+#[cfg(feature = "chrono")]
+pub fn format_time(time: NaiveTime) -> String {
+    format::format_time(&CURRENT_LANG.lock_ref(), time)
+}
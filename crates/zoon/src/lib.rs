@@ -1,3 +1,6 @@
+#[cfg(feature = "connection")]
+pub mod client_log;
+
 #[cfg(feature = "connection")]
 mod connection;
 
@@ -7,6 +10,10 @@ pub mod routing;
 #[cfg(feature = "web_storage")]
 pub mod web_storage;
 
+#[cfg(feature = "panic_hook")]
+pub mod panic_hook;
+
+pub mod accessibility;
 mod animation;
 mod app_event;
 mod class_id;
@@ -22,13 +29,17 @@ mod element;
 mod event_options;
 pub mod events_extra;
 mod fn_box_clone;
+pub mod form;
+pub mod i18n;
 mod index_generator;
 pub mod lazy;
+mod media_query;
 mod monotonic_ids;
 mod not;
 mod resize_observer;
 mod style;
 mod task;
+pub mod theme;
 mod timer;
 mod unify;
 mod viewport;
@@ -50,6 +61,7 @@ pub use either::{Either, IntoEither};
 pub use element::*;
 pub use event_options::EventOptions;
 pub use fn_box_clone::FnBoxClone;
+pub use form::{Field, FieldInput, Form};
 pub use futures_channel::{
     self,
     mpsc::{self, UnboundedReceiver, UnboundedSender},
@@ -64,6 +76,7 @@ pub use index_generator::IndexGenerator;
 pub use js_sys::{self, JsString, Reflect};
 pub use lang::Lang;
 pub use lazy::{Lazy, LazyExt};
+pub use media_query::{media_query_matches, media_query_signal};
 pub use monotonic_ids::MonotonicIds;
 pub use not::not;
 pub use num_traits;
@@ -86,7 +99,7 @@ pub use web_worker_loader::WebWorkerLoader;
 
 #[cfg(feature = "connection")]
 pub use connection::{
-    Connection, ExchangeMsgsError, MsgOptions, ReceiveDownMsgError, SendUpMsgError,
+    Connection, ExchangeMsgsError, FileUpload, MsgOptions, ReceiveDownMsgError, SendUpMsgError,
 };
 
 #[cfg(feature = "routing")]
@@ -101,9 +114,6 @@ pub use routing::{
 #[cfg(feature = "moonlight")]
 pub use moonlight::{self, AuthToken, CorId, EntityId};
 
-#[cfg(feature = "panic_hook")]
-pub use console_error_panic_hook;
-
 #[cfg(feature = "static_ref")]
 pub use static_ref_macro::static_ref;
 
@@ -139,12 +149,52 @@ pub use chrono::{self, prelude::*, Duration};
 #[cfg(feature = "serde-wasm-bindgen")]
 pub use serde_wasm_bindgen;
 
+// -- base_path --
+
+/// Prefix of app URLs when the frontend is served from a subpath, e.g. `/my_repo`.
+/// Set by `mzoon build --frontend-dist --base-path my_repo`, empty otherwise.
+pub const BASE_PATH: &str = match option_env!("BASE_PATH") {
+    Some(base_path) => base_path,
+    None => "",
+};
+
+/// Prefixes the absolute path with [`BASE_PATH`], e.g. `/_api/upload` => `/my_repo/_api/upload`.
+pub fn with_base_path(path: &str) -> String {
+    [BASE_PATH, path].concat()
+}
+
 // -- public_url --
 
 pub static PUBLIC_URL: &str = "/_api/public/";
-
+pub static HASHED_PUBLIC_URL: &str = "/_api/pkg/";
+
+/// Logical names mapped to content-hashed paths by the mzoon asset pipeline.
+/// Set by Moon as `window.MOON_PUBLIC_ASSETS` when `cache_busting` is enabled.
+static PUBLIC_ASSETS: Lazy<std::collections::BTreeMap<String, String>> = Lazy::new(|| {
+    let Ok(manifest) = Reflect::get(&js_sys::global(), &JsValue::from_str("MOON_PUBLIC_ASSETS"))
+    else {
+        return <_>::default();
+    };
+    let Some(manifest) = manifest.dyn_ref::<js_sys::Object>() else {
+        return <_>::default();
+    };
+    js_sys::Object::entries(manifest)
+        .iter()
+        .filter_map(|entry| {
+            let entry = entry.unchecked_into::<js_sys::Array>();
+            Some((entry.get(0).as_string()?, entry.get(1).as_string()?))
+        })
+        .collect()
+});
+
+/// Resolves a path relative to the `public` folder to its URL.
+/// Content-hashed files (cacheable forever) are preferred when available.
 pub fn public_url(path: impl AsRef<str>) -> String {
-    format!("{PUBLIC_URL}{}", path.as_ref())
+    let path = path.as_ref();
+    match PUBLIC_ASSETS.get(path.trim_start_matches('/')) {
+        Some(hashed_path) => [BASE_PATH, HASHED_PUBLIC_URL, hashed_path].concat(),
+        None => [BASE_PATH, PUBLIC_URL, path].concat(),
+    }
 }
 
 #[macro_export]
@@ -248,8 +298,7 @@ pub fn start_app<'a, I: IntoElementIterator>(
     view_root: impl FnOnce() -> I,
 ) {
     #[cfg(feature = "panic_hook")]
-    #[cfg(debug_assertions)]
-    console_error_panic_hook::set_once();
+    panic_hook::init();
 
     let parent = browser_element_id
         .into()
@@ -260,4 +309,7 @@ pub fn start_app<'a, I: IntoElementIterator>(
     for element in view_root().into_element_iter() {
         dominator::append_dom(&parent, element.into_raw().into_dom());
     }
+
+    #[cfg(debug_assertions)]
+    accessibility::audit_after_start();
 }
@@ -0,0 +1,43 @@
+use crate::*;
+use std::{cell::RefCell, collections::BTreeMap};
+
+thread_local! {
+    static MEDIA_QUERIES: RefCell<BTreeMap<String, Mutable<bool>>> = RefCell::new(BTreeMap::new());
+}
+
+// ------ media_query ------
+
+/// `true` while the CSS media query matches, e.g. `"(prefers-color-scheme: dark)"` or `"(min-width: 800px)"`.
+/// There is only one `matchMedia` listener per query, kept for the whole app lifetime.
+pub fn media_query_signal(query: &str) -> impl Signal<Item = bool> + Unpin {
+    media_query_mutable(query).signal()
+}
+
+pub fn media_query_matches(query: &str) -> bool {
+    media_query_mutable(query).get()
+}
+
+fn media_query_mutable(query: &str) -> Mutable<bool> {
+    MEDIA_QUERIES.with_borrow_mut(|media_queries| {
+        media_queries
+            .entry(query.to_owned())
+            .or_insert_with(|| {
+                let Some(media_query_list) = window().match_media(query).ok().flatten() else {
+                    crate::eprintln!("invalid media query '{query}'");
+                    return Mutable::new(false);
+                };
+                let matches = Mutable::new(media_query_list.matches());
+                let on_change = Closure::<dyn Fn(web_sys::MediaQueryListEvent)>::new(
+                    clone!((matches) move |event: web_sys::MediaQueryListEvent| {
+                        matches.set_neq(event.matches())
+                    }),
+                );
+                media_query_list
+                    .add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref())
+                    .unwrap_throw();
+                on_change.forget();
+                matches
+            })
+            .clone()
+    })
+}
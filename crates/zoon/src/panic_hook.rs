@@ -0,0 +1,217 @@
+use crate::*;
+use once_cell::sync::OnceCell;
+use std::{
+    collections::BTreeMap,
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Once,
+    },
+};
+use web_sys::Response;
+
+// NOTE: Sync with Moon's `panic_report_responder`.
+const PANIC_REPORT_URL: &str = "/_api/panic_report";
+
+static SYMBOL_MAP: OnceCell<SymbolMap> = OnceCell::new();
+static REPORT_TO_BACKEND: AtomicBool = AtomicBool::new(false);
+
+// ------ SymbolMap ------
+
+/// Sidecar `frontend.symbols.json` created by mzoon in dev and profiling builds.
+#[derive(Deserialize)]
+struct SymbolMap {
+    code_offset: u32,
+    functions: BTreeMap<u32, String>,
+    files: Vec<String>,
+    lines: Vec<(u32, u32, u32)>,
+}
+
+impl SymbolMap {
+    /// `offset` is relative to the wasm module start.
+    fn source_location(&self, offset: u32) -> Option<String> {
+        let address = offset.checked_sub(self.code_offset)?;
+        let index = self
+            .lines
+            .partition_point(|(line_address, ..)| *line_address <= address)
+            .checked_sub(1)?;
+        let (_, file_index, line) = self.lines[index];
+        let file = self.files.get(file_index as usize)?;
+        Some(format!("{file}:{line}"))
+    }
+}
+
+// ------ public ------
+
+/// Installs the panic hook that logs the panic message with a stack trace
+/// symbolicated by the symbol map (if available) to the browser console.
+///
+/// Called by `start_app`.
+pub fn init() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        panic::set_hook(Box::new(|panic_info| on_panic(&panic_info.to_string())));
+        load_symbol_map();
+    });
+}
+
+/// Sends panic reports also to Moon where they are logged with the level `error`.
+/// Ignored when `client_log` is enabled because it ships panics to Moon as well.
+pub fn report_to_backend(enabled: bool) {
+    REPORT_TO_BACKEND.store(enabled, Ordering::SeqCst);
+}
+
+// ------ private ------
+
+fn on_panic(message: &str) {
+    let stack = Reflect::get(&js_sys::Error::new(""), &JsValue::from_str("stack"))
+        .ok()
+        .and_then(|stack| stack.as_string())
+        .unwrap_or_default();
+
+    let stack = match SYMBOL_MAP.get() {
+        Some(symbol_map) => symbolicate(&stack, symbol_map),
+        None => stack,
+    };
+    let report = format!("{message}\n\nStack:\n\n{stack}\n\n");
+    console::error_local(&report);
+
+    // Panics are sent to Moon only once, the client log takes precedence when it's enabled.
+    #[cfg(feature = "connection")]
+    if client_log::is_enabled() {
+        return client_log::push_panic_and_flush(&report);
+    }
+    if REPORT_TO_BACKEND.load(Ordering::SeqCst) {
+        // `sendBeacon` is delivered even when the app is broken or the page is being closed.
+        if let Ok(window) = js_sys::global().dyn_into::<web_sys::Window>() {
+            let _ = window
+                .navigator()
+                .send_beacon_with_opt_str(&with_base_path(PANIC_REPORT_URL), Some(&report));
+        }
+    }
+}
+
+fn load_symbol_map() {
+    let Some(url) = Reflect::get(&js_sys::global(), &JsValue::from_str("MOON_SYMBOL_MAP_URL"))
+        .ok()
+        .and_then(|url| url.as_string())
+    else {
+        return;
+    };
+    Task::start(async move {
+        let symbol_map = async {
+            let response = JsFuture::from(window().fetch_with_str(&url))
+                .await?
+                .unchecked_into::<Response>();
+            let text = JsFuture::from(response.text()?).await?;
+            Ok::<_, JsValue>(text.as_string().unwrap_or_default())
+        }
+        .await
+        .ok()
+        .and_then(|text| serde_json::from_str::<SymbolMap>(&text).ok());
+
+        match symbol_map {
+            Some(symbol_map) => {
+                let _ = SYMBOL_MAP.set(symbol_map);
+            }
+            None => console::error(&format!("Failed to load the symbol map '{url}'")),
+        }
+    });
+}
+
+fn symbolicate(stack: &str, symbol_map: &SymbolMap) -> String {
+    stack
+        .lines()
+        .map(|frame| symbolicate_frame(frame, symbol_map).unwrap_or_else(|| frame.to_owned()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Frame examples:
+/// - Chromium: `at frontend_bg.wasm.__rust_start_panic (http://localhost:8080/_api/pkg/frontend_bg.wasm:wasm-function[123]:0x1a2b)`
+/// - Firefox: `__rust_start_panic@http://localhost:8080/_api/pkg/frontend_bg.wasm:wasm-function[123]:0x1a2b`
+fn symbolicate_frame(frame: &str, symbol_map: &SymbolMap) -> Option<String> {
+    let (_, location) = frame.split_once("wasm-function[")?;
+    let (function_index, offset) = location.split_once("]:0x")?;
+    let function_index = function_index.parse::<u32>().ok()?;
+    let offset = offset
+        .split(|char: char| !char.is_ascii_hexdigit())
+        .next()?;
+    let offset = u32::from_str_radix(offset, 16).ok()?;
+
+    let function = symbol_map
+        .functions
+        .get(&function_index)
+        .map(String::as_str)
+        .unwrap_or("<unknown>");
+    Some(match symbol_map.source_location(offset) {
+        Some(source_location) => format!("    at {function} ({source_location})"),
+        None => format!("    at {function}"),
+    })
+}
+
+// ====== ====== TESTS ====== ======
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE_OFFSET: u32 = 0x100;
+
+    fn symbol_map() -> SymbolMap {
+        SymbolMap {
+            code_offset: CODE_OFFSET,
+            functions: BTreeMap::from([(123, "frontend::counter::increment".to_owned())]),
+            files: vec![
+                "frontend/src/counter.rs".to_owned(),
+                "zoon/src/lib.rs".to_owned(),
+            ],
+            lines: vec![(0x1000, 0, 42), (0x1a00, 1, 7)],
+        }
+    }
+
+    #[test]
+    fn test_source_location() {
+        let symbol_map = symbol_map();
+        let location = |address| symbol_map.source_location(CODE_OFFSET + address);
+        // Before the code section and before the first line entry.
+        assert_eq!(symbol_map.source_location(CODE_OFFSET - 1), None);
+        assert_eq!(location(0xfff), None);
+        // At and between line entries.
+        assert_eq!(
+            location(0x1000).as_deref(),
+            Some("frontend/src/counter.rs:42")
+        );
+        assert_eq!(
+            location(0x1500).as_deref(),
+            Some("frontend/src/counter.rs:42")
+        );
+        assert_eq!(location(0x1a00).as_deref(), Some("zoon/src/lib.rs:7"));
+        // After the last line entry.
+        assert_eq!(location(0x2000).as_deref(), Some("zoon/src/lib.rs:7"));
+    }
+
+    #[test]
+    fn test_symbolicate_frame() {
+        let symbol_map = symbol_map();
+        let expected = Some("    at frontend::counter::increment (frontend/src/counter.rs:42)");
+
+        let chromium = "    at frontend_bg.wasm.__rust_start_panic (http://localhost:8080/_api/pkg/frontend_bg.wasm:wasm-function[123]:0x1a2b)";
+        assert_eq!(
+            symbolicate_frame(chromium, &symbol_map).as_deref(),
+            expected
+        );
+
+        let firefox = "__rust_start_panic@http://localhost:8080/_api/pkg/frontend_bg.wasm:wasm-function[123]:0x1a2b";
+        assert_eq!(symbolicate_frame(firefox, &symbol_map).as_deref(), expected);
+
+        let unknown = "@http://localhost:8080/_api/pkg/frontend_bg.wasm:wasm-function[7]:0x10";
+        assert_eq!(
+            symbolicate_frame(unknown, &symbol_map).as_deref(),
+            Some("    at <unknown>")
+        );
+
+        let js_frame = "    at run (http://localhost:8080/_api/pkg/frontend.js:10:5)";
+        assert_eq!(symbolicate_frame(js_frame, &symbol_map), None);
+    }
+}
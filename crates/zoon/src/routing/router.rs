@@ -131,7 +131,7 @@ fn go<'a>(url_change_sender: &UrlChangeSender, to: impl IntoCowStr<'a>, silent:
         return window().location().assign(&to).unwrap_throw();
     }
     history()
-        .push_state_with_url(&JsValue::NULL, "", Some(&to))
+        .push_state_with_url(&JsValue::NULL, "", Some(&with_base_path(&to)))
         .unwrap_throw();
     if !silent {
         url_change_sender
@@ -146,7 +146,7 @@ fn replace<'a>(url_change_sender: &UrlChangeSender, with: impl IntoCowStr<'a>, s
         return window().location().replace(&with).unwrap_throw();
     }
     history()
-        .replace_state_with_url(&JsValue::NULL, "", Some(&with))
+        .replace_state_with_url(&JsValue::NULL, "", Some(&with_base_path(&with)))
         .unwrap_throw();
     if !silent {
         url_change_sender
@@ -158,7 +158,10 @@ fn replace<'a>(url_change_sender: &UrlChangeSender, with: impl IntoCowStr<'a>, s
 fn current_url_segments() -> Option<Vec<String>> {
     let path = window().location().pathname().unwrap_throw();
     let mut segments = Vec::new();
-    for segment in path.trim_start_matches('/').split_terminator('/') {
+    for segment in strip_base_path(&path)
+        .trim_start_matches('/')
+        .split_terminator('/')
+    {
         match decode_uri_component(segment) {
             Ok(segment) => segments.push(segment),
             Err(error) => {
@@ -212,6 +215,14 @@ fn link_click_handler(event: MouseEvent, url_change_sender: &UrlChangeSender) ->
         .ok()??;
     let href = a.get_attribute("href")?;
     event.prevent_default();
-    go(url_change_sender, href, false);
+    // `Link` hrefs include `BASE_PATH`.
+    go(url_change_sender, strip_base_path(&href), false);
     Some(())
 }
+
+/// E.g. `/my_repo/about` => `/about`.
+pub(crate) fn strip_base_path(path: &str) -> &str {
+    path.strip_prefix(BASE_PATH)
+        .filter(|path| path.is_empty() || path.starts_with('/'))
+        .unwrap_or(path)
+}
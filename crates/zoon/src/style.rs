@@ -8,7 +8,8 @@ use std::{
     sync::Arc,
 };
 use web_sys::{
-    CssKeyframesRule, CssStyleDeclaration, CssStyleRule, CssStyleSheet, HtmlStyleElement,
+    CssGroupingRule, CssKeyframesRule, CssStyleDeclaration, CssStyleRule, CssStyleSheet,
+    HtmlStyleElement,
 };
 
 mod align;
@@ -47,6 +48,9 @@ pub use padding::Padding;
 mod resizable;
 pub use resizable::Resizable;
 
+mod responsive;
+pub use responsive::{breakpoint_signal, Breakpoint, Container, ContainerQuery, MediaQuery};
+
 mod rounded_corners;
 pub use rounded_corners::{IntoOptionRadius, Radius, RoundedCorners};
 
@@ -208,6 +212,19 @@ pub fn pct<'a>(percent: impl IntoCowStr<'a>) -> Cow<'a, str> {
     [&percent.into_cow_str(), "%"].concat().into()
 }
 
+// ------ IntoCssLength ------
+
+/// Pixels or a theme token, e.g. `Padding::all(12)` or `Padding::all(SpaceToken::Medium)`.
+pub trait IntoCssLength {
+    fn into_css_length(self) -> Cow<'static, str>;
+}
+
+impl IntoCssLength for u32 {
+    fn into_css_length(self) -> Cow<'static, str> {
+        px(self)
+    }
+}
+
 // ------ Style ------
 
 /// Trait to be implemented to enable the use for styling.
@@ -273,6 +290,10 @@ pub struct StyleGroup<'a> {
     pub selector: Cow<'a, str>,
     pub static_css_props: StaticCSSProps<'a>,
     pub dynamic_css_props: DynamicCSSProps,
+    /// Styles apply only while the media query matches.
+    pub media_query: Option<MediaQuery>,
+    /// Styles apply only while the query container matches.
+    pub container_query: Option<ContainerQuery>,
     // --- not applicable to global styles (only directly to elements) ---
     pub static_css_classes: StaticCSSClasses<'a>,
     pub dynamic_css_classes: DynamicCSSClasses,
@@ -391,6 +412,52 @@ impl<'a> StyleGroup<'a> {
         self
     }
 
+    /// Apply styles only while the media query matches.
+    /// Conditional styles override unconditional ones of the same element.
+    /// # Example
+    /// ```no_run
+    /// use zoon::*;
+    ///
+    /// global_styles().style_group(
+    ///     StyleGroup::new(".sidebar")
+    ///         .media(Breakpoint::Medium.down())
+    ///         .style("display", "none"),
+    /// );
+    /// ```
+    pub fn media(mut self, query: MediaQuery) -> Self {
+        self.media_query = Some(match self.media_query.take() {
+            Some(media_query) => media_query.and(query),
+            None => query,
+        });
+        self
+    }
+
+    /// Apply styles only while the nearest ancestor with the [Container] style matches the query.
+    pub fn container(mut self, query: ContainerQuery) -> Self {
+        self.container_query = Some(match self.container_query.take() {
+            Some(container_query) => container_query.and(query),
+            None => query,
+        });
+        self
+    }
+
+    pub fn is_conditional(&self) -> bool {
+        self.media_query.is_some() || self.container_query.is_some()
+    }
+
+    /// At-rule preludes wrapping the style rule, e.g. `@media (min-width: 768px)`.
+    fn at_rules(&self) -> Vec<String> {
+        let media_rule = self
+            .media_query
+            .as_ref()
+            .map(|query| ["@media ", query.as_str()].concat());
+        let container_rule = self
+            .container_query
+            .as_ref()
+            .map(|query| ["@container ", &query.to_css_string()].concat());
+        media_rule.into_iter().chain(container_rule).collect()
+    }
+
     pub fn class(mut self, class: &'a str) -> Self {
         self.static_css_classes.insert(class);
         self
@@ -621,31 +688,46 @@ impl GlobalStyles {
 
     fn style_group_inner(&self, group: StyleGroup, droppable: bool) -> (u32, Vec<TaskHandle>) {
         let (rule_id_and_index, ids_lock) = self.rule_ids.add_new_id();
-        let empty_rule = [&group.selector, "{}"].concat();
+        let at_rules = group.at_rules();
+        let empty_rule = at_rules
+            .iter()
+            .rev()
+            .fold([&group.selector, "{}"].concat(), |rule, at_rule| {
+                [at_rule, "{", &rule, "}"].concat()
+            });
 
         self.sheet
             .insert_rule_with_index(&empty_rule, rule_id_and_index)
             .unwrap_or_else(|_| {
-                panic!("invalid CSS selector: `{}`", &group.selector);
+                panic!("invalid CSS rule: `{empty_rule}`");
             });
 
-        let declaration = self
+        let mut rule = self
             .sheet
             .css_rules()
             .expect_throw("failed to get global CSS rules")
             .item(rule_id_and_index)
-            .expect_throw("failed to get selected global CSS rule")
-            .unchecked_into::<CssStyleRule>()
-            .style();
+            .expect_throw("failed to get selected global CSS rule");
+        for _ in &at_rules {
+            rule = rule
+                .unchecked_into::<CssGroupingRule>()
+                .css_rules()
+                .item(0)
+                .expect_throw("failed to get nested CSS rule");
+        }
+        let declaration = rule.unchecked_into::<CssStyleRule>().style();
 
         drop(ids_lock);
 
+        // Conditional rules have to override inline styles of the element.
+        let important = group.is_conditional();
+
         for (name, css_prop_value) in group.static_css_props {
             set_css_property(
                 &declaration,
                 name,
                 &css_prop_value.value,
-                css_prop_value.important,
+                css_prop_value.important || important,
             );
         }
 
@@ -656,7 +738,7 @@ impl GlobalStyles {
             let task = value_signal.signal_cloned().for_each_sync(move |value| {
                 if let Some(css_prop_value) = value {
                     // @TODO allow to set `important ` in dynamic styles, too
-                    set_css_property(&declaration, &name, &css_prop_value.value, false);
+                    set_css_property(&declaration, &name, &css_prop_value.value, important);
                 } else {
                     declaration
                         .remove_property(&name)
@@ -77,9 +77,8 @@ impl<'a> Background<'a> {
     /// let element = El::new().s(Background::new().color(BLUE_0));
     /// ```
     pub fn color(mut self, color: impl IntoOptionColor) -> Self {
-        if let Some(color) = color.into_option_color() {
-            self.static_css_props
-                .insert("background-color", color.into_color_string());
+        if let Some(color) = color.into_option_color_string() {
+            self.static_css_props.insert("background-color", color);
         }
         self
     }
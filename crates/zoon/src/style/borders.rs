@@ -315,7 +315,7 @@ pub struct Border {
     /// Style to apply.
     style: BorderStyle,
     /// CSS Color.
-    color: Color,
+    color: String,
 }
 
 impl Border {
@@ -325,7 +325,7 @@ impl Border {
         Self {
             width: 1,
             style: BorderStyle::Solid,
-            color: oklch().l(0).c(0).h(0).a(1).into_color(),
+            color: oklch().l(0).c(0).h(0).a(1).into_color_string(),
         }
     }
 
@@ -345,7 +345,7 @@ impl Border {
     /// let green_border = Border::new().color(GREEN_7);
     /// ```
     pub fn color(mut self, color: impl IntoOptionColor) -> Self {
-        if let Some(color) = color.into_option_color() {
+        if let Some(color) = color.into_option_color_string() {
             self.color = color;
         }
         self
@@ -383,13 +383,7 @@ impl Border {
 
     /// Convert the border properties as `Cow<'static, str>`.
     fn to_cow_str(&self) -> Cow<'static, str> {
-        crate::format!(
-            "{}px {} {}",
-            self.width,
-            self.style.as_str(),
-            self.color.clone().into_color_string()
-        )
-        .into()
+        crate::format!("{}px {} {}", self.width, self.style.as_str(), self.color).into()
     }
 }
 
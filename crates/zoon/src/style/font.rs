@@ -142,9 +142,8 @@ impl<'a> Font<'a> {
     ///     .label("Click me");
     /// ```
     pub fn color(mut self, color: impl IntoOptionColor) -> Self {
-        if let Some(color) = color.into_option_color() {
-            self.static_css_props
-                .insert(StyleName::Color.into(), color.into_color_string());
+        if let Some(color) = color.into_option_color_string() {
+            self.static_css_props.insert(StyleName::Color.into(), color);
         }
         self
     }
@@ -179,9 +178,9 @@ impl<'a> Font<'a> {
     ///
     /// let button = Button::new().s(Font::new().size(350)).label("Click me");
     /// ```
-    pub fn size(mut self, size: u32) -> Self {
+    pub fn size(mut self, size: impl IntoCssLength) -> Self {
         self.static_css_props
-            .insert(StyleName::FontSize.into(), px(size));
+            .insert(StyleName::FontSize.into(), size.into_css_length());
         self
     }
 
@@ -1,4 +1,4 @@
-use crate::*;
+use crate::{theme::FontToken, *};
 use std::borrow::Cow;
 
 // ------ FontFamily ------
@@ -9,6 +9,8 @@ pub enum FontFamily<'a> {
     SansSerif,
     Monospace,
     Custom(Cow<'a, str>),
+    /// Families of the theme's font token, see [`FontToken`].
+    Token(FontToken),
 }
 
 impl<'a> FontFamily<'a> {
@@ -24,6 +26,7 @@ impl<'a> IntoCowStr<'a> for FontFamily<'a> {
             FontFamily::SansSerif => "sans-serif".into(),
             FontFamily::Monospace => "monospace".into(),
             FontFamily::Custom(family) => ["\"", family.as_ref(), "\""].concat().into(),
+            FontFamily::Token(token) => token.var().into(),
         }
     }
 }
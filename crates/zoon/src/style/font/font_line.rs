@@ -41,9 +41,8 @@ impl FontLine<'_> {
     }
 
     pub fn color(mut self, color: impl IntoOptionColor) -> Self {
-        if let Some(color) = color.into_option_color() {
-            self.static_css_props
-                .insert("text-decoration-color", color.into_color_string());
+        if let Some(color) = color.into_option_color_string() {
+            self.static_css_props.insert("text-decoration-color", color);
         }
         self
     }
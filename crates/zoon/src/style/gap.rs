@@ -25,8 +25,12 @@ impl<'a> Gap<'a> {
     ///     .item(Column::new().item("first column"))
     ///     .item(Column::new().item("second column"));
     /// ```
-    pub fn both(gap: u32) -> Self {
-        Self::default().x(gap).y(gap)
+    pub fn both(gap: impl IntoCssLength) -> Self {
+        let mut this = Self::default();
+        let gap = gap.into_css_length();
+        this.static_css_props.insert("column-gap", gap.clone());
+        this.static_css_props.insert("row-gap", gap);
+        this
     }
 
     pub fn both_signal(gap: impl Signal<Item = impl Into<Option<u32>>> + Unpin + 'static) -> Self {
@@ -35,8 +39,9 @@ impl<'a> Gap<'a> {
         this.x_signal(gap.signal()).y_signal(gap.signal())
     }
 
-    pub fn x(mut self, gap: u32) -> Self {
-        self.static_css_props.insert("column-gap", px(gap));
+    pub fn x(mut self, gap: impl IntoCssLength) -> Self {
+        self.static_css_props
+            .insert("column-gap", gap.into_css_length());
         self
     }
 
@@ -50,8 +55,9 @@ impl<'a> Gap<'a> {
         self
     }
 
-    pub fn y(mut self, gap: u32) -> Self {
-        self.static_css_props.insert("row-gap", px(gap));
+    pub fn y(mut self, gap: impl IntoCssLength) -> Self {
+        self.static_css_props
+            .insert("row-gap", gap.into_css_length());
         self
     }
 
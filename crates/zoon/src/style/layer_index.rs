@@ -16,6 +16,8 @@ impl<'a> LayerIndex<'a> {
     pub const MIN_VALUE: i32 = i32::MIN + Self::MAX_VALUE_OFFSET;
     pub const MAX_VALUE: i32 = i32::MAX - Self::MAX_VALUE_OFFSET;
     pub const NEARBY_ELEMENTS: i32 = 20;
+    /// `Popover` and `Tooltip`.
+    pub const OVERLAYS: i32 = 1000;
 
     /// Set the layer index for an element.
     /// # Example
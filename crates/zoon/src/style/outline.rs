@@ -5,7 +5,7 @@ use std::borrow::Cow;
 pub struct Outline {
     width: u32,
     style: OutlineStyle,
-    color: Color,
+    color: String,
     inner: bool,
     self_signal: Option<Broadcaster<LocalBoxSignal<'static, Option<Self>>>>,
 }
@@ -15,7 +15,7 @@ impl Default for Outline {
         Self {
             width: 1,
             style: OutlineStyle::Solid,
-            color: oklch().l(0).c(0).h(0).a(1).into_color(),
+            color: oklch().l(0).c(0).h(0).a(1).into_color_string(),
             inner: false,
             self_signal: None,
         }
@@ -54,7 +54,7 @@ impl Outline {
     }
 
     pub fn color(mut self, color: impl IntoOptionColor) -> Self {
-        if let Some(color) = color.into_option_color() {
+        if let Some(color) = color.into_option_color_string() {
             self.color = color;
         }
         self
@@ -76,13 +76,7 @@ impl Outline {
     }
 
     fn to_css_outline_value(&self) -> Cow<'static, str> {
-        crate::format!(
-            "{}px {} {}",
-            self.width,
-            self.style.as_str(),
-            self.color.clone().into_color_string()
-        )
-        .into()
+        crate::format!("{}px {} {}", self.width, self.style.as_str(), self.color).into()
     }
 
     fn to_css_outline_offset_value(&self) -> Cow<'static, str> {
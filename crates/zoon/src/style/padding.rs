@@ -1,4 +1,5 @@
 use crate::*;
+use std::borrow::Cow;
 
 /// Define padding with pixels for an element.
 #[derive(Default, Clone)]
@@ -21,8 +22,13 @@ impl<'a> Padding<'a> {
     ///
     /// let button = Button::new().s(Padding::all(15)).label("Click me");
     /// ```
-    pub fn all(padding: u32) -> Self {
-        Self::default().x(padding).y(padding)
+    pub fn all(padding: impl IntoCssLength) -> Self {
+        let padding = padding.into_css_length();
+        Self::default()
+            .insert("padding-top", padding.clone())
+            .insert("padding-right", padding.clone())
+            .insert("padding-bottom", padding.clone())
+            .insert("padding-left", padding)
     }
 
     /// Set all paddings, e.g top, right, bottom and left depending of signal's
@@ -50,8 +56,10 @@ impl<'a> Padding<'a> {
     ///
     /// let button = Button::new().s(Padding::new().x(15)).label("Click me");
     /// ```
-    pub fn x(self, x: u32) -> Self {
-        self.left(x).right(x)
+    pub fn x(self, x: impl IntoCssLength) -> Self {
+        let x = x.into_css_length();
+        self.insert("padding-left", x.clone())
+            .insert("padding-right", x)
     }
 
     /// Set left and right padding depending of signal's state.
@@ -77,8 +85,10 @@ impl<'a> Padding<'a> {
     ///
     /// let button = Button::new().s(Padding::new().y(15)).label("Click me");
     /// ```
-    pub fn y(self, y: u32) -> Self {
-        self.top(y).bottom(y)
+    pub fn y(self, y: impl IntoCssLength) -> Self {
+        let y = y.into_css_length();
+        self.insert("padding-top", y.clone())
+            .insert("padding-bottom", y)
     }
 
     /// Set top and bottom padding depending of signal's state.
@@ -104,9 +114,8 @@ impl<'a> Padding<'a> {
     ///
     /// let button = Button::new().s(Padding::new().top(15)).label("Click me");
     /// ```
-    pub fn top(mut self, top: u32) -> Self {
-        self.static_css_props.insert("padding-top", px(top));
-        self
+    pub fn top(self, top: impl IntoCssLength) -> Self {
+        self.insert("padding-top", top.into_css_length())
     }
 
     /// Set top padding depending of signal's state.
@@ -137,9 +146,8 @@ impl<'a> Padding<'a> {
     ///
     /// let button = Button::new().s(Padding::new().right(15)).label("Click me");
     /// ```
-    pub fn right(mut self, right: u32) -> Self {
-        self.static_css_props.insert("padding-right", px(right));
-        self
+    pub fn right(self, right: impl IntoCssLength) -> Self {
+        self.insert("padding-right", right.into_css_length())
     }
 
     /// Set right padding depending of signal's state.
@@ -170,9 +178,8 @@ impl<'a> Padding<'a> {
     ///
     /// let button = Button::new().s(Padding::new().bottom(15)).label("Click me");
     /// ```
-    pub fn bottom(mut self, bottom: u32) -> Self {
-        self.static_css_props.insert("padding-bottom", px(bottom));
-        self
+    pub fn bottom(self, bottom: impl IntoCssLength) -> Self {
+        self.insert("padding-bottom", bottom.into_css_length())
     }
 
     /// Set bottom padding depending of signal's state.
@@ -203,9 +210,8 @@ impl<'a> Padding<'a> {
     ///
     /// let button = Button::new().s(Padding::new().left(15)).label("Click me");
     /// ```
-    pub fn left(mut self, left: u32) -> Self {
-        self.static_css_props.insert("padding-left", px(left));
-        self
+    pub fn left(self, left: impl IntoCssLength) -> Self {
+        self.insert("padding-left", left.into_css_length())
     }
 
     /// Set left padding depending of signal's state.
@@ -228,6 +234,11 @@ impl<'a> Padding<'a> {
             .insert("padding-left".into(), box_css_signal(left));
         self
     }
+
+    fn insert(mut self, name: &'a str, length: Cow<'static, str>) -> Self {
+        self.static_css_props.insert(name, length);
+        self
+    }
 }
 
 impl<'a> Style<'a> for Padding<'a> {
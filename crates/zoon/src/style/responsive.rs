@@ -0,0 +1,215 @@
+use crate::*;
+use std::borrow::Cow;
+use strum::{EnumIter, IntoEnumIterator};
+
+// ------ Breakpoint ------
+
+/// Viewport width ranges, `Breakpoint::Medium` covers widths from `768px` to `1023px`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, EnumIter)]
+pub enum Breakpoint {
+    #[default]
+    ExtraSmall,
+    Small,
+    Medium,
+    Large,
+    ExtraLarge,
+}
+
+impl Breakpoint {
+    pub fn min_width(self) -> u32 {
+        match self {
+            Self::ExtraSmall => 0,
+            Self::Small => 640,
+            Self::Medium => 768,
+            Self::Large => 1024,
+            Self::ExtraLarge => 1280,
+        }
+    }
+
+    fn next(self) -> Option<Self> {
+        Self::iter().find(|breakpoint| *breakpoint > self)
+    }
+
+    /// The breakpoint and all larger ones.
+    pub fn up(self) -> MediaQuery {
+        MediaQuery::min_width(self.min_width())
+    }
+
+    /// All breakpoints smaller than this one.
+    pub fn down(self) -> MediaQuery {
+        MediaQuery::max_width_exclusive(self.min_width())
+    }
+
+    pub fn only(self) -> MediaQuery {
+        match self.next() {
+            Some(next) => self.up().and(next.down()),
+            None => self.up(),
+        }
+    }
+}
+
+/// The current `Breakpoint` according to the viewport width.
+pub fn breakpoint_signal() -> impl Signal<Item = Breakpoint> + Unpin {
+    let breakpoint = map_ref! {
+        let small = Breakpoint::Small.up().signal(),
+        let medium = Breakpoint::Medium.up().signal(),
+        let large = Breakpoint::Large.up().signal(),
+        let extra_large = Breakpoint::ExtraLarge.up().signal() =>
+        match (*small, *medium, *large, *extra_large) {
+            (_, _, _, true) => Breakpoint::ExtraLarge,
+            (_, _, true, _) => Breakpoint::Large,
+            (_, true, _, _) => Breakpoint::Medium,
+            (true, _, _, _) => Breakpoint::Small,
+            _ => Breakpoint::ExtraSmall,
+        }
+    };
+    breakpoint.dedupe()
+}
+
+// ------ MediaQuery ------
+
+/// Condition of [`Styleable::s_media`] or [`StyleGroup::media`] styles.
+/// More information at <https://developer.mozilla.org/en-US/docs/Web/CSS/CSS_media_queries>.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaQuery(Cow<'static, str>);
+
+impl MediaQuery {
+    /// Raw media query, e.g. `"(hover: hover)"` or `"print"`.
+    pub fn new(query: impl IntoCowStr<'static>) -> Self {
+        Self(query.into_cow_str())
+    }
+
+    pub fn min_width(pixels: u32) -> Self {
+        Self::new(["(min-width: ", &px(pixels), ")"].concat())
+    }
+
+    pub fn max_width(pixels: u32) -> Self {
+        Self::new(["(max-width: ", &px(pixels), ")"].concat())
+    }
+
+    // Fractional pixels don't overlap with `min_width(pixels)` even with zoom.
+    fn max_width_exclusive(pixels: u32) -> Self {
+        Self::new(["(max-width: ", &px(f64::from(pixels) - 0.02), ")"].concat())
+    }
+
+    pub fn portrait() -> Self {
+        Self::new("(orientation: portrait)")
+    }
+
+    pub fn landscape() -> Self {
+        Self::new("(orientation: landscape)")
+    }
+
+    /// The user prefers less animations, e.g. disable `Transitions`.
+    pub fn reduced_motion() -> Self {
+        Self::new("(prefers-reduced-motion: reduce)")
+    }
+
+    pub fn dark_color_scheme() -> Self {
+        Self::new("(prefers-color-scheme: dark)")
+    }
+
+    pub fn and(self, query: MediaQuery) -> Self {
+        Self::new([&self.0, " and ", &query.0].concat())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn signal(&self) -> impl Signal<Item = bool> + Unpin {
+        media_query_signal(&self.0)
+    }
+
+    pub fn matches(&self) -> bool {
+        media_query_matches(&self.0)
+    }
+}
+
+// ------ ContainerQuery ------
+
+/// Condition of [`Styleable::s_container`] or [`StyleGroup::container`] styles
+/// evaluated against the nearest ancestor with the [`Container`] style.
+/// More information at <https://developer.mozilla.org/en-US/docs/Web/CSS/CSS_containment/Container_queries>.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerQuery {
+    name: Option<Cow<'static, str>>,
+    condition: Cow<'static, str>,
+}
+
+impl ContainerQuery {
+    /// Raw container condition, e.g. `"(min-width: 400px) and (max-width: 800px)"`.
+    pub fn new(condition: impl IntoCowStr<'static>) -> Self {
+        Self {
+            name: None,
+            condition: condition.into_cow_str(),
+        }
+    }
+
+    pub fn min_width(pixels: u32) -> Self {
+        Self::new(["(min-width: ", &px(pixels), ")"].concat())
+    }
+
+    pub fn max_width(pixels: u32) -> Self {
+        Self::new(["(max-width: ", &px(pixels), ")"].concat())
+    }
+
+    pub fn portrait() -> Self {
+        Self::new("(orientation: portrait)")
+    }
+
+    pub fn landscape() -> Self {
+        Self::new("(orientation: landscape)")
+    }
+
+    /// Evaluates the condition against the nearest container with the given name, see [`Container::name`].
+    pub fn name(mut self, name: impl IntoCowStr<'static>) -> Self {
+        self.name = Some(name.into_cow_str());
+        self
+    }
+
+    pub fn and(mut self, query: ContainerQuery) -> Self {
+        self.condition = [&self.condition, " and ", &query.condition].concat().into();
+        self
+    }
+
+    /// Prelude of the `@container` rule, e.g. `sidebar (min-width: 400px)`.
+    pub fn to_css_string(&self) -> String {
+        match &self.name {
+            Some(name) => [name, " ", &self.condition].concat(),
+            None => self.condition.to_string(),
+        }
+    }
+}
+
+// ------ Container ------
+
+/// Makes the element a query container for its descendants' [`ContainerQuery`] styles.
+/// Only the inline size (width) is queried, so the element can't be sized by its content horizontally.
+#[derive(Default, Clone)]
+pub struct Container<'a> {
+    static_css_props: StaticCSSProps<'a>,
+}
+
+impl<'a> Container<'a> {
+    pub fn new() -> Self {
+        let mut this = Self::default();
+        this.static_css_props
+            .insert("container-type", "inline-size");
+        this
+    }
+
+    pub fn name(mut self, name: impl IntoCowStr<'a>) -> Self {
+        self.static_css_props.insert("container-name", name);
+        self
+    }
+}
+
+impl<'a> Style<'a> for Container<'a> {
+    fn move_to_groups(self, groups: &mut StyleGroups<'a>) {
+        groups.update_first(|mut group| {
+            group.static_css_props.extend(self.static_css_props);
+            group
+        });
+    }
+}
@@ -1,4 +1,4 @@
-use crate::*;
+use crate::{theme::RadiusToken, *};
 
 // ------ Radius ------
 /// Define radius for rounded corners with pixels.
@@ -6,6 +6,8 @@ use crate::*;
 pub enum Radius {
     Px(u32),
     Max,
+    /// Pixels of the theme's radius token, it's updated when the theme changes.
+    Token(RadiusToken),
 }
 
 impl Radius {
@@ -38,6 +40,12 @@ impl From<u32> for Radius {
     }
 }
 
+impl From<RadiusToken> for Radius {
+    fn from(token: RadiusToken) -> Self {
+        Self::Token(token)
+    }
+}
+
 // ------ IntoOptionRadius ------
 
 pub trait IntoOptionRadius {
@@ -63,7 +71,7 @@ struct RadiusSignal(Broadcaster<LocalBoxSignal<'static, Option<Radius>>>);
 
 impl RadiusSignal {
     fn new_from_value(radius: impl Into<Radius>) -> Self {
-        Self(always(Some(radius.into())).boxed_local().broadcast())
+        Self::new_from_signal(always(radius.into()))
     }
 
     fn new_from_signal(
@@ -72,6 +80,14 @@ impl RadiusSignal {
         Self(
             radius
                 .map(|radius| radius.into_option_radius())
+                // Overlapping radii are computed in pixels, so tokens can't be set as `var(--radius-*)`.
+                .switch(|radius| match radius {
+                    Some(Radius::Token(token)) => token
+                        .signal()
+                        .map(|pixels| Some(Radius::Px(pixels)))
+                        .boxed_local(),
+                    radius => always(radius).boxed_local(),
+                })
                 .boxed_local()
                 .broadcast(),
         )
@@ -107,7 +123,8 @@ impl RoundedCorners {
     ///
     /// let button = Button::new().s(RoundedCorners::all(10)).label("Click me");
     /// ```
-    pub fn all(radius: u32) -> Self {
+    pub fn all(radius: impl Into<Radius>) -> Self {
+        let radius = radius.into();
         Self::default().top(radius).bottom(radius)
     }
 
@@ -148,7 +148,7 @@ pub struct Shadow {
     y: i32,
     spread: i32,
     blur: u32,
-    color: Option<Color>,
+    color: Option<String>,
 }
 
 impl Shadow {
@@ -260,7 +260,7 @@ impl Shadow {
     ///     .label("Click me");
     /// ```
     pub fn color(mut self, color: impl IntoOptionColor) -> Self {
-        if let Some(color) = color.into_option_color() {
+        if let Some(color) = color.into_option_color_string() {
             self.color = Some(color);
         }
         self
@@ -275,7 +275,7 @@ impl<'a> IntoCowStr<'a> for Shadow {
         }
         shadow_settings.extend([px(self.x), px(self.y), px(self.blur), px(self.spread)]);
         if let Some(color) = self.color {
-            shadow_settings.push(color.into_color_string().into());
+            shadow_settings.push(color.into());
         }
         shadow_settings.join(" ").into()
     }
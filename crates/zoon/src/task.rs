@@ -189,7 +189,7 @@ fn worker_loader_url() -> String {
     } else {
         format!("/_api/pkg/frontend.js")
     };
-    let js_url = web_sys::Url::new_with_base(&js_url, &current_href)
+    let js_url = web_sys::Url::new_with_base(&with_base_path(&js_url), &current_href)
         .expect_throw("Failed to create URL for Web Worker Javascript")
         .to_string()
         .as_string()
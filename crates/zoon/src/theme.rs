@@ -0,0 +1,363 @@
+//! Design tokens (colors, spacing, radii and fonts) emitted as CSS custom properties on `:root`.
+//!
+//! ```no_run
+//! use zoon::{*, theme::*};
+//!
+//! let light = Theme::new(ColorScheme::Light)
+//!     .color(ColorToken::Background, oklch().l(0.98).c(0).h(0))
+//!     .color(ColorToken::Text, oklch().l(0.2).c(0).h(0))
+//!     .color(ColorToken::Primary, hsluv!(250, 80, 50))
+//!     .space(SpaceToken::Medium, 12)
+//!     .radius(RadiusToken::Medium, 6)
+//!     .font(FontToken::Body, [FontFamily::new("Inter"), FontFamily::SansSerif]);
+//! let dark = light
+//!     .clone()
+//!     .color_scheme(ColorScheme::Dark)
+//!     .color(ColorToken::Background, oklch().l(0.2).c(0).h(0))
+//!     .color(ColorToken::Text, oklch().l(0.95).c(0).h(0));
+//! set_theme_by_color_scheme(light, dark);
+//!
+//! let button = Button::new()
+//!     .s(Background::new().color(ColorToken::Primary))
+//!     .s(Font::new().family([FontToken::Body.into()]))
+//!     .s(Padding::all(SpaceToken::Medium))
+//!     .s(RoundedCorners::all(RadiusToken::Medium))
+//!     .label("Themed");
+//! ```
+
+use crate::*;
+use std::{borrow::Cow, cell::RefCell, collections::BTreeMap, iter};
+use strum::IntoStaticStr;
+
+static THEME: Lazy<Mutable<Theme>> = Lazy::new(|| {
+    let theme = Mutable::new(Theme::default());
+    Task::start(theme.signal_cloned().for_each_sync(|theme| {
+        let properties = theme.css_properties();
+        let group = properties
+            .iter()
+            .fold(StyleGroup::new(":root"), |group, (name, value)| {
+                group.style(name, value.as_str())
+            });
+        let rule_handle = global_styles().style_group_droppable(group);
+        // The old rule is removed after the new one is inserted to prevent flashing.
+        THEME_RULE.with_borrow_mut(|old_rule_handle| *old_rule_handle = Some(rule_handle));
+    }));
+    theme
+});
+
+thread_local! {
+    static THEME_RULE: RefCell<Option<StyleRuleHandle>> = const { RefCell::new(None) };
+    static THEME_SIGNAL_TASK: RefCell<Option<TaskHandle>> = const { RefCell::new(None) };
+}
+
+// ------ tokens ------
+
+macro_rules! make_tokens {
+    ($($token:ident($prefix:literal) { $($variant:ident => $name:literal),* $(,)? })*) => (
+        $(
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum $token {
+            $($variant,)*
+            Custom(&'static str),
+        }
+
+        impl $token {
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                    Self::Custom(name) => *name,
+                }
+            }
+
+            /// CSS custom property name, e.g. `--color-primary`.
+            pub fn property(&self) -> String {
+                [concat!("--", $prefix, "-"), self.name()].concat()
+            }
+
+            /// CSS value referencing the token, e.g. `var(--color-primary)`.
+            pub fn var(&self) -> String {
+                ["var(", &self.property(), ")"].concat()
+            }
+        }
+        )*
+    )
+}
+
+make_tokens! {
+    ColorToken("color") {
+        Background => "background",
+        Surface => "surface",
+        Text => "text",
+        TextMuted => "text-muted",
+        Primary => "primary",
+        OnPrimary => "on-primary",
+        Secondary => "secondary",
+        OnSecondary => "on-secondary",
+        Border => "border",
+        Focus => "focus",
+        Success => "success",
+        Warning => "warning",
+        Danger => "danger",
+    }
+    SpaceToken("space") {
+        ExtraSmall => "xs",
+        Small => "s",
+        Medium => "m",
+        Large => "l",
+        ExtraLarge => "xl",
+    }
+    RadiusToken("radius") {
+        Small => "s",
+        Medium => "m",
+        Large => "l",
+    }
+    FontToken("font") {
+        Body => "body",
+        Heading => "heading",
+        Mono => "mono",
+    }
+    FontSizeToken("font-size") {
+        ExtraSmall => "xs",
+        Small => "s",
+        Medium => "m",
+        Large => "l",
+        ExtraLarge => "xl",
+    }
+}
+
+/// Style builders set the color to `var(--color-*)`,
+/// `into_color` returns the color of the current theme.
+impl IntoColor for ColorToken {
+    fn into_color(self) -> Color {
+        THEME
+            .lock_ref()
+            .colors
+            .get(&self)
+            .cloned()
+            .unwrap_or_else(|| oklch().into_color())
+    }
+
+    fn into_color_string(self) -> String {
+        self.var()
+    }
+}
+
+macro_rules! make_into_css_length_impls {
+    ($($token:ident),*) => (
+        $(
+        /// Style builders set the length to `var(--*)`, e.g. `Padding::all(SpaceToken::Medium)`.
+        impl IntoCssLength for $token {
+            fn into_css_length(self) -> Cow<'static, str> {
+                self.var().into()
+            }
+        }
+        )*
+    )
+}
+make_into_css_length_impls!(SpaceToken, RadiusToken, FontSizeToken);
+
+impl SpaceToken {
+    /// Pixels in the current theme, use with `_signal` style methods, e.g. `Padding::all_signal`.
+    pub fn signal(self) -> impl Signal<Item = u32> + Unpin {
+        THEME
+            .signal_ref(move |theme| theme.spaces.get(&self).copied().unwrap_or_default())
+            .dedupe()
+    }
+}
+
+impl RadiusToken {
+    /// Pixels in the current theme, use with `_signal` style methods, e.g. `RoundedCorners::all_signal`.
+    pub fn signal(self) -> impl Signal<Item = u32> + Unpin {
+        THEME
+            .signal_ref(move |theme| theme.radii.get(&self).copied().unwrap_or_default())
+            .dedupe()
+    }
+}
+
+impl FontSizeToken {
+    /// Pixels in the current theme, use with `_signal` style methods, e.g. `Font::size_signal`.
+    pub fn signal(self) -> impl Signal<Item = u32> + Unpin {
+        THEME
+            .signal_ref(move |theme| theme.font_sizes.get(&self).copied().unwrap_or_default())
+            .dedupe()
+    }
+}
+
+impl<'a> From<FontToken> for FontFamily<'a> {
+    fn from(token: FontToken) -> Self {
+        FontFamily::Token(token)
+    }
+}
+
+// ------ ColorScheme ------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum ColorScheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+// ------ Theme ------
+
+#[derive(Debug, Clone, Default)]
+pub struct Theme {
+    color_scheme: ColorScheme,
+    colors: BTreeMap<ColorToken, Color>,
+    spaces: BTreeMap<SpaceToken, u32>,
+    radii: BTreeMap<RadiusToken, u32>,
+    fonts: BTreeMap<FontToken, String>,
+    font_sizes: BTreeMap<FontSizeToken, u32>,
+}
+
+impl Theme {
+    /// `color_scheme` is applied to native controls and scrollbars (CSS property `color-scheme`).
+    pub fn new(color_scheme: ColorScheme) -> Self {
+        Self {
+            color_scheme,
+            ..Self::default()
+        }
+    }
+
+    pub fn color_scheme(mut self, color_scheme: ColorScheme) -> Self {
+        self.color_scheme = color_scheme;
+        self
+    }
+
+    /// E.g. `oklch().l(0.6).c(0.15).h(250)` or `hsluv!(250, 80, 50)`.
+    pub fn color(mut self, token: ColorToken, color: impl IntoColor) -> Self {
+        self.colors.insert(token, color.into_color());
+        self
+    }
+
+    pub fn space(mut self, token: SpaceToken, pixels: u32) -> Self {
+        self.spaces.insert(token, pixels);
+        self
+    }
+
+    pub fn radius(mut self, token: RadiusToken, pixels: u32) -> Self {
+        self.radii.insert(token, pixels);
+        self
+    }
+
+    pub fn font<'a>(
+        mut self,
+        token: FontToken,
+        family: impl IntoIterator<Item = FontFamily<'a>>,
+    ) -> Self {
+        let family = family
+            .into_iter()
+            .map(|family| family.into_cow_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.fonts.insert(token, family);
+        self
+    }
+
+    pub fn font_size(mut self, token: FontSizeToken, pixels: u32) -> Self {
+        self.font_sizes.insert(token, pixels);
+        self
+    }
+
+    // -- getters --
+
+    pub fn get_color_scheme(&self) -> ColorScheme {
+        self.color_scheme
+    }
+
+    pub fn get_color(&self, token: ColorToken) -> Option<&Color> {
+        self.colors.get(&token)
+    }
+
+    pub fn get_space(&self, token: SpaceToken) -> Option<u32> {
+        self.spaces.get(&token).copied()
+    }
+
+    pub fn get_radius(&self, token: RadiusToken) -> Option<u32> {
+        self.radii.get(&token).copied()
+    }
+
+    pub fn get_font(&self, token: FontToken) -> Option<&str> {
+        self.fonts.get(&token).map(String::as_str)
+    }
+
+    pub fn get_font_size(&self, token: FontSizeToken) -> Option<u32> {
+        self.font_sizes.get(&token).copied()
+    }
+
+    /// Custom properties with values, e.g. `("--space-m", "12px")`, and `color-scheme`.
+    pub fn css_properties(&self) -> Vec<(String, String)> {
+        let color_scheme: &str = self.color_scheme.into();
+        iter::once(("color-scheme".to_owned(), color_scheme.to_owned()))
+            .chain(
+                self.colors
+                    .iter()
+                    .map(|(token, color)| (token.property(), color.clone().into_color_string())),
+            )
+            .chain(
+                self.spaces
+                    .iter()
+                    .map(|(token, pixels)| (token.property(), px(*pixels).into_owned())),
+            )
+            .chain(
+                self.radii
+                    .iter()
+                    .map(|(token, pixels)| (token.property(), px(*pixels).into_owned())),
+            )
+            .chain(
+                self.fonts
+                    .iter()
+                    .map(|(token, family)| (token.property(), family.clone())),
+            )
+            .chain(
+                self.font_sizes
+                    .iter()
+                    .map(|(token, pixels)| (token.property(), px(*pixels).into_owned())),
+            )
+            .collect()
+    }
+}
+
+// ------ current theme ------
+
+/// Applies the theme, it replaces the theme set by `set_theme_signal`.
+pub fn set_theme(theme: Theme) {
+    THEME_SIGNAL_TASK.with_borrow_mut(Option::take);
+    THEME.set(theme);
+}
+
+/// Switches themes, e.g. on a toggle or according to `prefers_color_scheme_signal`.
+pub fn set_theme_signal(theme: impl Signal<Item = Theme> + Unpin + 'static) {
+    let task = Task::start_droppable(theme.for_each_sync(|theme| THEME.set(theme)));
+    THEME_SIGNAL_TASK.with_borrow_mut(|old_task| *old_task = Some(task));
+}
+
+/// Follows the color scheme preferred by the user's system or browser.
+pub fn set_theme_by_color_scheme(light: Theme, dark: Theme) {
+    set_theme_signal(
+        prefers_color_scheme_signal().map(move |color_scheme| match color_scheme {
+            ColorScheme::Light => light.clone(),
+            ColorScheme::Dark => dark.clone(),
+        }),
+    )
+}
+
+pub fn theme_signal() -> impl Signal<Item = Theme> + Unpin {
+    THEME.signal_cloned()
+}
+
+pub fn current_theme() -> Theme {
+    THEME.get_cloned()
+}
+
+/// `prefers-color-scheme` media feature.
+pub fn prefers_color_scheme_signal() -> impl Signal<Item = ColorScheme> + Unpin {
+    media_query_signal("(prefers-color-scheme: dark)").map(|dark| {
+        if dark {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
+        }
+    })
+}
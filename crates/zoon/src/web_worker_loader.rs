@@ -18,7 +18,7 @@ impl WebWorkerLoader {
         } else {
             format!("/_api/web_workers/{crate_name}/pkg/{crate_name}.js")
         };
-        let js_url = web_sys::Url::new_with_base(&js_url, &current_href)
+        let js_url = web_sys::Url::new_with_base(&with_base_path(&js_url), &current_href)
             .expect_throw("Failed to create URL for Web Worker Javascript")
             .to_string();
 
@@ -27,7 +27,7 @@ impl WebWorkerLoader {
         } else {
             format!("/_api/web_workers/{crate_name}/pkg/{crate_name}_bg.wasm")
         };
-        let wasm_url = web_sys::Url::new_with_base(&wasm_url, &current_href)
+        let wasm_url = web_sys::Url::new_with_base(&with_base_path(&wasm_url), &current_href)
             .expect_throw("Failed to create URL for Web Worker Wasm")
             .to_string();
 
@@ -12,9 +12,9 @@ use zoon::{eprintln, println, *};
 static DROP_ZONE_ACTIVE: Lazy<Mutable<bool>> = lazy::default();
 static COMPONENT_SAID: Lazy<Mutable<Option<String>>> = lazy::default();
 
-async fn load_and_use_component(file_list: web_sys::FileList) -> anyhow::Result<()> {
-    let file_bytes = file_list
-        .get(0)
+async fn load_and_use_component(files: Vec<web_sys::File>) -> anyhow::Result<()> {
+    let file_bytes = files
+        .first()
         .ok_or_else(|| anyhow!("failed to get the dropped file"))?
         .apply(|file| JsFuture::from(file.array_buffer()))
         .await
@@ -93,53 +93,17 @@ fn drop_zone() -> impl Element {
         .s(RoundedCorners::all(30))
         .s(Borders::all(Border::new().color(color!("Green")).width(2)))
         .s(Background::new().color_signal(DROP_ZONE_ACTIVE.signal().map_true(|| color!("DarkGreen"))))
-        // @TODO refactor with a new MoonZoon ability
-        .update_raw_el(|raw_el| {
-            raw_el
-                .event_handler_with_options(
-                    EventOptions::new().preventable(),
-                    |event: events::DragEnter| {
-                        event.stop_propagation();
-                        event.prevent_default();
-                        DROP_ZONE_ACTIVE.set_neq(true);
-                    },
-                )
-                .event_handler_with_options(
-                    EventOptions::new().preventable(),
-                    |event: events::DragOver| {
-                        event.stop_propagation();
-                        event.prevent_default();
-                        event.data_transfer().unwrap_throw().set_drop_effect("copy");
-                    },
-                )
-                .event_handler_with_options(
-                    EventOptions::new().preventable(),
-                    |event: events::DragLeave| {
-                        event.stop_propagation();
-                        event.prevent_default();
-                        DROP_ZONE_ACTIVE.set_neq(false);
-                    },
-                )
-                .event_handler_with_options(
-                    EventOptions::new().preventable(),
-                    |event: events::Drop| {
-                        event.stop_propagation();
-                        event.prevent_default();
-                        DROP_ZONE_ACTIVE.set_neq(false);
-                        let file_list = event.data_transfer().unwrap_throw().files().unwrap_throw();
-                        Task::start(async move {
-                            if let Err(error) = load_and_use_component(file_list).await {
-                                eprintln!("{error:#}");
-                            }
-                        });
-                    },
-                )
+        .on_drag_hovered_change(|hovered| DROP_ZONE_ACTIVE.set_neq(hovered))
+        .on_files_drop(|files| {
+            Task::start(async move {
+                if let Err(error) = load_and_use_component(files).await {
+                    eprintln!("{error:#}");
+                }
+            });
         })
         .child(
             El::new()
                 .s(Align::center())
-                // @TODO the new ability shouldn't fire `dragleave` on moving to a child
-                .pointer_handling(PointerHandling::none())
                 .child("Drop Wasm component here"),
         )
 }